@@ -20,9 +20,7 @@ pub async fn compile(State(state): State<AppState>, Json(req): Json<CompileReque
         Some("workflow") => signia_plugins::builtin::config::schema_detect::DetectedKind::Workflow,
         Some("openapi") => signia_plugins::builtin::config::schema_detect::DetectedKind::OpenApi,
         Some(_) => return Err(ApiError::BadRequest("unknown kind".to_string())),
-        None => signia_plugins::builtin::config::schema_detect::detect_input_kind(&canonical)
-            .map_err(|e| ApiError::BadRequest(e.to_string()))?
-            .kind,
+        None => signia_plugins::builtin::config::schema_detect::detect_input_kind(&canonical).kind,
     };
 
     // 3) Compile via plugin into IR (schema-like JSON) and metadata.
@@ -50,8 +48,7 @@ pub async fn compile(State(state): State<AppState>, Json(req): Json<CompileReque
     };
 
     let plugin = state.plugins.get(plugin_id).ok_or_else(|| ApiError::Internal(format!("plugin not found: {plugin_id}")))?;
-    plugin
-        .execute(&signia_plugins::plugin::PluginInput::Pipeline(&mut ctx))
+    signia_plugins::plugin::execute_pipeline(plugin.plugin.as_ref(), &mut ctx)
         .map_err(|e| ApiError::BadRequest(e.to_string()))?;
 
     let ir_value = serde_json::to_value(&ctx.ir).map_err(|e| ApiError::Internal(e.to_string()))?;
@@ -60,15 +57,15 @@ pub async fn compile(State(state): State<AppState>, Json(req): Json<CompileReque
 
     // 4) Create manifest/proof (deterministic hashes)
     let schema_bytes = serde_json::to_vec(&schema_json).map_err(|e| ApiError::Internal(e.to_string()))?;
-    let schema_id = state.store.put_object_bytes(&schema_bytes).map_err(|e| ApiError::Internal(e.to_string()))?;
+    let schema_id = state.store.put_object(&schema_bytes, "schema").map_err(|e| ApiError::Internal(e.to_string()))?;
 
     let manifest = build_manifest(&canonical, &schema_id, input_key);
     let manifest_bytes = serde_json::to_vec(&manifest).map_err(|e| ApiError::Internal(e.to_string()))?;
-    let manifest_id = state.store.put_object_bytes(&manifest_bytes).map_err(|e| ApiError::Internal(e.to_string()))?;
+    let manifest_id = state.store.put_object(&manifest_bytes, "manifest").map_err(|e| ApiError::Internal(e.to_string()))?;
 
     let proof = build_proof(&canonical, &schema_id, &manifest_id);
     let proof_bytes = serde_json::to_vec(&proof).map_err(|e| ApiError::Internal(e.to_string()))?;
-    let proof_id = state.store.put_object_bytes(&proof_bytes).map_err(|e| ApiError::Internal(e.to_string()))?;
+    let proof_id = state.store.put_object(&proof_bytes, "proof").map_err(|e| ApiError::Internal(e.to_string()))?;
 
     Ok(Json(CompileResponse {
         kind: input_key.to_string(),
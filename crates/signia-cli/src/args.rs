@@ -1,5 +1,7 @@
 use clap::{Parser, Subcommand};
 
+use crate::io::export::ArchiveFormat;
+
 #[derive(Parser, Debug, Clone)]
 #[command(name = "signia", version, about = "SIGNIA CLI")]
 pub struct Cli {
@@ -29,17 +31,71 @@ pub enum Command {
         /// Output directory to write schema/manifest/proof.
         #[arg(long, default_value = "./out")]
         out: String,
+
+        /// Verify the emitted bundle after writing it; fail if verification fails.
+        #[arg(long, alias = "verify-after-compile")]
+        verify: bool,
+
+        /// Also write the bundle as a single reproducible archive (`<out>/bundle.<tar|zip>`).
+        #[arg(long, value_enum)]
+        archive: Option<ArchiveFormat>,
+
+        /// Detect, run the plugin and validate the IR, then print stats and
+        /// diagnostics without touching the store or writing any files.
+        #[arg(long, conflicts_with_all = ["verify", "archive"])]
+        dry_run: bool,
+
+        /// Manifest creation time (RFC 3339, e.g. 1970-01-01T00:00:00Z).
+        /// Defaults to `SOURCE_DATE_EPOCH`, else the Unix epoch; never the wall clock.
+        #[arg(long, value_name = "ISO8601", conflicts_with = "dry_run")]
+        created_at: Option<String>,
+
+        /// Also write the plugin's IR graph, before schema emission, as JSON to FILE.
+        #[arg(long, value_name = "FILE", conflicts_with = "dry_run")]
+        emit_ir: Option<String>,
+
+        /// Test aid: compile N times in-process and fail unless every run is
+        /// byte-identical, before anything is stored or written.
+        #[arg(long, value_name = "N", hide = true, conflicts_with = "dry_run",
+              value_parser = clap::value_parser!(u32).range(2..))]
+        assert_deterministic: Option<u32>,
+
+        /// Builtin plugin configuration (`BuiltinConfig`) as a JSON or YAML file,
+        /// e.g. `{"repo": {"max_files": 1000, "exclude": ["vendor/**"]}}`.
+        #[arg(long, value_name = "FILE")]
+        config: Option<String>,
+
+        /// Also load external WASM plugins, laid out as for `plugins --plugins-dir`.
+        #[arg(long, value_name = "PATH")]
+        plugins_dir: Option<String>,
+
+        /// Run this registered plugin instead of the builtin for the kind,
+        /// e.g. one loaded with `--plugins-dir`. It must support the kind.
+        #[arg(long, value_name = "ID")]
+        plugin: Option<String>,
     },
 
-    /// Verify a Merkle inclusion proof.
+    /// Verify a Merkle inclusion proof, or a whole bundle with `--archive` or `--bundle`.
     Verify {
-        #[arg(long)]
-        root: String,
-        #[arg(long)]
-        leaf: String,
+        #[arg(long, required_unless_present_any = ["archive", "bundle"])]
+        root: Option<String>,
+        #[arg(long, required_unless_present_any = ["archive", "bundle"])]
+        leaf: Option<String>,
         /// Proof JSON file (MerkleProof structure).
-        #[arg(long)]
-        proof: String,
+        #[arg(long, required_unless_present_any = ["archive", "bundle"])]
+        proof: Option<String>,
+        /// Bundle archive (tar or zip) written by `compile --archive`.
+        #[arg(long, conflicts_with_all = ["root", "leaf", "proof", "bundle"])]
+        archive: Option<String>,
+        /// Bundle directory (schema/manifest/proof.json) written by `compile --out`.
+        #[arg(long, conflicts_with_all = ["root", "leaf", "proof"])]
+        bundle: Option<String>,
+    },
+
+    /// Print the bundle descriptor (artifact ids and file digests) of a bundle directory.
+    BundleInfo {
+        /// Bundle directory (schema/manifest/proof.json) written by `compile --out`.
+        dir: String,
     },
 
     /// Fetch an artifact from the local store by object id.
@@ -49,8 +105,24 @@ pub enum Command {
         to: Option<String>,
     },
 
+    /// Delete store objects not reachable from the given roots.
+    Gc {
+        /// Root object ids to keep (e.g. proof or manifest ids); references are followed.
+        #[arg(required = true)]
+        roots: Vec<String>,
+
+        /// List deletion candidates without deleting anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// List available plugins and versions.
-    Plugins,
+    Plugins {
+        /// Also load external WASM plugins: one subdirectory per plugin holding
+        /// a `*.wasm` module and its `plugin.json` spec.
+        #[arg(long, value_name = "PATH")]
+        plugins_dir: Option<String>,
+    },
 
     /// Run environment checks.
     Doctor,
@@ -64,5 +136,8 @@ pub enum Command {
         /// Optional object id to publish (manifest or schema).
         #[arg(long)]
         id: Option<String>,
+        /// Registry program id (base58). Defaults to the placeholder id.
+        #[arg(long)]
+        program_id: Option<String>,
     },
 }
@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use signia_core::determinism::canonical_json::to_canonical_bytes;
+use signia_core::determinism::hashing::hash_proof_v1_hex;
+use signia_core::model::v1::{BundleInfoV1, ProofV1};
+use signia_store::objects::{object_id, parse_object_id, HashAlg};
+
+use crate::io::export::sha256_hex;
+use crate::output;
+
+/// Print the `BundleInfoV1` of a bundle directory written by `compile --out`.
+pub async fn run(dir: &str) -> Result<()> {
+    let info = read_bundle_info(Path::new(dir))?;
+    output::print(&info)?;
+    Ok(())
+}
+
+/// Recompute the bundle descriptor from `schema.json`, `manifest.json` and
+/// `proof.json` in `dir`.
+///
+/// Object ids are derived the way `compile` stores the artifacts (canonical JSON),
+/// using the id algorithm recorded in `bundle.json` if there is one, else the
+/// store default. The proof hash is `hash_proof_v1_hex` of the proof. A
+/// `bundle.json` that disagrees with the artifacts is an error; one without a
+/// proof hash is accepted.
+pub fn read_bundle_info(dir: &Path) -> Result<BundleInfoV1> {
+    let recorded = match fs::read(dir.join("bundle.json")) {
+        Ok(bytes) => Some(
+            serde_json::from_slice::<BundleInfoV1>(&bytes).map_err(|e| anyhow!("invalid bundle.json: {e}"))?,
+        ),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => return Err(anyhow!("bundle.json: {e}")),
+    };
+    let alg = match &recorded {
+        Some(r) => parse_object_id(&r.schema_id)?.0,
+        None => HashAlg::default(),
+    };
+
+    let mut ids = Vec::with_capacity(3);
+    let mut hashes = BTreeMap::new();
+    let mut proof_hash = String::new();
+    for name in ["schema.json", "manifest.json", "proof.json"] {
+        let bytes = fs::read(dir.join(name)).map_err(|e| anyhow!("{name}: {e}"))?;
+        let value: serde_json::Value =
+            serde_json::from_slice(&bytes).map_err(|e| anyhow!("{name} is not valid JSON: {e}"))?;
+        ids.push(object_id(alg, &to_canonical_bytes(&value)?)?);
+        if name == "proof.json" {
+            let proof: ProofV1 =
+                serde_json::from_value(value).map_err(|e| anyhow!("{name} is not a v1 proof: {e}"))?;
+            proof_hash = hash_proof_v1_hex(&proof)?;
+        }
+        hashes.insert(name.to_string(), sha256_hex(&bytes));
+    }
+    let [schema_id, manifest_id, proof_id]: [String; 3] =
+        ids.try_into().map_err(|_| anyhow!("expected three artifact ids"))?;
+    let info = BundleInfoV1::from_bundle(schema_id, manifest_id, proof_id, hashes).with_proof_hash(proof_hash);
+
+    if let Some(recorded) = recorded {
+        let mut stale = Vec::new();
+        if recorded.version != info.version {
+            stale.push("version");
+        }
+        for (field, (a, b)) in ["schemaId", "manifestId", "proofId"]
+            .into_iter()
+            .zip(recorded.artifact_ids().into_iter().zip(info.artifact_ids()))
+        {
+            if a != b {
+                stale.push(field);
+            }
+        }
+        if recorded.proof_hash.is_some() && recorded.proof_hash != info.proof_hash {
+            stale.push("proofHash");
+        }
+        if recorded.hashes != info.hashes {
+            stale.push("hashes");
+        }
+        if !stale.is_empty() {
+            return Err(anyhow!("bundle.json does not match the bundle artifacts ({})", stale.join(", ")));
+        }
+    }
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::test_support::compile_into;
+    use crate::io::export;
+
+    fn recorded(dir: &Path) -> BundleInfoV1 {
+        serde_json::from_slice(&fs::read(dir.join("bundle.json")).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn recomputed_info_matches_compile() {
+        let td = tempfile::TempDir::new().unwrap();
+        let out = compile_into(td.path(), None).await;
+        let written = recorded(&out);
+        assert_eq!(read_bundle_info(&out).unwrap(), written);
+
+        // The proof id is the content digest like the other ids; the
+        // domain-separated proof hash is recorded separately.
+        let proof: ProofV1 = serde_json::from_slice(&fs::read(out.join("proof.json")).unwrap()).unwrap();
+        let canonical = to_canonical_bytes(&serde_json::to_value(&proof).unwrap()).unwrap();
+        assert_eq!(written.proof_id, object_id(HashAlg::Sha256, &canonical).unwrap());
+        assert_eq!(written.proof_hash, Some(hash_proof_v1_hex(&proof).unwrap()));
+        assert!(!written.proof_id.ends_with(written.proof_hash.as_deref().unwrap()));
+
+        // Without bundle.json the descriptor is recomputed from the artifacts alone.
+        fs::remove_file(out.join("bundle.json")).unwrap();
+        assert_eq!(read_bundle_info(&out).unwrap(), written);
+
+        // Descriptors without a proof hash are still accepted.
+        let mut legacy = written.clone();
+        legacy.proof_hash = None;
+        export::write_bundle_info(&out, &legacy).unwrap();
+        assert_eq!(read_bundle_info(&out).unwrap(), written);
+    }
+
+    #[tokio::test]
+    async fn stale_bundle_json_is_rejected() {
+        let td = tempfile::TempDir::new().unwrap();
+        let out = compile_into(td.path(), None).await;
+        let mut proof: ProofV1 = serde_json::from_slice(&fs::read(out.join("proof.json")).unwrap()).unwrap();
+        proof.root = "0".repeat(64);
+        fs::write(out.join("proof.json"), serde_json::to_vec_pretty(&proof).unwrap()).unwrap();
+
+        let err = read_bundle_info(&out).unwrap_err().to_string();
+        assert!(err.contains("proofId") && err.contains("proofHash") && err.contains("hashes"), "{err}");
+        assert!(!err.contains("schemaId"), "{err}");
+    }
+}
@@ -1,9 +1,21 @@
+use std::cell::RefCell;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::Serialize;
+use signia_core::determinism::canonical_json::to_canonical_bytes;
+use signia_core::determinism::hashing::{hash_canonical_json_hex, hash_proof_v1_hex};
+use signia_core::errors::{SigniaError, SigniaResult};
+use signia_core::model::ir::IrGraph;
+use signia_core::pipeline::compile::{
+    compile_from_input, CompileReport, CompileRequest, InputSpec, IrProducer, LimitsSpec,
+};
+use signia_core::pipeline::context::{PipelineConfig, PipelineContext};
+use signia_core::pipeline::detect::{detect_input_kind, DetectedKind, DetectionResult};
+use signia_plugins::plugin::{HostCapabilities, PluginInputKind};
+use signia_plugins::registry::{PluginRegistry, PluginResolver, ResolvedPlugin};
 
 use crate::io::{export, input};
 use crate::output;
@@ -14,11 +26,55 @@ pub struct CompileOut {
     pub schema_id: String,
     pub manifest_id: String,
     pub proof_id: String,
+    /// Canonical proof hash (`hash_proof_v1_hex`), also recorded in `bundle.json`.
+    pub proof_hash: String,
     pub out_dir: String,
     pub metadata: BTreeMap<String, String>,
+    /// Non-fatal plugin diagnostics (e.g. warnings).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<String>,
+    /// Present when the kind was detected rather than given with `--kind`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detection: Option<DetectionResult>,
 }
 
-pub async fn run(store_root: &str, input_arg: &str, kind_hint: Option<&str>, out_dir: &str) -> Result<()> {
+/// The options of `compile`, as given on the command line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompileOptions<'a> {
+    /// Input path or URL.
+    pub input: &'a str,
+    /// `--kind`; detected from the input when absent.
+    pub kind: Option<&'a str>,
+    /// `--out` directory for the bundle files.
+    pub out: &'a str,
+    /// `--verify`: verify the written bundle.
+    pub verify: bool,
+    /// `--archive`: also write `<out>/bundle.<tar|zip>`.
+    pub archive: Option<export::ArchiveFormat>,
+    /// `--assert-deterministic`: compile this many times and compare.
+    pub assert_deterministic: Option<u32>,
+    /// `--created-at` (see `export::resolve_created_at`).
+    pub created_at: Option<&'a str>,
+    /// `--emit-ir` file.
+    pub emit_ir: Option<&'a str>,
+    /// `--config`, `--plugins-dir` and `--plugin`.
+    pub plugins: PluginSelection<'a>,
+}
+
+pub async fn run(store_root: &str, opts: &CompileOptions<'_>) -> Result<()> {
+    let CompileOptions {
+        input: input_arg,
+        kind: kind_hint,
+        out: out_dir,
+        verify,
+        archive,
+        assert_deterministic: assert_runs,
+        created_at,
+        emit_ir,
+        plugins,
+    } = *opts;
+    let created_at = export::resolve_created_at(created_at)?;
+
     let pb = ProgressBar::new_spinner();
     pb.set_style(ProgressStyle::with_template("{spinner} {msg}").unwrap());
     pb.enable_steady_tick(std::time::Duration::from_millis(80));
@@ -33,58 +89,52 @@ pub async fn run(store_root: &str, input_arg: &str, kind_hint: Option<&str>, out
     let store_cfg = signia_store::StoreConfig::local_dev(PathBuf::from(store_root))?;
     let store = signia_store::Store::open(store_cfg)?;
 
-    pb.set_message("loading plugins");
-    let mut reg = signia_plugins::registry::PluginRegistry::default();
-    signia_plugins::builtin::repo::register(&mut reg);
-    signia_plugins::builtin::dataset::register(&mut reg);
-    signia_plugins::builtin::workflow::register(&mut reg);
-    signia_plugins::builtin::api::register(&mut reg);
-    signia_plugins::builtin::spec::register(&mut reg);
-
     pb.set_message("detecting kind");
-    let detected = match kind_hint {
-        Some("repo") => signia_plugins::builtin::config::schema_detect::DetectedKind::Repo,
-        Some("dataset") => signia_plugins::builtin::config::schema_detect::DetectedKind::Dataset,
-        Some("workflow") => signia_plugins::builtin::config::schema_detect::DetectedKind::Workflow,
-        Some("openapi") => signia_plugins::builtin::config::schema_detect::DetectedKind::OpenApi,
-        Some(_) => return Err(anyhow!("unknown kind hint")),
-        None => signia_plugins::builtin::config::schema_detect::detect_input_kind(&canonical)?.kind,
-    };
+    let (kind_key, builtin_id, detection) = resolve_kind(kind_hint, &canonical)?;
+    let reg = plugins.registry()?;
+    let plugin_id = select_plugin(&reg, kind_key, builtin_id, plugins.plugin)?;
 
-    let (kind_key, plugin_id) = match detected {
-        signia_plugins::builtin::config::schema_detect::DetectedKind::Repo => ("repo", "builtin.repo"),
-        signia_plugins::builtin::config::schema_detect::DetectedKind::Dataset => ("dataset", "builtin.dataset"),
-        signia_plugins::builtin::config::schema_detect::DetectedKind::Workflow => ("workflow", "builtin.workflow"),
-        signia_plugins::builtin::config::schema_detect::DetectedKind::OpenApi => ("openapi", "builtin.api.openapi"),
-        signia_plugins::builtin::config::schema_detect::DetectedKind::Unknown => return Err(anyhow!("unable to detect input kind")),
-    };
+    if let Some(runs) = assert_runs {
+        pb.set_message("checking determinism");
+        assert_deterministic(runs, || artifact_bytes(&reg, kind_key, plugin_id, &canonical, &created_at))?;
+    }
 
     pb.set_message("compiling");
-    let mut ctx = signia_core::pipeline::context::PipelineContext::new(
-        signia_core::pipeline::context::PipelineConfig::default(),
-    );
-    ctx.inputs.insert(kind_key.to_string(), canonical.clone());
-
-    let plugin = reg.get(plugin_id).ok_or_else(|| anyhow!("plugin not found: {plugin_id}"))?;
-    plugin.execute(&signia_plugins::plugin::PluginInput::Pipeline(&mut ctx))?;
-
-    let ir_value = serde_json::to_value(&ctx.ir)?;
-    let schema_json = signia_core::determinism::canonical_json::canonicalize_json(&ir_value)?;
+    let Compiled { report, ctx } = compile_input(&reg, kind_key, plugin_id, &canonical, &created_at)?;
+    let diagnostics = diagnostic_lines(&ctx);
+    if let Some(path) = emit_ir {
+        write_ir(path, &ctx)?;
+    }
+    let bundle = report.bundle;
+    let proof = bundle.proof.ok_or_else(|| anyhow!("compile produced no proof"))?;
+    let schema_json = serde_json::to_value(&bundle.schema)?;
+    let manifest = serde_json::to_value(&bundle.manifest)?;
+    let proof_json = serde_json::to_value(&proof)?;
 
+    // Objects are stored as canonical JSON, so schema and manifest ids are the
+    // digests the manifest and proof record for them. The proof's
+    // domain-separated hash is not a content digest; it is recorded alongside.
     pb.set_message("storing artifacts");
-    let schema_bytes = serde_json::to_vec(&schema_json)?;
-    let schema_id = store.put_object_bytes(&schema_bytes)?;
-
-    let manifest = export::build_manifest(&canonical, &schema_id, kind_key);
-    let manifest_bytes = serde_json::to_vec(&manifest)?;
-    let manifest_id = store.put_object_bytes(&manifest_bytes)?;
-
-    let proof = export::build_proof(&canonical, &schema_id, &manifest_id)?;
-    let proof_bytes = serde_json::to_vec(&proof)?;
-    let proof_id = store.put_object_bytes(&proof_bytes)?;
+    let schema_id = store.put_object(&to_canonical_bytes(&schema_json)?, "schema")?;
+    let manifest_id = store.put_object(&to_canonical_bytes(&manifest)?, "manifest")?;
+    let proof_id = store.put_object(&to_canonical_bytes(&proof_json)?, "proof")?;
+    let proof_hash = hash_proof_v1_hex(&proof)?;
 
     pb.set_message("writing bundle");
-    export::write_bundle(out_dir, &schema_json, &manifest, &proof)?;
+    export::write_bundle(out_dir, &schema_json, &manifest, &proof_json)?;
+    let bundle_info =
+        export::build_bundle_info(&schema_id, &manifest_id, &proof_id, &schema_json, &manifest, &proof_json)?
+            .with_proof_hash(&proof_hash);
+    export::write_bundle_info(out_dir, &bundle_info)?;
+    if let Some(format) = archive {
+        let path = PathBuf::from(out_dir).join(format!("bundle.{}", format.extension()));
+        export::write_bundle_archive(&path, &schema_json, &manifest, &proof_json, format)?;
+    }
+
+    if verify {
+        pb.set_message("verifying bundle");
+        self_verify(&schema_json, &manifest, &proof_json)?;
+    }
 
     pb.finish_and_clear();
 
@@ -93,9 +143,542 @@ pub async fn run(store_root: &str, input_arg: &str, kind_hint: Option<&str>, out
         schema_id,
         manifest_id,
         proof_id,
+        proof_hash,
         out_dir: out_dir.to_string(),
         metadata: ctx.metadata,
+        diagnostics,
+        detection,
+    };
+    output::print(&out)?;
+    Ok(())
+}
+
+/// Output of `compile --dry-run`: what a compile would produce, without side effects.
+#[derive(Debug, Serialize)]
+pub struct DryRunOut {
+    pub dry_run: bool,
+    pub kind: String,
+    pub nodes: usize,
+    pub edges: usize,
+    /// Size of the schema that would be stored.
+    pub schema_bytes: usize,
+    pub diagnostics: Vec<String>,
+    pub metadata: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detection: Option<DetectionResult>,
+}
+
+/// Validate and preview a compile: the bundle is compiled in memory, but the
+/// store is not opened and nothing is written. Only `input`, `kind` and
+/// `plugins` of `opts` are used.
+pub async fn run_dry(opts: &CompileOptions<'_>) -> Result<()> {
+    let reg = opts.plugins.registry()?;
+    let input_json = input::resolve_to_json(opts.input).await?;
+    let canonical = signia_core::determinism::canonical_json::canonicalize_json(&input_json)?;
+    let (kind_key, builtin_id, detection) = resolve_kind(opts.kind, &canonical)?;
+    let plugin_id = select_plugin(&reg, kind_key, builtin_id, opts.plugins.plugin)?;
+
+    let created_at = export::resolve_created_at(None)?;
+    let Compiled { report, ctx } = compile_input(&reg, kind_key, plugin_id, &canonical, &created_at)?;
+
+    let out = DryRunOut {
+        dry_run: true,
+        kind: kind_key.to_string(),
+        nodes: report.stats.entities,
+        edges: report.stats.edges,
+        schema_bytes: report.stats.schema_bytes,
+        diagnostics: diagnostic_lines(&ctx),
+        metadata: ctx.metadata,
+        detection,
     };
     output::print(&out)?;
     Ok(())
 }
+
+/// The plugin options of `compile`: `--config`, `--plugins-dir` and `--plugin`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PluginSelection<'a> {
+    /// `BuiltinConfig` file for the builtin plugins.
+    pub config: Option<&'a str>,
+    /// Directory of external WASM plugins to register as well.
+    pub plugins_dir: Option<&'a str>,
+    /// Registered plugin id to run instead of the builtin for the kind.
+    pub plugin: Option<&'a str>,
+}
+
+impl PluginSelection<'_> {
+    /// The configured builtins plus any external plugins.
+    fn registry(&self) -> Result<PluginRegistry> {
+        let config = super::plugins::load_config(self.config.map(Path::new))?;
+        super::plugins::registry(&config, self.plugins_dir.map(Path::new))
+    }
+}
+
+/// The plugin to compile `kind_key` with: `requested` (`--plugin`) if given,
+/// else the builtin for the kind.
+///
+/// A requested plugin must support the kind and run on a pipeline context.
+fn select_plugin<'a>(
+    reg: &PluginRegistry,
+    kind_key: &str,
+    builtin_id: &'a str,
+    requested: Option<&'a str>,
+) -> Result<&'a str> {
+    let Some(id) = requested else {
+        return Ok(builtin_id);
+    };
+    let registered = reg.get(id).ok_or_else(|| anyhow!("--plugin: plugin not found: {id}"))?;
+    if !registered.plugin.supports(kind_key) {
+        return Err(anyhow!("--plugin: {id} does not support kind {kind_key}"));
+    }
+    if !registered.plugin.accepts(PluginInputKind::Pipeline) {
+        return Err(anyhow!("--plugin: {id} does not accept pipeline input, so it cannot compile"));
+    }
+    Ok(id)
+}
+
+/// Resolve `--kind` (or detect it) to the kind key and builtin plugin id.
+///
+/// The detection result is returned when detection was used.
+fn resolve_kind(
+    kind_hint: Option<&str>,
+    canonical: &serde_json::Value,
+) -> Result<(&'static str, &'static str, Option<DetectionResult>)> {
+    let mut detection = None;
+    let detected = match kind_hint {
+        Some("repo") => DetectedKind::Repo,
+        Some("dataset") => DetectedKind::Dataset,
+        Some("workflow") => DetectedKind::Workflow,
+        Some("openapi") => DetectedKind::OpenApi,
+        Some(_) => return Err(anyhow!("unknown kind hint")),
+        None => {
+            let res = detect_input_kind(canonical);
+            let kind = res.kind;
+            detection = Some(res);
+            kind
+        }
+    };
+
+    let (kind_key, plugin_id) = match detected {
+        DetectedKind::Repo => ("repo", "builtin.repo"),
+        DetectedKind::Dataset => ("dataset", "builtin.dataset"),
+        DetectedKind::Workflow => ("workflow", "builtin.workflow"),
+        DetectedKind::OpenApi => ("openapi", "builtin.api.openapi"),
+        DetectedKind::Unknown => {
+            let reasons = detection.map(|d| d.reasons.join("; ")).unwrap_or_default();
+            return Err(anyhow!("unable to detect input kind ({reasons}); pass --kind"));
+        }
+    };
+
+    Ok((kind_key, plugin_id, detection))
+}
+
+/// One in-memory compile: the v1 report and the context the plugin ran in.
+#[derive(Debug)]
+pub struct Compiled {
+    pub report: CompileReport,
+    /// IR before emission, plugin metadata and plugin diagnostics.
+    pub ctx: PipelineContext,
+}
+
+/// Compile canonical input with plugin `plugin_id` from `reg` into a v1 bundle.
+///
+/// `created_at` is an RFC 3339 timestamp (see `export::resolve_created_at`).
+/// The source is recorded by content hash only, so the bundle does not depend
+/// on where the input was read from.
+pub fn compile_input(
+    reg: &PluginRegistry,
+    kind_key: &str,
+    plugin_id: &str,
+    canonical: &serde_json::Value,
+    created_at: &str,
+) -> Result<Compiled> {
+    let resolved = PluginResolver::new(HostCapabilities::default()).resolve(reg, plugin_id, None)?;
+    compile_resolved(resolved, kind_key, canonical, created_at)
+}
+
+/// `compile_input` with an already resolved plugin; its capability evaluation
+/// is recorded in the manifest.
+fn compile_resolved(
+    resolved: ResolvedPlugin<'_>,
+    kind_key: &str,
+    canonical: &serde_json::Value,
+    created_at: &str,
+) -> Result<Compiled> {
+    let plugin_id = resolved.id.as_str().to_string();
+    let producer = PluginProducer { resolved, kind_key, ctx: RefCell::default(), error: RefCell::default() };
+
+    let report = compile_from_input(canonical, &producer, compile_request(kind_key, canonical, created_at)?, None);
+    if let Some(e) = producer.error.take() {
+        return Err(e);
+    }
+    let report = report?;
+    let ctx = producer.ctx.take().ok_or_else(|| anyhow!("plugin {plugin_id} did not run"))?;
+    Ok(Compiled { report, ctx })
+}
+
+/// The core compile request for one input.
+fn compile_request(kind_key: &str, canonical: &serde_json::Value, created_at: &str) -> Result<CompileRequest> {
+    let content_hash = hash_canonical_json_hex(canonical)?;
+    let locator = format!("artifact:/{kind_key}");
+    let name = canonical.get("name").and_then(|v| v.as_str()).unwrap_or(kind_key);
+
+    Ok(CompileRequest {
+        kind: kind_key.to_string(),
+        meta: serde_json::json!({
+            "name": name,
+            "createdAt": created_at,
+            "source": {"type": "input", "locator": locator, "contentHash": content_hash},
+            "normalization": {
+                "policyVersion": "v1",
+                "pathRoot": "artifact:/",
+                "newline": "lf",
+                "encoding": "utf-8",
+                "symlinks": "deny",
+                "network": "deny"
+            }
+        }),
+        created_at: created_at.to_string(),
+        labels: BTreeMap::new(),
+        inputs: vec![InputSpec { r#type: kind_key.to_string(), locator, digest: Some(content_hash) }],
+        outputs: vec![],
+        plugins: vec![],
+        limits: LimitsSpec::default(),
+        run_inference: false,
+        build_proof: true,
+    })
+}
+
+/// Adapts a resolved plugin to `IrProducer`, so `compile_from_input` records
+/// the plugin that actually ran.
+///
+/// The executed context is kept for the caller, and so is the plugin's own
+/// error, which is returned as is rather than through `SigniaError`.
+struct PluginProducer<'a> {
+    resolved: ResolvedPlugin<'a>,
+    kind_key: &'a str,
+    ctx: RefCell<Option<PipelineContext>>,
+    error: RefCell<Option<anyhow::Error>>,
+}
+
+impl IrProducer for PluginProducer<'_> {
+    fn id(&self) -> &str {
+        self.resolved.id.as_str()
+    }
+
+    fn name(&self) -> &str {
+        self.resolved.name()
+    }
+
+    fn version(&self) -> &str {
+        self.resolved.version()
+    }
+
+    fn fingerprint(&self) -> Option<String> {
+        Some(self.resolved.plugin.fingerprint())
+    }
+
+    fn granted_caps(&self) -> Option<Vec<String>> {
+        Some(self.resolved.evaluation.granted.clone())
+    }
+
+    fn produce(&self, input: &serde_json::Value) -> SigniaResult<IrGraph> {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert(self.kind_key.to_string(), input.clone());
+
+        let executed = signia_plugins::plugin::execute_pipeline(self.resolved.plugin, &mut ctx);
+        let ir = ctx.ir.clone();
+        *self.ctx.borrow_mut() = Some(ctx);
+        if let Err(e) = executed {
+            let err = SigniaError::invalid_argument(format!("plugin {} failed: {e:#}", self.id()));
+            *self.error.borrow_mut() = Some(e);
+            return Err(err);
+        }
+        ir.ok_or_else(|| SigniaError::invalid_argument(format!("plugin {} produced no IR", self.id())))
+    }
+}
+
+/// Serialized v1 bundle and plugin metadata of one in-memory compile.
+pub fn artifact_bytes(
+    reg: &PluginRegistry,
+    kind_key: &str,
+    plugin_id: &str,
+    canonical: &serde_json::Value,
+    created_at: &str,
+) -> Result<Vec<u8>> {
+    let Compiled { report, ctx } = compile_input(reg, kind_key, plugin_id, canonical, created_at)?;
+    let mut out = serde_json::to_vec(&report.bundle)?;
+    out.extend(serde_json::to_vec(&ctx.metadata)?);
+    Ok(out)
+}
+
+/// Run `compile_once` `runs` times and fail unless every run returns the same bytes.
+pub fn assert_deterministic<F>(runs: u32, mut compile_once: F) -> Result<()>
+where
+    F: FnMut() -> Result<Vec<u8>>,
+{
+    let first = compile_once()?;
+    for run in 2..=runs {
+        let next = compile_once()?;
+        if next != first {
+            let offset = first
+                .iter()
+                .zip(&next)
+                .position(|(a, b)| a != b)
+                .unwrap_or(first.len().min(next.len()));
+            return Err(anyhow!(
+                "nondeterministic compile: run {run} differs from run 1 at byte {offset} ({} vs {} bytes)",
+                next.len(),
+                first.len()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Write the context's IR graph to `path` as `IrGraph::to_canonical_json`.
+fn write_ir(path: &str, ctx: &PipelineContext) -> Result<()> {
+    let ir = ctx.ir.as_ref().ok_or_else(|| anyhow!("--emit-ir: plugin produced no IR"))?;
+    let bytes = serde_json::to_vec_pretty(&ir.to_canonical_json())?;
+    std::fs::write(path, bytes).map_err(|e| anyhow!("--emit-ir: writing {path}: {e}"))?;
+    Ok(())
+}
+
+/// One line per pipeline diagnostic: `<Level> <code>: <message>`.
+fn diagnostic_lines(ctx: &PipelineContext) -> Vec<String> {
+    ctx.diagnostics
+        .iter()
+        .map(|d| format!("{:?} {}: {}", d.level, d.code, d.message))
+        .collect()
+}
+
+/// Verify the in-memory bundle that was just written.
+///
+/// This is an integrity self-check for emission bugs: the artifacts must decode
+/// as v1 models and pass `verify_bundle` with default options.
+pub fn self_verify(schema: &serde_json::Value, manifest: &serde_json::Value, proof: &serde_json::Value) -> Result<()> {
+    let report = super::verify::verify_bundle_json(schema, manifest, proof).map_err(|e| anyhow!("self-verify: {e}"))?;
+    if !report.ok {
+        let errors = super::verify::error_findings(&report);
+        return Err(anyhow!("self-verify failed: {}", errors.join("; ")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo_input() -> serde_json::Value {
+        signia_core::determinism::canonical_json::canonicalize_json(
+            &serde_json::json!({"name": "demo", "files": [{"path": "README.md"}, {"path": "src/lib.rs"}]}),
+        )
+        .unwrap()
+    }
+
+    fn compiled() -> (serde_json::Value, serde_json::Value, serde_json::Value) {
+        let reg = crate::cmd::plugins::registry(&Default::default(), None).unwrap();
+        let Compiled { report, .. } =
+            compile_input(&reg, "repo", "builtin.repo", &repo_input(), "1970-01-01T00:00:00Z").unwrap();
+        (
+            serde_json::to_value(&report.bundle.schema).unwrap(),
+            serde_json::to_value(&report.bundle.manifest).unwrap(),
+            serde_json::to_value(report.bundle.proof.as_ref().unwrap()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn assert_deterministic_accepts_repeatable_compile() {
+        let reg = crate::cmd::plugins::registry(&Default::default(), None).unwrap();
+        let canonical = repo_input();
+        assert_deterministic(3, || artifact_bytes(&reg, "repo", "builtin.repo", &canonical, "1970-01-01T00:00:00Z"))
+            .unwrap();
+    }
+
+    #[test]
+    fn assert_deterministic_rejects_seeded_stub() {
+        // Stands in for a compile that leaks per-run state (e.g. a seeded HashMap).
+        let mut seed = 0u8;
+        let err = assert_deterministic(3, || {
+            seed += 1;
+            Ok(vec![b'{', seed, b'}'])
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("run 2 differs from run 1 at byte 1"), "{err}");
+    }
+
+    #[test]
+    fn compile_records_the_executed_plugin() {
+        let (schema, manifest, _) = compiled();
+        assert_eq!(schema["version"], "v1");
+        assert_eq!(schema["kind"], "repo");
+        assert_eq!(schema["meta"]["createdAt"], "1970-01-01T00:00:00Z");
+        assert_eq!(schema["entities"].as_array().unwrap().len(), 3);
+
+        let plugin = &manifest["plugins"][0];
+        assert_eq!(plugin["id"], "builtin.repo");
+        assert_eq!(plugin["version"], "0.1.0");
+        assert_eq!(manifest["inputs"][0]["digest"], schema["meta"]["source"]["contentHash"]);
+    }
+
+    #[test]
+    fn manifest_records_plugin_fingerprint() {
+        let reg = crate::cmd::plugins::registry(&Default::default(), None).unwrap();
+        let expected = reg.get("builtin.repo").unwrap().plugin.fingerprint();
+
+        let (_, manifest, _) = compiled();
+        assert_eq!(manifest["plugins"][0]["fingerprint"], expected.as_str());
+    }
+
+    #[test]
+    fn manifest_records_granted_capabilities() {
+        use signia_core::model::ir::IrNode;
+        use signia_plugins::plugin::{Plugin, PluginInput, PluginOutput};
+        use signia_plugins::spec::{evaluate_spec, PluginSpec};
+
+        struct NetworkPlugin;
+        impl Plugin for NetworkPlugin {
+            fn name(&self) -> &str {
+                "net"
+            }
+            fn version(&self) -> &str {
+                "0.1.0"
+            }
+            fn supports(&self, input_type: &str) -> bool {
+                input_type == "repo"
+            }
+            fn execute(&self, input: PluginInput<'_>) -> anyhow::Result<PluginOutput> {
+                let PluginInput::Pipeline(ctx) = input else {
+                    anyhow::bail!("net plugin requires pipeline input");
+                };
+                let mut ir = IrGraph::new();
+                ir.add_node(IrNode::new("repo", "net"))?;
+                ctx.ir = Some(ir);
+                Ok(PluginOutput::None)
+            }
+        }
+
+        let spec = PluginSpec::new("test.net", "Network Plugin", "0.1.0").support("repo").want("network", true);
+        let mut reg = PluginRegistry::new();
+        reg.register(spec.clone(), Box::new(NetworkPlugin)).unwrap();
+
+        let host = HostCapabilities { network: true, ..HostCapabilities::default() };
+        let granted = evaluate_spec(&spec, &host).granted;
+        assert_eq!(granted, ["network"]);
+
+        let resolved = PluginResolver::new(host).resolve(&reg, "test.net", None).unwrap();
+        let Compiled { report, .. } = compile_resolved(resolved, "repo", &repo_input(), "1970-01-01T00:00:00Z").unwrap();
+        assert_eq!(report.bundle.manifest.plugins[0].granted_caps, Some(granted));
+
+        // Builtins want nothing, so nothing is granted.
+        let (_, manifest, _) = compiled();
+        assert_eq!(manifest["plugins"][0]["grantedCaps"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn plugin_errors_are_returned_verbatim() {
+        let reg = crate::cmd::plugins::registry(&Default::default(), None).unwrap();
+        let input = serde_json::json!({"files": []});
+        let err = compile_input(&reg, "repo", "builtin.repo", &input, "1970-01-01T00:00:00Z").unwrap_err();
+        assert_eq!(err.to_string(), "repo.name missing or invalid");
+    }
+
+    #[test]
+    fn self_verify_accepts_compiled_bundle() {
+        let (schema, manifest, proof) = compiled();
+        self_verify(&schema, &manifest, &proof).unwrap();
+    }
+
+    #[test]
+    fn self_verify_rejects_corrupted_proof() {
+        let (schema, manifest, mut proof) = compiled();
+        proof["root"] = serde_json::Value::String("0".repeat(64));
+        let err = self_verify(&schema, &manifest, &proof).unwrap_err();
+        assert!(err.to_string().contains("proof.root.mismatch"));
+        assert!(err.to_string().contains(&format!("expected {}", "0".repeat(64))), "{err}");
+    }
+
+    #[test]
+    fn plugin_flag_selects_a_registered_plugin() {
+        use signia_plugins::plugin::{Plugin, PluginInput, PluginOutput};
+        use signia_plugins::spec::PluginSpec;
+
+        struct BytesPlugin;
+        impl Plugin for BytesPlugin {
+            fn name(&self) -> &str {
+                "bytes"
+            }
+            fn version(&self) -> &str {
+                "0.1.0"
+            }
+            fn supports(&self, input_type: &str) -> bool {
+                input_type == "repo"
+            }
+            fn accepts(&self, kind: PluginInputKind) -> bool {
+                kind == PluginInputKind::Bytes
+            }
+            fn execute(&self, _input: PluginInput<'_>) -> anyhow::Result<PluginOutput> {
+                Ok(PluginOutput::None)
+            }
+        }
+
+        let mut reg = crate::cmd::plugins::registry(&Default::default(), None).unwrap();
+        reg.register(PluginSpec::new("ext.bytes", "Bytes", "0.1.0").support("repo"), Box::new(BytesPlugin))
+            .unwrap();
+
+        let select = |id| select_plugin(&reg, "repo", "builtin.repo", id).map_err(|e| e.to_string());
+        assert_eq!(select(None).unwrap(), "builtin.repo");
+        assert_eq!(select(Some("builtin.repo")).unwrap(), "builtin.repo");
+        assert_eq!(select(Some("ext.missing")).unwrap_err(), "--plugin: plugin not found: ext.missing");
+        assert_eq!(select(Some("builtin.dataset")).unwrap_err(), "--plugin: builtin.dataset does not support kind repo");
+        assert!(select(Some("ext.bytes")).unwrap_err().contains("does not accept pipeline input"));
+    }
+
+    #[test]
+    fn config_file_changes_compiled_output() {
+        let td = tempfile::TempDir::new().unwrap();
+        let entities = |config: Option<&str>| {
+            let path = td.path().join("signia.yaml");
+            if let Some(config) = config {
+                std::fs::write(&path, config).unwrap();
+            }
+            let config = crate::cmd::plugins::load_config(config.map(|_| path.as_path())).unwrap();
+            let reg = crate::cmd::plugins::registry(&config, None).unwrap();
+            compile_input(&reg, "repo", "builtin.repo", &repo_input(), "1970-01-01T00:00:00Z")
+                .map(|c| c.report.stats.entities)
+        };
+
+        assert_eq!(entities(None).unwrap(), 3);
+        assert_eq!(entities(Some("repo:\n  exclude: [\"src/**\"]\n")).unwrap(), 2);
+        let err = entities(Some("repo:\n  max_files: 1\n")).unwrap_err();
+        assert!(err.to_string().contains("max_files 1"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn written_bundle_decodes_as_v1_and_verifies() {
+        use crate::io::{load_artifact, LoadedArtifact};
+
+        let td = tempfile::TempDir::new().unwrap();
+        let input = td.path().join("repo.json");
+        std::fs::write(&input, serde_json::to_vec(&repo_input()).unwrap()).unwrap();
+        let out = td.path().join("out");
+        let store = td.path().join("store");
+
+        let opts = CompileOptions {
+            input: input.to_str().unwrap(),
+            out: out.to_str().unwrap(),
+            verify: true,
+            created_at: Some("1970-01-01T00:00:00Z"),
+            ..CompileOptions::default()
+        };
+        run(store.to_str().unwrap(), &opts).await.unwrap();
+
+        let load = |name: &str| load_artifact(out.join(name)).unwrap();
+        let (LoadedArtifact::Schema(schema), LoadedArtifact::Manifest(manifest), LoadedArtifact::Proof(proof)) =
+            (load("schema.json"), load("manifest.json"), load("proof.json"))
+        else {
+            panic!("bundle files did not decode as schema, manifest and proof");
+        };
+        assert!(crate::cmd::verify::verify_models(schema, manifest, proof).unwrap().ok);
+    }
+}
@@ -1,8 +1,17 @@
 use anyhow::Result;
 use serde::Serialize;
 
+use signia_core::determinism::canonical_json::canonicalize_json;
+use signia_core::determinism::hashing::hash_bytes_hex;
+
 use crate::output;
 
+/// Tiny repo input compiled by the determinism self-test.
+const SELF_TEST_FIXTURE: &str = r#"{"name":"doctor","files":[{"path":"src/lib.rs"},{"path":"README.md"}]}"#;
+
+/// sha256("abc") (FIPS 180-2 test vector).
+const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
 #[derive(Debug, Serialize)]
 pub struct Check {
     pub name: String,
@@ -10,12 +19,24 @@ pub struct Check {
     pub detail: String,
 }
 
+/// Features compiled into the core and plugin crates.
+#[derive(Debug, Serialize)]
+pub struct FeatureReport {
+    pub canonical_json: bool,
+    pub builtin: bool,
+    pub wasm: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct DoctorOut {
     pub ok: bool,
     pub checks: Vec<Check>,
+    pub features: FeatureReport,
 }
 
+/// Checks that are reported but do not fail `ok`.
+const ADVISORY_CHECKS: &[&str] = &["solana", "features"];
+
 pub async fn run() -> Result<()> {
     let mut checks = Vec::new();
 
@@ -39,11 +60,110 @@ pub async fn run() -> Result<()> {
         detail: "optional (required for publish to on-chain registry)".to_string(),
     });
 
-    let ok = checks.iter().all(|c| c.ok || c.name == "solana");
-    output::print(&DoctorOut { ok, checks })?;
+    let features = feature_report();
+    checks.push(features_check(&features));
+
+    checks.extend(self_test());
+
+    let ok = checks.iter().all(|c| c.ok || ADVISORY_CHECKS.contains(&c.name.as_str()));
+    output::print(&DoctorOut { ok, checks, features })?;
     Ok(())
 }
 
+pub fn feature_report() -> FeatureReport {
+    FeatureReport {
+        canonical_json: signia_core::features::CANONICAL_JSON,
+        builtin: signia_plugins::features::BUILTIN,
+        wasm: signia_plugins::features::WASM,
+    }
+}
+
+fn features_check(f: &FeatureReport) -> Check {
+    let mut enabled = Vec::new();
+    if f.canonical_json {
+        enabled.push("canonical-json");
+    }
+    if f.builtin {
+        enabled.push("builtin");
+    }
+    if f.wasm {
+        enabled.push("wasm");
+    }
+    let mut detail = format!("compiled in: [{}]", enabled.join(", "));
+    if !f.canonical_json {
+        detail.push_str("; warning: canonical-json is off, most compile/verify functionality requires it");
+    }
+    Check {
+        name: "features".to_string(),
+        ok: f.canonical_json,
+        detail,
+    }
+}
+
+/// Runtime checks of the determinism guarantees this build relies on.
+///
+/// A failure here usually means a broken build or a feature-flag mismatch.
+pub fn self_test() -> Vec<Check> {
+    let compile = match (compile_fixture(), compile_fixture()) {
+        (Ok(a), Ok(b)) if a == b => Check {
+            name: "determinism.compile".to_string(),
+            ok: true,
+            detail: format!("embedded fixture compiled twice to identical bytes ({} bytes)", a.len()),
+        },
+        (Ok(_), Ok(_)) => Check {
+            name: "determinism.compile".to_string(),
+            ok: false,
+            detail: "embedded fixture compiled to different bytes".to_string(),
+        },
+        (Err(e), _) | (_, Err(e)) => Check {
+            name: "determinism.compile".to_string(),
+            ok: false,
+            detail: format!("embedded fixture failed to compile: {e}"),
+        },
+    };
+
+    let canonical = match canonical_json_sorted() {
+        Ok(got) => Check {
+            name: "determinism.canonical_json".to_string(),
+            ok: got == r#"{"a":{"c":3,"d":2},"b":1}"#,
+            detail: format!("canonicalize_json output: {got}"),
+        },
+        Err(e) => Check {
+            name: "determinism.canonical_json".to_string(),
+            ok: false,
+            detail: format!("canonicalize_json failed: {e}"),
+        },
+    };
+
+    let sha256 = match hash_bytes_hex(b"abc") {
+        Ok(got) => Check {
+            name: "determinism.sha256".to_string(),
+            ok: got == SHA256_ABC,
+            detail: format!("sha256(\"abc\") = {got}"),
+        },
+        Err(e) => Check {
+            name: "determinism.sha256".to_string(),
+            ok: false,
+            detail: format!("sha256 failed: {e}"),
+        },
+    };
+
+    vec![compile, canonical, sha256]
+}
+
+/// Compile the embedded fixture (with a fixed creation time) to one byte string.
+fn compile_fixture() -> Result<Vec<u8>> {
+    let input: serde_json::Value = serde_json::from_str(SELF_TEST_FIXTURE)?;
+    let canonical = canonicalize_json(&input)?;
+    let reg = super::plugins::registry(&Default::default(), None)?;
+    super::compile::artifact_bytes(&reg, "repo", "builtin.repo", &canonical, "1970-01-01T00:00:00Z")
+}
+
+fn canonical_json_sorted() -> Result<String> {
+    let v = serde_json::json!({"b": 1, "a": {"d": 2, "c": 3}});
+    Ok(serde_json::to_string(&canonicalize_json(&v)?)?)
+}
+
 fn which_ok(cmd: &str) -> bool {
     std::env::var_os("PATH").and_then(|paths| {
         for p in std::env::split_paths(&paths) {
@@ -62,3 +182,42 @@ fn which_ok(cmd: &str) -> bool {
         None
     }).is_some()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_test_passes() {
+        for c in self_test() {
+            assert!(c.ok, "{}: {}", c.name, c.detail);
+        }
+    }
+
+    #[test]
+    fn feature_report_reflects_build() {
+        let f = feature_report();
+        assert_eq!(f.canonical_json, signia_core::features::CANONICAL_JSON);
+        assert_eq!(f.builtin, signia_plugins::features::BUILTIN);
+        assert_eq!(f.wasm, signia_plugins::features::WASM);
+        // The CLI compiles against the builtin plugins and canonical JSON.
+        assert!(f.canonical_json);
+        assert!(f.builtin);
+
+        let c = features_check(&f);
+        assert!(c.ok);
+        assert!(c.detail.contains("canonical-json"));
+        assert!(!c.detail.contains("warning"));
+
+        let off = features_check(&FeatureReport { canonical_json: false, builtin: true, wasm: false });
+        assert!(!off.ok);
+        assert!(off.detail.contains("warning: canonical-json is off"));
+    }
+
+    #[test]
+    fn fixture_compiles_deterministically() {
+        let a = compile_fixture().unwrap();
+        assert!(!a.is_empty());
+        assert_eq!(a, compile_fixture().unwrap());
+    }
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::output;
+
+#[derive(Debug, Serialize)]
+pub struct GcOut {
+    pub dry_run: bool,
+    pub reachable: usize,
+    pub deleted: usize,
+    pub freed_bytes: u64,
+    /// Unreachable ids; only listed for dry runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub candidates: Option<Vec<String>>,
+}
+
+pub async fn run(store_root: &str, roots: &[String], dry_run: bool) -> Result<()> {
+    let store_cfg = signia_store::StoreConfig::local_dev(PathBuf::from(store_root))?;
+    let store = signia_store::Store::open(store_cfg)?;
+
+    let report = store.gc(roots, dry_run)?;
+
+    output::print(&GcOut {
+        dry_run,
+        reachable: report.reachable,
+        deleted: report.deleted(),
+        freed_bytes: report.freed_bytes,
+        candidates: if dry_run { Some(report.candidates) } else { None },
+    })?;
+    Ok(())
+}
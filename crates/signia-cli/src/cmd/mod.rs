@@ -2,20 +2,72 @@ use anyhow::Result;
 
 use crate::args::{Cli, Command};
 
+mod bundle_info;
 mod compile;
 mod doctor;
 mod fetch;
+mod gc;
 mod plugins;
 mod publish;
+#[cfg(test)]
+mod test_support;
 mod verify;
 
 pub async fn dispatch(cli: Cli) -> Result<()> {
     match cli.command {
-        Command::Compile { input, kind, out } => compile::run(&cli.store_root, &input, kind.as_deref(), &out).await,
-        Command::Verify { root, leaf, proof } => verify::run(&root, &leaf, &proof).await,
+        Command::Compile {
+            input,
+            kind,
+            out,
+            verify,
+            archive,
+            dry_run,
+            created_at,
+            emit_ir,
+            assert_deterministic,
+            config,
+            plugins_dir,
+            plugin,
+        } => {
+            let opts = compile::CompileOptions {
+                input: &input,
+                kind: kind.as_deref(),
+                out: &out,
+                verify,
+                archive,
+                assert_deterministic,
+                created_at: created_at.as_deref(),
+                emit_ir: emit_ir.as_deref(),
+                plugins: compile::PluginSelection {
+                    config: config.as_deref(),
+                    plugins_dir: plugins_dir.as_deref(),
+                    plugin: plugin.as_deref(),
+                },
+            };
+            if dry_run {
+                compile::run_dry(&opts).await
+            } else {
+                compile::run(&cli.store_root, &opts).await
+            }
+        }
+        Command::Verify { root, leaf, proof, archive, bundle } => match (archive, bundle) {
+            (Some(path), _) => verify::run_archive(&path).await,
+            (None, Some(dir)) => verify::run_bundle_dir(&dir).await,
+            (None, None) => {
+                // clap guarantees these are present without --archive/--bundle.
+                let (Some(root), Some(leaf), Some(proof)) = (root, leaf, proof) else {
+                    return Err(anyhow::anyhow!("--root, --leaf and --proof are required"));
+                };
+                verify::run(&root, &leaf, &proof).await
+            }
+        },
+        Command::BundleInfo { dir } => bundle_info::run(&dir).await,
         Command::Fetch { id, to } => fetch::run(&cli.store_root, &id, to.as_deref()).await,
-        Command::Plugins => plugins::run(&cli.store_root).await,
+        Command::Gc { roots, dry_run } => gc::run(&cli.store_root, &roots, dry_run).await,
+        Command::Plugins { plugins_dir } => plugins::run(&cli.store_root, plugins_dir.as_deref()).await,
         Command::Doctor => doctor::run().await,
-        Command::Publish { devnet, mainnet, id } => publish::run(devnet, mainnet, id.as_deref()).await,
+        Command::Publish { devnet, mainnet, id, program_id } => {
+            publish::run(devnet, mainnet, id.as_deref(), program_id.as_deref()).await
+        }
     }
 }
@@ -1,6 +1,12 @@
-use anyhow::Result;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
 use serde::Serialize;
+use signia_plugins::builtin::config::json::parse_config_json;
+use signia_plugins::builtin::config::BuiltinConfig;
+use signia_plugins::registry::PluginRegistry;
 
+use crate::io::input;
 use crate::output;
 
 #[derive(Debug, Serialize)]
@@ -15,20 +21,55 @@ pub struct PluginsOut {
     pub plugins: Vec<PluginInfo>,
 }
 
-pub async fn run(_store_root: &str) -> Result<()> {
-    let mut reg = signia_plugins::registry::PluginRegistry::default();
-    signia_plugins::builtin::repo::register(&mut reg);
-    signia_plugins::builtin::dataset::register(&mut reg);
-    signia_plugins::builtin::workflow::register(&mut reg);
-    signia_plugins::builtin::api::register(&mut reg);
-    signia_plugins::builtin::spec::register(&mut reg);
+pub async fn run(_store_root: &str, plugins_dir: Option<&str>) -> Result<()> {
+    let reg = registry(&BuiltinConfig::default(), plugins_dir.map(Path::new))?;
 
     let plugins = reg
-        .list()
-        .into_iter()
-        .map(|s| PluginInfo { id: s.id, version: s.version, kind: s.kind })
+        .iter()
+        .map(|(id, r)| PluginInfo {
+            id: id.clone(),
+            version: r.spec.version.clone(),
+            kind: r.spec.meta.get("kind").cloned().unwrap_or_else(|| "builtin".to_string()),
+        })
         .collect();
 
     output::print(&PluginsOut { plugins })?;
     Ok(())
 }
+
+/// All builtin plugins, configured from `config`, plus the WASM plugins under
+/// `plugins_dir` if given.
+pub fn registry(config: &BuiltinConfig, plugins_dir: Option<&Path>) -> Result<PluginRegistry> {
+    let mut reg = PluginRegistry::default();
+    signia_plugins::builtin::register_all_with_config(&mut reg, config);
+
+    if let Some(dir) = plugins_dir {
+        load_external(&mut reg, dir)?;
+    }
+    Ok(reg)
+}
+
+/// Load the builtin plugin configuration from a JSON or YAML file (`--config`),
+/// or the defaults if no file is given. Omitted sections keep their defaults.
+pub fn load_config(path: Option<&Path>) -> Result<BuiltinConfig> {
+    let Some(path) = path else {
+        return Ok(BuiltinConfig::default());
+    };
+    let value = input::read_input_file(path).map_err(|e| anyhow!("--config {}: {e}", path.display()))?;
+    parse_config_json(&value).map_err(|e| anyhow!("--config {}: {e}", path.display()))
+}
+
+/// Register the WASM plugins under `dir`, sandboxed with no host capabilities.
+#[cfg(feature = "wasm")]
+fn load_external(reg: &mut PluginRegistry, dir: &Path) -> Result<()> {
+    use signia_plugins::sandbox::{load_plugins_dir, SandboxConfig};
+
+    load_plugins_dir(reg, dir, &SandboxConfig::default())
+        .map_err(|e| anyhow!("--plugins-dir: {e:#}"))?;
+    Ok(())
+}
+
+#[cfg(not(feature = "wasm"))]
+fn load_external(_reg: &mut PluginRegistry, _dir: &Path) -> Result<()> {
+    Err(anyhow!("--plugins-dir: this build was compiled without the `wasm` feature"))
+}
@@ -10,9 +10,14 @@ pub struct PublishOut {
     pub cluster: String,
     pub note: String,
     pub id: Option<String>,
+    pub program_id: String,
+    /// Lamports to make one record account rent-exempt, if RPC was reachable.
+    pub rent_lamports: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
-pub async fn run(devnet: bool, mainnet: bool, id: Option<&str>) -> Result<()> {
+pub async fn run(devnet: bool, mainnet: bool, id: Option<&str>, program_id: Option<&str>) -> Result<()> {
     let cluster = if devnet && mainnet {
         return Err(anyhow!("choose only one: --devnet or --mainnet"));
     } else if mainnet {
@@ -23,12 +28,36 @@ pub async fn run(devnet: bool, mainnet: bool, id: Option<&str>) -> Result<()> {
 
     // Placeholder: wire to signia-program instructions once available.
     // This implementation performs client initialization and prints a clear action note.
-    let _client = solana::client::SolanaClient::new(cluster)?;
+    let client = solana::client::SolanaClient::new(cluster)?;
+
+    let program_id = match program_id {
+        Some(s) => signia_solana_client::parse_pubkey(s).map_err(|e| anyhow!("--program-id: {e}"))?,
+        None => signia_solana_client::default_program_id(),
+    };
+    let mut warnings = Vec::new();
+    if signia_solana_client::is_placeholder_program_id(&program_id) {
+        warnings.push(format!(
+            "program id {program_id} is the placeholder, not a deployed registry; pass --program-id"
+        ));
+    }
+
+    // Estimating needs the network; report failures instead of failing the stub.
+    let registry = signia_solana_client::RegistryClient::with_rpc(program_id, &client.rpc_url());
+    let rent_lamports = match registry.estimate_rent(signia_solana_client::RECORD_ACCOUNT_LEN) {
+        Ok(lamports) => Some(lamports),
+        Err(e) => {
+            warnings.push(format!("could not estimate record rent: {e}"));
+            None
+        }
+    };
 
     output::print(&PublishOut {
         ok: true,
         cluster: cluster.to_string(),
         id: id.map(|s| s.to_string()),
+        program_id: program_id.to_string(),
+        rent_lamports,
+        warnings,
         note: "publish is a stub in signia-cli; wire signia-program registry instructions to enable on-chain publishing".to_string(),
     })?;
     Ok(())
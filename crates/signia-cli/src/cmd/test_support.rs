@@ -0,0 +1,24 @@
+//! Shared fixtures for the `cmd` unit tests.
+
+use std::path::{Path, PathBuf};
+
+use crate::io::export::ArchiveFormat;
+
+use super::compile::{self, CompileOptions};
+
+/// Run `compile` over a small repo input, writing the bundle to `<dir>/out`
+/// (and the store to `<dir>/store`).
+pub async fn compile_into(dir: &Path, archive: Option<ArchiveFormat>) -> PathBuf {
+    let input = dir.join("repo.json");
+    std::fs::write(&input, br#"{"name":"demo","files":[{"path":"README.md"},{"path":"src/lib.rs"}]}"#).unwrap();
+    let out = dir.join("out");
+    let opts = CompileOptions {
+        input: input.to_str().unwrap(),
+        out: out.to_str().unwrap(),
+        archive,
+        created_at: Some("1970-01-01T00:00:00Z"),
+        ..CompileOptions::default()
+    };
+    compile::run(dir.join("store").to_str().unwrap(), &opts).await.unwrap();
+    out
+}
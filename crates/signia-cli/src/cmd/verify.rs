@@ -1,7 +1,7 @@
 use anyhow::{anyhow, Result};
 use serde::Serialize;
 
-use crate::io::input;
+use crate::io::{input, load_artifact, LoadedArtifact};
 use crate::output;
 
 #[derive(Debug, Serialize)]
@@ -10,7 +10,11 @@ pub struct VerifyOut {
 }
 
 pub async fn run(root_hex: &str, leaf_hex: &str, proof_path: &str) -> Result<()> {
-    let proof_json = input::read_json_file(proof_path)?;
+    // A MerkleProof is not a bundle artifact, so it loads as raw JSON/YAML.
+    let proof_json = match load_artifact(proof_path)? {
+        LoadedArtifact::Raw(v) => v,
+        other => return Err(anyhow!("{proof_path} is a {} artifact, expected a MerkleProof", other.kind())),
+    };
     let proof: signia_store::proofs::merkle::MerkleProof = serde_json::from_value(proof_json)
         .map_err(|e| anyhow!("invalid proof json: {e}"))?;
 
@@ -25,3 +29,135 @@ pub async fn run(root_hex: &str, leaf_hex: &str, proof_path: &str) -> Result<()>
     output::print(&VerifyOut { ok })?;
     Ok(())
 }
+
+#[derive(Debug, Serialize)]
+pub struct ArchiveVerifyOut {
+    pub ok: bool,
+    pub archive: String,
+    pub errors: Vec<String>,
+}
+
+/// Verify a bundle archive in memory with `verify_bundle`; fails if it does not verify.
+pub async fn run_archive(path: &str) -> Result<()> {
+    let bundle = input::read_bundle_archive(path)?;
+    let report = verify_bundle_json(&bundle.schema, &bundle.manifest, &bundle.proof)?;
+
+    let errors = error_findings(&report);
+    output::print(&ArchiveVerifyOut { ok: report.ok, archive: path.to_string(), errors: errors.clone() })?;
+    if !report.ok {
+        return Err(anyhow!("bundle verification failed: {}", errors.join("; ")));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleDirVerifyOut {
+    pub ok: bool,
+    pub bundle: String,
+    pub errors: Vec<String>,
+}
+
+/// Verify a bundle directory (`schema.json`, `manifest.json`, `proof.json`);
+/// fails if it does not verify.
+pub async fn run_bundle_dir(dir: &str) -> Result<()> {
+    let dir_path = std::path::Path::new(dir);
+    let schema = match load_artifact(dir_path.join("schema.json"))? {
+        LoadedArtifact::Schema(s) => s,
+        other => return Err(anyhow!("schema.json is a {} artifact, expected schema", other.kind())),
+    };
+    let manifest = match load_artifact(dir_path.join("manifest.json"))? {
+        LoadedArtifact::Manifest(m) => m,
+        other => return Err(anyhow!("manifest.json is a {} artifact, expected manifest", other.kind())),
+    };
+    let proof = match load_artifact(dir_path.join("proof.json"))? {
+        LoadedArtifact::Proof(p) => p,
+        other => return Err(anyhow!("proof.json is a {} artifact, expected proof", other.kind())),
+    };
+
+    let report = verify_models(schema, manifest, proof)?;
+    let errors = error_findings(&report);
+    output::print(&BundleDirVerifyOut { ok: report.ok, bundle: dir.to_string(), errors: errors.clone() })?;
+    if !report.ok {
+        return Err(anyhow!("bundle verification failed: {}", errors.join("; ")));
+    }
+    Ok(())
+}
+
+/// Run `verify_bundle` with default options over decoded v1 models.
+pub fn verify_models(
+    schema: signia_core::model::v1::SchemaV1,
+    manifest: signia_core::model::v1::ManifestV1,
+    proof: signia_core::model::v1::ProofV1,
+) -> Result<signia_core::pipeline::verify::VerifyReport> {
+    let bundle = signia_core::pipeline::verify::VerifyBundle {
+        schema,
+        manifest,
+        proof: Some(proof),
+        output_digests: Default::default(),
+    };
+    Ok(signia_core::pipeline::verify::verify_bundle(
+        bundle,
+        signia_core::pipeline::verify::VerifyOptions::default(),
+    )?)
+}
+
+/// Decode bundle JSON as v1 models and run `verify_bundle` with default options.
+pub fn verify_bundle_json(
+    schema: &serde_json::Value,
+    manifest: &serde_json::Value,
+    proof: &serde_json::Value,
+) -> Result<signia_core::pipeline::verify::VerifyReport> {
+    verify_models(
+        serde_json::from_value(schema.clone()).map_err(|e| anyhow!("schema is not SchemaV1: {e}"))?,
+        serde_json::from_value(manifest.clone()).map_err(|e| anyhow!("manifest is not ManifestV1: {e}"))?,
+        serde_json::from_value(proof.clone()).map_err(|e| anyhow!("proof is not ProofV1: {e}"))?,
+    )
+}
+
+/// Error-level findings formatted as `code: message`, followed by
+/// `(computed <hex>, expected <hex>)` for digest mismatches.
+pub fn error_findings(report: &signia_core::pipeline::verify::VerifyReport) -> Vec<String> {
+    report
+        .findings
+        .iter()
+        .filter(|f| matches!(f.level, signia_core::pipeline::verify::VerifyLevel::Error))
+        .map(|f| match (f.data.get("computed"), f.data.get("expected")) {
+            (Some(computed), Some(expected)) => {
+                format!("{}: {} (computed {computed}, expected {expected})", f.code, f.message)
+            }
+            _ => format!("{}: {}", f.code, f.message),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::cmd::test_support::compile_into;
+    use crate::io::export::ArchiveFormat;
+
+    #[tokio::test]
+    async fn verifies_compiled_archives() {
+        for format in [ArchiveFormat::Tar, ArchiveFormat::Zip] {
+            let td = tempfile::TempDir::new().unwrap();
+            let out = compile_into(td.path(), Some(format)).await;
+            let archive = out.join(format!("bundle.{}", format.extension()));
+            run_archive(archive.to_str().unwrap()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn verifies_compiled_bundle_dir() {
+        let td = tempfile::TempDir::new().unwrap();
+        let out = compile_into(td.path(), None).await;
+        run_bundle_dir(out.to_str().unwrap()).await.unwrap();
+
+        // An edited schema no longer matches the manifest and proof.
+        let schema = out.join("schema.json");
+        let edited = std::fs::read_to_string(&schema).unwrap().replace("README.md", "READYOU.md");
+        std::fs::write(&schema, edited).unwrap();
+        let err = run_bundle_dir(out.to_str().unwrap()).await.unwrap_err();
+        assert!(err.to_string().starts_with("bundle verification failed"), "{err}");
+    }
+}
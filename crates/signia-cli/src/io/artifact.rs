@@ -0,0 +1,184 @@
+//! Load schema/manifest/proof artifacts from disk.
+//!
+//! Commands that read bundle files share this loader instead of parsing per
+//! kind. The file format (JSON or YAML) is taken from the extension, or sniffed
+//! from the first non-whitespace byte when the extension says nothing. The
+//! artifact kind is detected from the parsed content with
+//! `signia_core::pipeline::parse`, never from the file name.
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use signia_core::model::v1::{ManifestV1, ProofV1, SchemaV1};
+use signia_core::pipeline::parse::{self, ArtifactKind};
+
+use crate::io::input;
+
+/// Maximum artifact file size accepted by `load_artifact` (same cap as a bundle
+/// archive entry).
+pub const MAX_ARTIFACT_BYTES: u64 = input::MAX_ARCHIVE_ENTRY_BYTES;
+
+/// A decoded artifact.
+#[derive(Debug, Clone)]
+pub enum LoadedArtifact {
+    Schema(SchemaV1),
+    Manifest(ManifestV1),
+    Proof(ProofV1),
+    /// Valid JSON/YAML that is not a recognized artifact.
+    Raw(serde_json::Value),
+}
+
+impl LoadedArtifact {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LoadedArtifact::Schema(_) => "schema",
+            LoadedArtifact::Manifest(_) => "manifest",
+            LoadedArtifact::Proof(_) => "proof",
+            LoadedArtifact::Raw(_) => "raw",
+        }
+    }
+}
+
+/// Load and decode an artifact file, capped at `MAX_ARTIFACT_BYTES`.
+pub fn load_artifact<P: AsRef<Path>>(path: P) -> Result<LoadedArtifact> {
+    load_artifact_with_limit(path, MAX_ARTIFACT_BYTES)
+}
+
+/// `load_artifact` with an explicit size limit.
+pub fn load_artifact_with_limit<P: AsRef<Path>>(path: P, max_bytes: u64) -> Result<LoadedArtifact> {
+    let path = path.as_ref();
+    let size = fs::metadata(path)
+        .map_err(|e| anyhow!("cannot read artifact {}: {e}", path.display()))?
+        .len();
+    if size > max_bytes {
+        return Err(anyhow!("{}: artifact too large: {size} bytes (max {max_bytes})", path.display()));
+    }
+
+    // The file may grow between the size check and the read.
+    let mut bytes = Vec::new();
+    fs::File::open(path)?.take(max_bytes + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > max_bytes {
+        return Err(anyhow!("{}: artifact exceeds {max_bytes} bytes", path.display()));
+    }
+
+    let text = input::decode_utf8_text(&bytes).map_err(|e| anyhow!("{}: {e}", path.display()))?;
+    decode_artifact(text, is_yaml(path, text)).map_err(|e| anyhow!("{}: {e}", path.display()))
+}
+
+fn is_yaml(path: &Path, text: &str) -> bool {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml" | "yml") => true,
+        Some("json") => false,
+        _ => !matches!(text.trim_start().as_bytes().first(), Some(b'{' | b'[')),
+    }
+}
+
+fn decode_artifact(text: &str, yaml: bool) -> Result<LoadedArtifact> {
+    let json_bytes;
+    let bytes = if yaml {
+        let v: serde_json::Value = serde_yaml::from_str(text).map_err(|e| anyhow!("invalid yaml: {e}"))?;
+        json_bytes = serde_json::to_vec(&v)?;
+        json_bytes.as_slice()
+    } else {
+        text.as_bytes()
+    };
+
+    // The file size is already capped; the parser limit only guards the YAML
+    // re-encoding, which can be larger than the source.
+    let v = parse::parse_json_bytes(bytes, usize::MAX)?;
+    let kind = parse::detect_kind(&v);
+    if kind == ArtifactKind::Unknown {
+        return Ok(LoadedArtifact::Raw(v));
+    }
+
+    let version = parse::read_version(&v)?;
+    if version != "v1" {
+        return Err(anyhow!("unsupported {kind:?} version: {version}"));
+    }
+    let decode_err = |e: serde_json::Error| anyhow!("failed to decode {kind:?}V1: {e}");
+    Ok(match kind {
+        ArtifactKind::Schema => LoadedArtifact::Schema(serde_json::from_value(v).map_err(decode_err)?),
+        ArtifactKind::Manifest => LoadedArtifact::Manifest(serde_json::from_value(v).map_err(decode_err)?),
+        ArtifactKind::Proof => LoadedArtifact::Proof(serde_json::from_value(v).map_err(decode_err)?),
+        ArtifactKind::Unknown => unreachable!("handled above"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    use signia_core::model::ir::{DefaultIdStrategy, IrGraph};
+    use signia_core::pipeline::compile::{compile_from_ir, CompileRequest, LimitsSpec};
+
+    fn bundle() -> (serde_json::Value, serde_json::Value, serde_json::Value) {
+        let req = CompileRequest {
+            kind: "repo".to_string(),
+            meta: serde_json::json!({
+                "name": "demo",
+                "createdAt": "1970-01-01T00:00:00Z",
+                "source": {"type": "path", "locator": "artifact:/demo"},
+                "normalization": {"policyVersion": "v1", "pathRoot": "artifact:/", "newline": "lf", "encoding": "utf-8", "symlinks": "deny", "network": "deny"}
+            }),
+            created_at: "1970-01-01T00:00:00Z".to_string(),
+            labels: BTreeMap::new(),
+            inputs: vec![],
+            outputs: vec![],
+            plugins: vec![],
+            limits: LimitsSpec::default(),
+            run_inference: false,
+            build_proof: true,
+        };
+        let rep = compile_from_ir(IrGraph::new(), req, Some(&DefaultIdStrategy::default())).unwrap();
+        (
+            serde_json::to_value(&rep.bundle.schema).unwrap(),
+            serde_json::to_value(&rep.bundle.manifest).unwrap(),
+            serde_json::to_value(rep.bundle.proof.as_ref().unwrap()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn loads_each_artifact_kind() {
+        let td = tempfile::TempDir::new().unwrap();
+        let (schema, manifest, proof) = bundle();
+        crate::io::export::write_bundle(td.path(), &schema, &manifest, &proof).unwrap();
+
+        assert!(matches!(load_artifact(td.path().join("schema.json")).unwrap(), LoadedArtifact::Schema(_)));
+        assert!(matches!(load_artifact(td.path().join("manifest.json")).unwrap(), LoadedArtifact::Manifest(_)));
+        let LoadedArtifact::Proof(p) = load_artifact(td.path().join("proof.json")).unwrap() else {
+            panic!("expected proof");
+        };
+        assert_eq!(p.root, proof["root"].as_str().unwrap());
+
+        // Kind comes from content, format from extension or sniffing.
+        let yaml = td.path().join("proof.yaml");
+        fs::write(&yaml, serde_yaml::to_string(&proof).unwrap()).unwrap();
+        assert_eq!(load_artifact(&yaml).unwrap().kind(), "proof");
+        let sniffed = td.path().join("manifest.artifact");
+        fs::write(&sniffed, serde_yaml::to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(load_artifact(&sniffed).unwrap().kind(), "manifest");
+
+        let raw = td.path().join("input.json");
+        fs::write(&raw, br#"{"name":"r","files":[]}"#).unwrap();
+        assert_eq!(load_artifact(&raw).unwrap().kind(), "raw");
+    }
+
+    #[test]
+    fn rejects_oversized_and_bad_versions() {
+        let td = tempfile::TempDir::new().unwrap();
+        let (_, _, mut proof) = bundle();
+
+        let p = td.path().join("proof.json");
+        fs::write(&p, serde_json::to_vec(&proof).unwrap()).unwrap();
+        let err = load_artifact_with_limit(&p, 16).unwrap_err().to_string();
+        assert!(err.contains("artifact too large"), "{err}");
+
+        proof["version"] = serde_json::json!("v9");
+        fs::write(&p, serde_json::to_vec(&proof).unwrap()).unwrap();
+        let err = load_artifact(&p).unwrap_err().to_string();
+        assert!(err.contains("unsupported Proof version: v9"), "{err}");
+    }
+}
@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
 use std::fs;
+use std::io::{Cursor, Write};
 use std::path::Path;
 
 use anyhow::{anyhow, Result};
 use sha2::{Digest, Sha256};
+use signia_core::model::v1::BundleInfoV1;
 
 pub fn write_bundle<P: AsRef<Path>>(out_dir: P, schema: &serde_json::Value, manifest: &serde_json::Value, proof: &serde_json::Value) -> Result<()> {
     let out_dir = out_dir.as_ref();
@@ -14,38 +17,249 @@ pub fn write_bundle<P: AsRef<Path>>(out_dir: P, schema: &serde_json::Value, mani
     Ok(())
 }
 
-fn sha256_hex(bytes: &[u8]) -> String {
+/// Describe a bundle written by `write_bundle`: the three object ids plus the
+/// sha256 of each file's bytes exactly as `write_bundle` writes them.
+pub fn build_bundle_info(
+    schema_id: &str,
+    manifest_id: &str,
+    proof_id: &str,
+    schema: &serde_json::Value,
+    manifest: &serde_json::Value,
+    proof: &serde_json::Value,
+) -> Result<BundleInfoV1> {
+    let mut hashes = BTreeMap::new();
+    for (name, value) in [("schema.json", schema), ("manifest.json", manifest), ("proof.json", proof)] {
+        hashes.insert(name.to_string(), sha256_hex(&serde_json::to_vec_pretty(value)?));
+    }
+    Ok(BundleInfoV1::from_bundle(schema_id, manifest_id, proof_id, hashes))
+}
+
+/// Write `info` as `bundle.json` in `out_dir`, next to the three artifacts.
+pub fn write_bundle_info<P: AsRef<Path>>(out_dir: P, info: &BundleInfoV1) -> Result<()> {
+    let out_dir = out_dir.as_ref();
+    fs::create_dir_all(out_dir)?;
+    fs::write(out_dir.join("bundle.json"), serde_json::to_vec_pretty(info)?)?;
+    Ok(())
+}
+
+/// Archive container for `write_bundle_archive`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ArchiveFormat {
+    Tar,
+    Zip,
+}
+
+impl ArchiveFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Tar => "tar",
+            ArchiveFormat::Zip => "zip",
+        }
+    }
+}
+
+/// Fixed modification time for archive entries (Unix epoch).
+const ARCHIVE_MTIME: u64 = 0;
+/// Fixed owner for tar entries.
+const ARCHIVE_UID: u64 = 0;
+const ARCHIVE_GID: u64 = 0;
+/// Fixed permissions for all archive entries.
+const ARCHIVE_MODE: u32 = 0o644;
+
+/// Write schema/manifest/proof into a single archive file.
+///
+/// Every piece of entry metadata is set explicitly (sorted names, mtime, uid/gid,
+/// owner names, permissions, entry type); nothing is inherited from the
+/// filesystem or the current user. The same bundle always produces the same
+/// archive bytes. Entry contents match `write_bundle`.
+pub fn write_bundle_archive<P: AsRef<Path>>(
+    out_file: P,
+    schema: &serde_json::Value,
+    manifest: &serde_json::Value,
+    proof: &serde_json::Value,
+    format: ArchiveFormat,
+) -> Result<()> {
+    let mut entries = vec![
+        ("schema.json", serde_json::to_vec_pretty(schema)?),
+        ("manifest.json", serde_json::to_vec_pretty(manifest)?),
+        ("proof.json", serde_json::to_vec_pretty(proof)?),
+    ];
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+
+    let bytes = match format {
+        ArchiveFormat::Tar => tar_bytes(&entries)?,
+        ArchiveFormat::Zip => zip_bytes(&entries)?,
+    };
+
+    let out_file = out_file.as_ref();
+    if let Some(parent) = out_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(out_file, bytes)?;
+    Ok(())
+}
+
+fn tar_bytes(entries: &[(&str, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut b = tar::Builder::new(Vec::new());
+    for (name, data) in entries {
+        let mut h = tar::Header::new_gnu();
+        h.set_entry_type(tar::EntryType::Regular);
+        h.set_path(name)?;
+        h.set_size(data.len() as u64);
+        h.set_mode(ARCHIVE_MODE);
+        h.set_mtime(ARCHIVE_MTIME);
+        h.set_uid(ARCHIVE_UID);
+        h.set_gid(ARCHIVE_GID);
+        h.set_username("")?;
+        h.set_groupname("")?;
+        h.set_cksum();
+        b.append(&h, data.as_slice())?;
+    }
+    Ok(b.into_inner()?)
+}
+
+fn zip_bytes(entries: &[(&str, Vec<u8>)]) -> Result<Vec<u8>> {
+    // Stored (no compression): output does not depend on a compressor implementation.
+    let opts = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .last_modified_time(zip::DateTime::default())
+        .unix_permissions(ARCHIVE_MODE);
+
+    let mut z = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, data) in entries {
+        z.start_file(*name, opts)?;
+        z.write_all(data)?;
+    }
+    Ok(z.finish()?.into_inner())
+}
+
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
     let mut h = Sha256::new();
     h.update(bytes);
     hex::encode(h.finalize())
 }
 
-pub fn build_manifest(input: &serde_json::Value, schema_id: &str, kind: &str) -> serde_json::Value {
-    let input_bytes = serde_json::to_vec(input).unwrap_or_default();
-    serde_json::json!({
-        "version": "v1",
-        "inputKind": kind,
-        "inputHash": sha256_hex(&input_bytes),
-        "schemaObjectId": schema_id,
-        "createdAt": time::OffsetDateTime::now_utc().unix_timestamp(),
-    })
-}
-
-pub fn build_proof(input: &serde_json::Value, schema_id: &str, manifest_id: &str) -> Result<serde_json::Value> {
-    let input_bytes = serde_json::to_vec(input)?;
-    let leaf = sha256_hex(&input_bytes);
-    let schema_leaf = sha256_hex(schema_id.as_bytes());
-
-    let leaves = vec![leaf.clone(), schema_leaf.clone()];
-    let root = signia_store::proofs::merkle::merkle_root_hex(&leaves)?;
-    let proof0 = signia_store::proofs::merkle::merkle_proof(&leaves, 0).ok();
-
-    Ok(serde_json::json!({
-        "version": "v1",
-        "root": root,
-        "leaf": leaf,
-        "schemaLeaf": schema_leaf,
-        "manifestObjectId": manifest_id,
-        "merkleProof": proof0,
-    }))
+/// Bundle creation time as an RFC 3339 timestamp in UTC.
+///
+/// `created_at` (from `--created-at`) must be an RFC 3339 timestamp; any offset
+/// is normalized to UTC. Without it, `SOURCE_DATE_EPOCH` (the reproducible-builds
+/// convention) is honored, and otherwise the Unix epoch is used. The wall clock
+/// is never read.
+pub(crate) fn resolve_created_at(created_at: Option<&str>) -> Result<String> {
+    use time::format_description::well_known::Rfc3339;
+
+    let t = match created_at {
+        Some(s) => {
+            signia_core::determinism::time::validate_iso8601(s).map_err(|e| anyhow!("--created-at: {e}"))?;
+            time::OffsetDateTime::parse(s, &Rfc3339)?.to_offset(time::UtcOffset::UTC)
+        }
+        None => {
+            let secs = std::env::var("SOURCE_DATE_EPOCH")
+                .ok()
+                .and_then(|s| s.trim().parse::<i64>().ok())
+                .unwrap_or(0);
+            time::OffsetDateTime::from_unix_timestamp(secs)
+                .map_err(|e| anyhow!("SOURCE_DATE_EPOCH out of range: {e}"))?
+        }
+    };
+    Ok(t.format(&Rfc3339)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle() -> (serde_json::Value, serde_json::Value, serde_json::Value) {
+        (
+            serde_json::json!({"version": "v1", "entities": []}),
+            serde_json::json!({"version": "v1", "schemaObjectId": "ab".repeat(32)}),
+            serde_json::json!({"version": "v1", "root": "cd".repeat(32)}),
+        )
+    }
+
+    #[test]
+    fn created_at_flag_is_validated() {
+        assert_eq!(resolve_created_at(Some("1970-01-01T00:00:00Z")).unwrap(), "1970-01-01T00:00:00Z");
+        assert_eq!(resolve_created_at(Some("2024-01-01T01:00:00+01:00")).unwrap(), "2024-01-01T00:00:00Z");
+
+        let err = resolve_created_at(Some("not-a-date")).unwrap_err();
+        assert!(err.to_string().starts_with("--created-at: "), "{err}");
+        assert!(resolve_created_at(Some("2024-01-01")).is_err());
+    }
+
+    #[test]
+    fn bundle_info_references_ids_and_written_files() {
+        let td = tempfile::TempDir::new().unwrap();
+        let (schema, manifest, proof) = bundle();
+        write_bundle(td.path(), &schema, &manifest, &proof).unwrap();
+
+        let info = build_bundle_info("sch", "man", "prf", &schema, &manifest, &proof).unwrap();
+        write_bundle_info(td.path(), &info).unwrap();
+        assert_eq!(info.artifact_ids(), ["sch", "man", "prf"]);
+        for name in ["schema.json", "manifest.json", "proof.json"] {
+            assert_eq!(info.hashes[name], sha256_hex(&fs::read(td.path().join(name)).unwrap()), "{name}");
+        }
+
+        let written: BundleInfoV1 = serde_json::from_slice(&fs::read(td.path().join("bundle.json")).unwrap()).unwrap();
+        assert_eq!(written, info);
+    }
+
+    #[test]
+    fn archives_are_reproducible() {
+        let td = tempfile::TempDir::new().unwrap();
+        let (schema, manifest, proof) = bundle();
+
+        for format in [ArchiveFormat::Tar, ArchiveFormat::Zip] {
+            let a = td.path().join(format!("a.{}", format.extension()));
+            let b = td.path().join(format!("b.{}", format.extension()));
+            write_bundle_archive(&a, &schema, &manifest, &proof, format).unwrap();
+            write_bundle_archive(&b, &schema, &manifest, &proof, format).unwrap();
+            assert_eq!(fs::read(&a).unwrap(), fs::read(&b).unwrap(), "{format:?} archive differs");
+        }
+    }
+
+    #[test]
+    fn tar_entries_are_sorted() {
+        let td = tempfile::TempDir::new().unwrap();
+        let (schema, manifest, proof) = bundle();
+        let p = td.path().join("bundle.tar");
+        write_bundle_archive(&p, &schema, &manifest, &proof, ArchiveFormat::Tar).unwrap();
+
+        let mut ar = tar::Archive::new(fs::File::open(&p).unwrap());
+        let names: Vec<String> = ar
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["manifest.json", "proof.json", "schema.json"]);
+    }
+
+    #[test]
+    fn archive_metadata_is_fixed() {
+        let td = tempfile::TempDir::new().unwrap();
+        let (schema, manifest, proof) = bundle();
+
+        let p = td.path().join("bundle.tar");
+        write_bundle_archive(&p, &schema, &manifest, &proof, ArchiveFormat::Tar).unwrap();
+        let mut ar = tar::Archive::new(fs::File::open(&p).unwrap());
+        for e in ar.entries().unwrap() {
+            let e = e.unwrap();
+            let h = e.header();
+            assert_eq!(h.entry_type(), tar::EntryType::Regular);
+            assert_eq!(h.mtime().unwrap(), ARCHIVE_MTIME);
+            assert_eq!(h.uid().unwrap(), ARCHIVE_UID);
+            assert_eq!(h.gid().unwrap(), ARCHIVE_GID);
+            assert_eq!(h.mode().unwrap(), ARCHIVE_MODE);
+            assert_eq!(h.username().unwrap(), Some(""));
+        }
+
+        let p = td.path().join("bundle.zip");
+        write_bundle_archive(&p, &schema, &manifest, &proof, ArchiveFormat::Zip).unwrap();
+        let mut z = zip::ZipArchive::new(fs::File::open(&p).unwrap()).unwrap();
+        for i in 0..z.len() {
+            let f = z.by_index(i).unwrap();
+            assert_eq!(f.unix_mode().map(|m| m & 0o777), Some(ARCHIVE_MODE));
+            assert_eq!(f.last_modified(), Some(zip::DateTime::default()));
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 
 use anyhow::{anyhow, Result};
@@ -16,16 +17,50 @@ pub async fn resolve_to_json(input: &str) -> Result<serde_json::Value> {
     }
 
     // 3) Local file
-    read_json_file(input)
+    read_input_file(input)
 }
 
+/// Read a local input file: YAML for `.yaml`/`.yml`, JSON otherwise.
+pub fn read_input_file<P: AsRef<Path>>(path: P) -> Result<serde_json::Value> {
+    let path = path.as_ref();
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml" | "yml") => read_yaml_file(path),
+        _ => read_json_file(path),
+    }
+}
+
+/// Read a YAML file as JSON via `yaml_to_json`, so detection and plugins see
+/// the same value a JSON input would give (non-string keys become strings).
+pub fn read_yaml_file<P: AsRef<Path>>(path: P) -> Result<serde_json::Value> {
+    let path = path.as_ref();
+    let bytes = fs::read(path)?;
+    let raw = decode_utf8_text(&bytes).map_err(|e| anyhow!("{}: {e}", path.display()))?;
+    let y: serde_yaml::Value = serde_yaml::from_str(raw).map_err(|e| anyhow!("invalid yaml: {e}"))?;
+    signia_plugins::builtin::workflow::yaml::yaml_to_json(&y)
+}
+
+/// Read a JSON file as UTF-8, ignoring a leading UTF-8 byte order mark.
+///
+/// Non-UTF-8 content is rejected with the byte offset of the first invalid sequence.
 pub fn read_json_file<P: AsRef<Path>>(path: P) -> Result<serde_json::Value> {
-    let raw = fs::read_to_string(path.as_ref())?;
-    let v: serde_json::Value = serde_json::from_str(&raw)
+    let path = path.as_ref();
+    let bytes = fs::read(path)?;
+    let raw = decode_utf8_text(&bytes).map_err(|e| anyhow!("{}: {e}", path.display()))?;
+    let v: serde_json::Value = serde_json::from_str(raw)
         .map_err(|e| anyhow!("invalid json: {e}"))?;
     Ok(v)
 }
 
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+pub(crate) fn decode_utf8_text(bytes: &[u8]) -> Result<&str> {
+    let body = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+    std::str::from_utf8(body).map_err(|e| {
+        let offset = e.valid_up_to() + (bytes.len() - body.len());
+        anyhow!("input is not valid UTF-8 (invalid byte at offset {offset})")
+    })
+}
+
 async fn fetch_url_json(url: &str) -> Result<serde_json::Value> {
     let resp = reqwest::get(url).await?;
     let status = resp.status();
@@ -78,3 +113,240 @@ fn parse_github_shorthand(s: &str) -> Result<(String, Option<String>, Option<Str
     }
     Ok((repo_part, ref_part, path_part))
 }
+
+/// Maximum archive file size accepted by `read_bundle_archive`.
+pub const MAX_ARCHIVE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Maximum size of a single extracted archive entry.
+pub const MAX_ARCHIVE_ENTRY_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Bundle artifacts extracted from an archive.
+#[derive(Debug, Clone)]
+pub struct ArchiveBundle {
+    pub schema: serde_json::Value,
+    pub manifest: serde_json::Value,
+    pub proof: serde_json::Value,
+}
+
+/// Read a tar or zip bundle (as written by `export::write_bundle_archive`) in memory.
+///
+/// The format is detected from the file contents, not the extension. Only
+/// `schema.json`, `manifest.json` and `proof.json` are read; each must appear
+/// exactly once, and other entries are skipped without being decompressed.
+/// The archive file and the total decompressed size are capped by
+/// `MAX_ARCHIVE_BYTES`, each entry by `MAX_ARCHIVE_ENTRY_BYTES`.
+pub fn read_bundle_archive<P: AsRef<Path>>(path: P) -> Result<ArchiveBundle> {
+    let path = path.as_ref();
+    let size = fs::metadata(path)
+        .map_err(|e| anyhow!("cannot read archive {}: {e}", path.display()))?
+        .len();
+    if size > MAX_ARCHIVE_BYTES {
+        return Err(anyhow!("archive too large: {size} bytes (max {MAX_ARCHIVE_BYTES})"));
+    }
+    let bytes = fs::read(path)?;
+    parse_bundle_archive(&bytes, ArchiveCaps::DEFAULT)
+}
+
+/// Caps on the decompressed size of archive entries.
+#[derive(Debug, Clone, Copy)]
+struct ArchiveCaps {
+    /// Per entry.
+    entry: u64,
+    /// Across all entries that are read.
+    total: u64,
+}
+
+impl ArchiveCaps {
+    const DEFAULT: Self = Self { entry: MAX_ARCHIVE_ENTRY_BYTES, total: MAX_ARCHIVE_BYTES };
+}
+
+fn parse_bundle_archive(bytes: &[u8], caps: ArchiveCaps) -> Result<ArchiveBundle> {
+    let mut entries: Vec<(String, Vec<u8>)> = Vec::new();
+    let mut total = 0u64;
+    if bytes.starts_with(b"PK\x03\x04") {
+        let mut z = zip::ZipArchive::new(std::io::Cursor::new(bytes))
+            .map_err(|e| anyhow!("malformed zip archive: {e}"))?;
+        for i in 0..z.len() {
+            let f = z.by_index(i).map_err(|e| anyhow!("malformed zip archive: {e}"))?;
+            if !f.is_file() {
+                continue;
+            }
+            let name = f.name().to_string();
+            if !ARCHIVE_FILES.contains(&name.as_str()) {
+                continue;
+            }
+            entries.push((name.clone(), read_capped(f, &name, caps, &mut total)?));
+        }
+    } else if bytes.len() > 262 && &bytes[257..262] == b"ustar" {
+        let mut ar = tar::Archive::new(bytes);
+        for e in ar.entries().map_err(|e| anyhow!("malformed tar archive: {e}"))? {
+            let e = e.map_err(|e| anyhow!("malformed tar archive: {e}"))?;
+            if e.header().entry_type() != tar::EntryType::Regular {
+                continue;
+            }
+            let name = e.path()?.to_string_lossy().into_owned();
+            if !ARCHIVE_FILES.contains(&name.as_str()) {
+                continue;
+            }
+            entries.push((name.clone(), read_capped(e, &name, caps, &mut total)?));
+        }
+    } else {
+        return Err(anyhow!("unsupported archive format (expected tar or zip)"));
+    }
+
+    let take = |name: &str| -> Result<serde_json::Value> {
+        let mut found = entries.iter().filter(|(n, _)| n == name);
+        let (_, data) = found.next().ok_or_else(|| anyhow!("archive is missing {name}"))?;
+        if found.next().is_some() {
+            return Err(anyhow!("archive contains {name} more than once"));
+        }
+        serde_json::from_slice(data).map_err(|e| anyhow!("invalid json in {name}: {e}"))
+    };
+
+    Ok(ArchiveBundle {
+        schema: take("schema.json")?,
+        manifest: take("manifest.json")?,
+        proof: take("proof.json")?,
+    })
+}
+
+/// The entries `read_bundle_archive` reads; anything else is skipped.
+const ARCHIVE_FILES: [&str; 3] = ["schema.json", "manifest.json", "proof.json"];
+
+/// Read one entry, capped at `caps.entry` and at what is left of the
+/// `caps.total` budget shared by all entries (`total` so far).
+fn read_capped<R: Read>(r: R, name: &str, caps: ArchiveCaps, total: &mut u64) -> Result<Vec<u8>> {
+    let budget = caps.total - *total;
+    let mut buf = Vec::new();
+    r.take(caps.entry.min(budget) + 1).read_to_end(&mut buf)?;
+    let len = buf.len() as u64;
+    if len > caps.entry {
+        return Err(anyhow!("archive entry {name} exceeds {} bytes", caps.entry));
+    }
+    if len > budget {
+        return Err(anyhow!("archive contents exceed {} bytes when decompressed", caps.total));
+    }
+    *total += len;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::export::{write_bundle_archive, ArchiveFormat};
+
+    #[test]
+    fn reads_bom_prefixed_json() {
+        let td = tempfile::TempDir::new().unwrap();
+        let p = td.path().join("bom.json");
+        fs::write(&p, b"\xEF\xBB\xBF{\"name\":\"demo\"}").unwrap();
+        assert_eq!(read_json_file(&p).unwrap(), serde_json::json!({"name": "demo"}));
+    }
+
+    #[test]
+    fn rejects_non_utf8_json_with_offset() {
+        let td = tempfile::TempDir::new().unwrap();
+        let p = td.path().join("latin1.json");
+        // BOM, then `{"a":"` and a Latin-1 'é' (0xE9) at file offset 9.
+        fs::write(&p, b"\xEF\xBB\xBF{\"a\":\"\xE9\"}").unwrap();
+        let err = read_json_file(&p).unwrap_err().to_string();
+        assert!(err.contains("not valid UTF-8"), "{err}");
+        assert!(err.contains("offset 9"), "{err}");
+    }
+
+    #[test]
+    fn petstore_yaml_detects_as_openapi() {
+        use signia_core::pipeline::detect::{detect_input_kind, DetectedKind};
+
+        let p = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures/openapi_petstore/petstore.yaml");
+        let v = read_input_file(&p).unwrap();
+        assert_eq!(v["openapi"], "3.0.0");
+        assert_eq!(detect_input_kind(&v).kind, DetectedKind::OpenApi);
+    }
+
+    #[test]
+    fn yaml_integer_keys_become_strings() {
+        let td = tempfile::TempDir::new().unwrap();
+        let p = td.path().join("spec.yml");
+        fs::write(&p, "paths:\n  /a:\n    get:\n      responses:\n        200:\n          description: OK\n").unwrap();
+        let v = read_input_file(&p).unwrap();
+        assert_eq!(v["paths"]["/a"]["get"]["responses"]["200"]["description"], "OK");
+    }
+
+    #[test]
+    fn reads_back_written_archives() {
+        let td = tempfile::TempDir::new().unwrap();
+        let schema = serde_json::json!({"version": "v1"});
+        let manifest = serde_json::json!({"version": "v1", "kind": "m"});
+        let proof = serde_json::json!({"version": "v1", "root": "00"});
+
+        for format in [ArchiveFormat::Tar, ArchiveFormat::Zip] {
+            let p = td.path().join(format!("bundle.{}", format.extension()));
+            write_bundle_archive(&p, &schema, &manifest, &proof, format).unwrap();
+            let b = read_bundle_archive(&p).unwrap();
+            assert_eq!(b.schema, schema);
+            assert_eq!(b.manifest, manifest);
+            assert_eq!(b.proof, proof);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_archives() {
+        let td = tempfile::TempDir::new().unwrap();
+
+        let p = td.path().join("junk.tar");
+        fs::write(&p, b"not an archive").unwrap();
+        let err = read_bundle_archive(&p).unwrap_err().to_string();
+        assert!(err.contains("unsupported archive format"), "{err}");
+
+        let p = td.path().join("partial.tar");
+        let mut b = tar::Builder::new(Vec::new());
+        let mut h = tar::Header::new_gnu();
+        h.set_path("schema.json").unwrap();
+        h.set_size(2);
+        h.set_cksum();
+        b.append(&h, &b"{}"[..]).unwrap();
+        fs::write(&p, b.into_inner().unwrap()).unwrap();
+        let err = read_bundle_archive(&p).unwrap_err().to_string();
+        assert!(err.contains("missing manifest.json"), "{err}");
+    }
+
+    fn tar_of(entries: &[(String, Vec<u8>)]) -> Vec<u8> {
+        let mut b = tar::Builder::new(Vec::new());
+        for (name, data) in entries {
+            let mut h = tar::Header::new_gnu();
+            h.set_path(name).unwrap();
+            h.set_size(data.len() as u64);
+            h.set_cksum();
+            b.append(&h, data.as_slice()).unwrap();
+        }
+        b.into_inner().unwrap()
+    }
+
+    #[test]
+    fn caps_decompressed_archive_contents() {
+        let caps = ArchiveCaps { entry: 1024, total: 2048 };
+        let json = |n: usize| {
+            let mut v = br#"{"pad":""#.to_vec();
+            v.resize(n - 2, b'x');
+            v.extend_from_slice(br#""}"#);
+            v
+        };
+        let bundle = |size: usize| -> Vec<(String, Vec<u8>)> {
+            ["schema.json", "manifest.json", "proof.json"].iter().map(|n| (n.to_string(), json(size))).collect()
+        };
+
+        // Oversized junk entries are skipped without being read.
+        let mut entries: Vec<_> = (0..100).map(|i| (format!("junk/{i}.bin"), vec![0u8; 4096])).collect();
+        entries.extend(bundle(100));
+        let b = parse_bundle_archive(&tar_of(&entries), caps).unwrap();
+        assert_eq!(b.proof["pad"].as_str().unwrap().len(), 100 - 10);
+
+        let err = parse_bundle_archive(&tar_of(&bundle(1025)), caps).unwrap_err().to_string();
+        assert_eq!(err, "archive entry schema.json exceeds 1024 bytes");
+
+        // Each entry is under the per-entry cap, but together they are not.
+        let err = parse_bundle_archive(&tar_of(&bundle(1000)), caps).unwrap_err().to_string();
+        assert_eq!(err, "archive contents exceed 2048 bytes when decompressed");
+    }
+}
@@ -1,2 +1,5 @@
+pub mod artifact;
 pub mod export;
 pub mod input;
+
+pub use artifact::{load_artifact, LoadedArtifact};
@@ -12,4 +12,9 @@ impl SolanaClient {
         }
         Ok(Self { cluster: cluster.to_string() })
     }
+
+    /// Public JSON-RPC endpoint for the cluster.
+    pub fn rpc_url(&self) -> String {
+        format!("https://api.{}.solana.com", self.cluster)
+    }
 }
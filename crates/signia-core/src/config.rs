@@ -9,6 +9,9 @@
 
 use crate::errors::{SigniaError, SigniaResult};
 
+#[cfg(feature = "canonical-json")]
+use serde::{Deserialize, Serialize};
+
 /// Global configuration container.
 #[derive(Debug, Clone)]
 pub struct CoreConfig {
@@ -28,7 +31,9 @@ impl Default for CoreConfig {
 }
 
 /// Normalization-related configuration.
-#[derive(Debug, Clone)]
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NormalizationConfig {
     pub path_root: String,
     pub newline: NewlineMode,
@@ -66,7 +71,9 @@ impl Default for HashingConfig {
 }
 
 /// Resource and complexity limits.
-#[derive(Debug, Clone)]
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LimitsConfig {
     pub max_total_bytes: u64,
     pub max_file_bytes: u64,
@@ -92,6 +99,8 @@ impl Default for LimitsConfig {
 }
 
 /// Supported newline normalization modes.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "lowercase"))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NewlineMode {
     Lf,
@@ -106,8 +115,10 @@ impl NewlineMode {
 }
 
 /// Supported encodings.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Encoding {
+    #[cfg_attr(feature = "canonical-json", serde(rename = "utf-8"))]
     Utf8,
 }
 
@@ -120,6 +131,8 @@ impl Encoding {
 }
 
 /// Symlink handling policy.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "kebab-case"))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SymlinkPolicy {
     Deny,
@@ -136,7 +149,11 @@ impl SymlinkPolicy {
 }
 
 /// Network access policy.
+///
+/// Serialized as "deny" / "allow-pinned-only"; no other spelling is accepted.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "kebab-case"))]
 pub enum NetworkPolicy {
     Deny,
     AllowPinnedOnly,
@@ -149,6 +166,17 @@ impl NetworkPolicy {
             Self::AllowPinnedOnly => "allow-pinned-only",
         }
     }
+
+    /// Parse the wire form. Matching is exact (no case folding or trimming).
+    pub fn parse(s: &str) -> SigniaResult<Self> {
+        match s {
+            "deny" => Ok(Self::Deny),
+            "allow-pinned-only" => Ok(Self::AllowPinnedOnly),
+            other => Err(SigniaError::invalid_argument(format!(
+                "unknown network policy {other:?} (expected \"deny\" or \"allow-pinned-only\")"
+            ))),
+        }
+    }
 }
 
 /// Supported hash algorithms.
@@ -213,4 +241,24 @@ mod tests {
         cfg.hashing.domain = "".to_string();
         assert!(validate_config(&cfg).is_err());
     }
+
+    #[test]
+    fn network_policy_parses_documented_values_only() {
+        for p in [NetworkPolicy::Deny, NetworkPolicy::AllowPinnedOnly] {
+            assert_eq!(NetworkPolicy::parse(p.as_str()).unwrap(), p);
+        }
+        for bad in ["Deny", "none", " deny", "allow", ""] {
+            assert!(NetworkPolicy::parse(bad).is_err(), "{bad:?}");
+        }
+    }
+
+    #[cfg(feature = "canonical-json")]
+    #[test]
+    fn network_policy_serde_is_strict() {
+        let p: NetworkPolicy = serde_json::from_str("\"allow-pinned-only\"").unwrap();
+        assert_eq!(p, NetworkPolicy::AllowPinnedOnly);
+        assert_eq!(serde_json::to_string(&NetworkPolicy::Deny).unwrap(), "\"deny\"");
+        assert!(serde_json::from_str::<NetworkPolicy>("\"Deny\"").is_err());
+        assert!(serde_json::from_str::<NetworkPolicy>("\"none\"").is_err());
+    }
 }
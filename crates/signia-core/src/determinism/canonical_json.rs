@@ -6,7 +6,10 @@
 //! Canonical JSON rules enforced here:
 //! - Object keys are sorted lexicographically
 //! - Arrays preserve order
-//! - Numbers are preserved exactly (callers must avoid non-deterministic floats)
+//! - Numbers are normalized so logically equal values share one encoding
+//!   (see `canonicalize_number`)
+//! - Floats that are not exact integers are rejected by default; see
+//!   `FloatPolicy` for the opt-in alternatives
 //! - Strings are preserved as UTF-8
 //! - No implicit defaults are inserted
 //!
@@ -14,22 +17,80 @@
 
 use crate::errors::{SigniaError, SigniaResult};
 
-use serde_json::{Map, Value};
+use serde_json::{Map, Number, Value};
 
-/// Canonicalize a JSON value recursively.
+/// 2^53: every integer of at most this magnitude is exactly representable as f64.
+const MAX_EXACT_INT_F64: f64 = 9_007_199_254_740_992.0;
+
+/// What to do with a number that is still a float after `canonicalize_number`:
+/// a fractional value (`0.1`) or an integer-valued one beyond 2^53 (`1e20`).
+///
+/// Float text is a classic determinism hazard (precision drift, `1.0` vs `1`
+/// across producers), so hashing paths (schema and manifest hashes,
+/// `canonical_len`) reject such numbers. Producers that need fractional values
+/// encode them as strings or integers, or opt into `RoundTripString`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatPolicy {
+    /// Error on the first such number.
+    #[default]
+    Reject,
+    /// Encode it as a JSON string holding its shortest round-trip form
+    /// (`0.1` -> `"0.1"`, `1e20` -> `"1e+20"`).
+    RoundTripString,
+    /// Keep it as a JSON number, written in serde_json's shortest round-trip form.
+    AllowNative,
+}
+
+/// Options for canonical JSON encoding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CanonicalJsonOptions {
+    /// Handling of non-integer floats. Defaults to `FloatPolicy::Reject`.
+    pub float_policy: FloatPolicy,
+}
+
+/// Canonicalize a JSON value recursively with the default options.
 ///
 /// This function produces a new `Value` where:
 /// - All objects have keys sorted
 /// - All nested objects are also canonicalized
+/// - All numbers are normalized with `canonicalize_number`
+/// - Remaining floats are rejected (`FloatPolicy::Reject`)
 ///
-/// This function does not modify arrays order.
+/// This function does not modify arrays order. Non-finite numbers (NaN, ±inf)
+/// are an error under every policy: they have no JSON encoding. `serde_json`
+/// normally refuses to build them, but with its `arbitrary_precision` feature
+/// enabled anywhere in the build, text like `1e400` parses to a number whose
+/// `f64` value is infinite.
 pub fn canonicalize(value: &Value) -> SigniaResult<Value> {
+    canonicalize_with(value, &CanonicalJsonOptions::default())
+}
+
+/// Canonicalize a JSON value recursively with explicit options.
+pub fn canonicalize_with(value: &Value, opts: &CanonicalJsonOptions) -> SigniaResult<Value> {
     match value {
-        Value::Object(map) => canonicalize_object(map),
+        Value::Object(map) => canonicalize_object(map, opts),
+        Value::Number(n) => {
+            if n.as_f64().is_some_and(|f| !f.is_finite()) {
+                return Err(SigniaError::canonicalization(format!(
+                    "non-finite number cannot be canonicalized: {n}"
+                )));
+            }
+            let n = canonicalize_number(n);
+            if !n.is_f64() {
+                return Ok(Value::Number(n));
+            }
+            match opts.float_policy {
+                FloatPolicy::Reject => Err(SigniaError::canonicalization(format!(
+                    "non-integer float {n} is rejected by the canonical float policy"
+                ))),
+                FloatPolicy::RoundTripString => Ok(Value::String(n.to_string())),
+                FloatPolicy::AllowNative => Ok(Value::Number(n)),
+            }
+        }
         Value::Array(arr) => {
             let mut out = Vec::with_capacity(arr.len());
             for v in arr {
-                out.push(canonicalize(v)?);
+                out.push(canonicalize_with(v, opts)?);
             }
             Ok(Value::Array(out))
         }
@@ -37,7 +98,26 @@ pub fn canonicalize(value: &Value) -> SigniaResult<Value> {
     }
 }
 
-fn canonicalize_object(map: &Map<String, Value>) -> SigniaResult<Value> {
+/// Normalize a JSON number (integer policy).
+///
+/// Rules:
+/// - integers (`i64`/`u64`) are kept as-is
+/// - `-0.0` becomes `0`
+/// - integer-valued floats with magnitude <= 2^53 become integers (`1.0` -> `1`,
+///   `-3e2` -> `-300`); within that range the conversion is exact
+/// - other floats (fractional, or integer-valued beyond 2^53) are kept and
+///   encoded in serde_json's shortest round-trip form
+pub fn canonicalize_number(n: &Number) -> Number {
+    match n.as_f64() {
+        Some(f) if n.is_f64() && f.fract() == 0.0 && f.abs() <= MAX_EXACT_INT_F64 => {
+            // `f as i64` maps -0.0 to 0.
+            Number::from(f as i64)
+        }
+        _ => n.clone(),
+    }
+}
+
+fn canonicalize_object(map: &Map<String, Value>, opts: &CanonicalJsonOptions) -> SigniaResult<Value> {
     let mut keys: Vec<&String> = map.keys().collect();
     keys.sort();
 
@@ -46,7 +126,7 @@ fn canonicalize_object(map: &Map<String, Value>) -> SigniaResult<Value> {
         let v = map.get(k).ok_or_else(|| {
             SigniaError::invariant("key disappeared during canonicalization")
         })?;
-        out.insert(k.clone(), canonicalize(v)?);
+        out.insert(k.clone(), canonicalize_with(v, opts)?);
     }
 
     Ok(Value::Object(out))
@@ -54,9 +134,15 @@ fn canonicalize_object(map: &Map<String, Value>) -> SigniaResult<Value> {
 
 /// Convert a JSON value into a canonical UTF-8 byte representation.
 ///
-/// This representation is stable across machines and runs.
+/// This representation is stable across machines and runs. Uses the default
+/// options, so non-integer floats are an error.
 pub fn to_canonical_bytes(value: &Value) -> SigniaResult<Vec<u8>> {
-    let canonical = canonicalize(value)?;
+    to_canonical_bytes_with(value, &CanonicalJsonOptions::default())
+}
+
+/// `to_canonical_bytes` with explicit options.
+pub fn to_canonical_bytes_with(value: &Value, opts: &CanonicalJsonOptions) -> SigniaResult<Vec<u8>> {
+    let canonical = canonicalize_with(value, opts)?;
     serde_json::to_vec(&canonical)
         .map_err(|e| SigniaError::serialization(format!("failed to serialize canonical JSON: {e}")))
 }
@@ -97,6 +183,73 @@ mod tests {
         assert!(canonical_eq(&a, &b).unwrap());
     }
 
+    fn canon_str(v: Value) -> String {
+        String::from_utf8(to_canonical_bytes(&v).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn negative_zero_is_zero() {
+        assert_eq!(canon_str(serde_json::json!(-0.0)), "0");
+        assert_eq!(canon_str(serde_json::json!(0.0)), "0");
+        assert!(canonical_eq(&serde_json::json!({"a": -0.0}), &serde_json::json!({"a": 0})).unwrap());
+    }
+
+    #[test]
+    fn integer_valued_floats_become_integers() {
+        assert_eq!(canon_str(serde_json::json!(1.0)), "1");
+        assert_eq!(canon_str(serde_json::json!(-300.0)), "-300");
+        assert_eq!(canon_str(serde_json::json!(9007199254740992.0)), "9007199254740992");
+        assert_eq!(canon_str(serde_json::json!([2.0, [3.0], {"x": 4.0}])), r#"[2,[3],{"x":4}]"#);
+        assert!(canonical_eq(&serde_json::json!(1.0), &serde_json::json!(1)).unwrap());
+    }
+
+    fn canon_str_with(v: Value, float_policy: FloatPolicy) -> SigniaResult<String> {
+        let bytes = to_canonical_bytes_with(&v, &CanonicalJsonOptions { float_policy })?;
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+
+    #[test]
+    fn other_numbers_are_preserved() {
+        let native = |v| canon_str_with(v, FloatPolicy::AllowNative).unwrap();
+        assert_eq!(native(serde_json::json!(1.5)), "1.5");
+        assert_eq!(native(serde_json::json!(-0.25)), "-0.25");
+        assert_eq!(canon_str(serde_json::json!(u64::MAX)), u64::MAX.to_string());
+        assert_eq!(canon_str(serde_json::json!(i64::MIN)), i64::MIN.to_string());
+        // Beyond 2^53 an integer-valued float may not be the integer it was written as.
+        assert_eq!(native(serde_json::json!(1e20)), "1e+20");
+    }
+
+    #[test]
+    fn float_policy_applies_to_non_integer_floats() {
+        let cases = [
+            (serde_json::json!(0.1), "0.1"),
+            (serde_json::json!(1e20), "1e+20"),
+            (serde_json::json!(-1.7976931348623157e308), "-1.7976931348623157e+308"),
+            (serde_json::json!(5e-324), "5e-324"),
+        ];
+        for (v, text) in cases {
+            let err = canon_str_with(v.clone(), FloatPolicy::Reject).unwrap_err();
+            assert!(err.to_string().contains("non-integer float"), "{err}");
+            assert_eq!(canon_str_with(v.clone(), FloatPolicy::AllowNative).unwrap(), text);
+            assert_eq!(canon_str_with(v, FloatPolicy::RoundTripString).unwrap(), format!("\"{text}\""));
+        }
+
+        // Exact integers pass under every policy, including `1.0` and 2^53.
+        for policy in [FloatPolicy::Reject, FloatPolicy::RoundTripString, FloatPolicy::AllowNative] {
+            assert_eq!(canon_str_with(serde_json::json!(1.0), policy).unwrap(), "1");
+            assert_eq!(canon_str_with(serde_json::json!(9007199254740992.0), policy).unwrap(), "9007199254740992");
+            assert_eq!(canon_str_with(serde_json::json!({"b": [1.0], "a": 2}), policy).unwrap(), r#"{"a":2,"b":[1]}"#);
+        }
+
+        // Nested floats are found, and the default is Reject.
+        assert_eq!(CanonicalJsonOptions::default().float_policy, FloatPolicy::Reject);
+        assert!(to_canonical_bytes(&serde_json::json!({"a": [{"b": 0.5}]})).is_err());
+        assert_eq!(
+            canon_str_with(serde_json::json!({"a": [{"b": 0.5}]}), FloatPolicy::RoundTripString).unwrap(),
+            r#"{"a":[{"b":"0.5"}]}"#
+        );
+    }
+
     #[test]
     fn canonical_eq_false_for_different_values() {
         let a = serde_json::json!({"a":1});
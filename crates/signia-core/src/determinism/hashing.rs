@@ -7,40 +7,106 @@
 //! - explicitly parameterized
 //!
 //! Supported algorithms:
-//! - sha256
+//! - sha256 (default)
+//! - blake3 (requires the `blake3` feature)
 //!
-//! No implicit defaults are allowed. Callers must choose algorithms explicitly.
+//! Domain separation is identical for both algorithms; only the digest
+//! function changes. Merkle helpers take the algorithm name as recorded in
+//! `ProofV1::hash_alg`, so proofs are recomputed with the algorithm they name.
 
 use crate::errors::{SigniaError, SigniaResult};
 
 use sha2::{Digest, Sha256};
 
 /// Hash algorithm identifier.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum HashAlg {
+    #[default]
     Sha256,
+    #[cfg(feature = "blake3")]
+    Blake3,
 }
 
 impl HashAlg {
     pub fn from_str(s: &str) -> SigniaResult<Self> {
         match s {
             "sha256" => Ok(HashAlg::Sha256),
+            #[cfg(feature = "blake3")]
+            "blake3" => Ok(HashAlg::Blake3),
             _ => Err(SigniaError::invalid_argument(format!(
                 "unsupported hash algorithm: {s}"
             ))),
         }
     }
+
+    /// Stable name, as accepted by `from_str`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlg::Sha256 => "sha256",
+            #[cfg(feature = "blake3")]
+            HashAlg::Blake3 => "blake3",
+        }
+    }
 }
 
-/// Hash raw bytes using the selected algorithm.
-pub fn hash_bytes(alg: HashAlg, bytes: &[u8]) -> Vec<u8> {
-    match alg {
-        HashAlg::Sha256 => {
-            let mut h = Sha256::new();
-            h.update(bytes);
-            h.finalize().to_vec()
+/// Incremental hasher for content fed in chunks (e.g. large files).
+///
+/// Any chunking of the same bytes yields the same digest as `hash_bytes` over
+/// their concatenation.
+#[derive(Clone)]
+pub struct Hasher {
+    inner: HasherInner,
+}
+
+#[derive(Clone)]
+enum HasherInner {
+    Sha256(Sha256),
+    #[cfg(feature = "blake3")]
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    /// Create a hasher for `alg`.
+    pub fn new(alg: HashAlg) -> Self {
+        let inner = match alg {
+            HashAlg::Sha256 => HasherInner::Sha256(Sha256::new()),
+            #[cfg(feature = "blake3")]
+            HashAlg::Blake3 => HasherInner::Blake3(Box::default()),
+        };
+        Self { inner }
+    }
+
+    /// Feed the next chunk of content.
+    pub fn update(&mut self, chunk: &[u8]) {
+        match &mut self.inner {
+            HasherInner::Sha256(h) => h.update(chunk),
+            #[cfg(feature = "blake3")]
+            HasherInner::Blake3(h) => {
+                h.update(chunk);
+            }
         }
     }
+
+    /// Finish and return the raw digest.
+    pub fn finalize(self) -> Vec<u8> {
+        match self.inner {
+            HasherInner::Sha256(h) => h.finalize().to_vec(),
+            #[cfg(feature = "blake3")]
+            HasherInner::Blake3(h) => h.finalize().as_bytes().to_vec(),
+        }
+    }
+
+    /// Finish and return the digest as lowercase hex.
+    pub fn finalize_hex(self) -> String {
+        hex::encode(self.finalize())
+    }
+}
+
+/// Hash raw bytes using the selected algorithm.
+pub fn hash_bytes(alg: HashAlg, bytes: &[u8]) -> Vec<u8> {
+    let mut h = Hasher::new(alg);
+    h.update(bytes);
+    h.finalize()
 }
 
 /// Hash raw bytes and return lowercase hex string.
@@ -74,6 +140,14 @@ pub fn hash_merkle_node_hex(alg: &str, left_hex: &str, right_hex: &str) -> Signi
     Ok(hex::encode(hash_bytes(alg, &buf)))
 }
 
+/// Domain-separated root of an empty leaf set.
+///
+/// This is `hash(MERKLE_EMPTY)`, which cannot collide with any leaf or node hash.
+pub fn hash_merkle_empty_hex(alg: &str) -> SigniaResult<String> {
+    let alg = HashAlg::from_str(alg)?;
+    Ok(hex::encode(hash_bytes(alg, crate::domain::MERKLE_EMPTY.as_bytes())))
+}
+
 #[cfg(feature = "canonical-json")]
 use crate::determinism::canonical_json;
 
@@ -100,6 +174,19 @@ pub fn hash_manifest_v1_hex(manifest: &crate::model::v1::ManifestV1) -> SigniaRe
     })?)
 }
 
+/// Hash ProofV1: `domain::PROOF` followed by the canonical JSON of
+/// `proof.canonical_form()`.
+///
+/// This is the proof's content id. Unlike the Merkle root it also covers the
+/// hash algorithm, inclusions and meta, and it is stable under leaf reordering.
+#[cfg(feature = "canonical-json")]
+pub fn hash_proof_v1_hex(proof: &crate::model::v1::ProofV1) -> SigniaResult<String> {
+    let canonical = canonical_json::to_canonical_bytes(&serde_json::to_value(proof.canonical_form())?)?;
+    let mut buf = crate::domain::PROOF.as_bytes().to_vec();
+    buf.extend_from_slice(&canonical);
+    hash_bytes_hex(&buf)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,4 +204,93 @@ mod tests {
         let node = hash_merkle_node_hex("sha256", &leaf, &leaf).unwrap();
         assert!(!node.is_empty());
     }
+
+    #[test]
+    fn chunked_hasher_matches_one_shot() {
+        let buf: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+
+        for alg in [
+            HashAlg::Sha256,
+            #[cfg(feature = "blake3")]
+            HashAlg::Blake3,
+        ] {
+            let mut one = Hasher::new(alg);
+            one.update(&buf);
+            let one = one.finalize_hex();
+
+            let mut chunked = Hasher::new(alg);
+            for chunk in [&buf[..1], &buf[1..4097], &buf[4097..]] {
+                chunked.update(chunk);
+            }
+            assert_eq!(chunked.finalize_hex(), one, "{}", alg.as_str());
+            assert_eq!(hex::encode(hash_bytes(alg, &buf)), one);
+        }
+        assert_eq!(hash_bytes_hex(&buf).unwrap(), {
+            let mut h = Hasher::new(HashAlg::Sha256);
+            h.update(&buf);
+            h.finalize_hex()
+        });
+    }
+
+    #[test]
+    fn default_alg_is_sha256() {
+        assert_eq!(HashAlg::default(), HashAlg::Sha256);
+        assert_eq!(HashAlg::from_str(HashAlg::default().as_str()).unwrap(), HashAlg::Sha256);
+        assert!(HashAlg::from_str("md5").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "blake3")]
+    fn blake3_leaves_and_nodes_differ_from_sha256_and_are_stable() {
+        assert_eq!(HashAlg::from_str("blake3").unwrap(), HashAlg::Blake3);
+        assert_eq!(HashAlg::Blake3.as_str(), "blake3");
+
+        let leaf_sha = hash_merkle_leaf_hex("sha256", b"x").unwrap();
+        let leaf_b3 = hash_merkle_leaf_hex("blake3", b"x").unwrap();
+        assert_ne!(leaf_sha, leaf_b3);
+        assert_eq!(leaf_b3, hash_merkle_leaf_hex("blake3", b"x").unwrap());
+        assert_eq!(leaf_b3.len(), 64);
+
+        let node_sha = hash_merkle_node_hex("sha256", &leaf_b3, &leaf_b3).unwrap();
+        let node_b3 = hash_merkle_node_hex("blake3", &leaf_b3, &leaf_b3).unwrap();
+        assert_ne!(node_sha, node_b3);
+        assert_eq!(node_b3, hash_merkle_node_hex("blake3", &leaf_b3, &leaf_b3).unwrap());
+
+        assert_ne!(hash_merkle_empty_hex("sha256").unwrap(), hash_merkle_empty_hex("blake3").unwrap());
+
+        // Domain-separated: not the plain blake3 of the payload.
+        assert_ne!(leaf_b3, hex::encode(hash_bytes(HashAlg::Blake3, b"x")));
+    }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn proof_hash_ignores_leaf_order() {
+        use crate::model::v1::{LeafV1, ProofV1};
+        use crate::proof::leaf_keys;
+
+        let leaf = |k: &str, v: &str| LeafV1 {
+            key: k.to_string(),
+            value: v.repeat(64),
+        };
+        let mut a = ProofV1::new("sha256", "0".repeat(64));
+        a.leaves = vec![
+            leaf(leaf_keys::SCHEMA_HASH, "a"),
+            leaf(leaf_keys::MANIFEST_HASH, "b"),
+            leaf(leaf_keys::META_KIND, "c"),
+        ];
+        let mut b = a.clone();
+        b.leaves.reverse();
+
+        let h = hash_proof_v1_hex(&a).unwrap();
+        assert_eq!(h, hash_proof_v1_hex(&b).unwrap());
+        assert_eq!(h, hash_proof_v1_hex(&a.canonical_form()).unwrap());
+        assert_ne!(h, a.root);
+
+        // Domain-separated from the plain canonical JSON hash.
+        let plain = hash_canonical_json_hex(&serde_json::to_value(a.canonical_form()).unwrap()).unwrap();
+        assert_ne!(h, plain);
+
+        b.leaves[0].value = "d".repeat(64);
+        assert_ne!(h, hash_proof_v1_hex(&b).unwrap());
+    }
 }
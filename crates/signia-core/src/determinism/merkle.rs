@@ -7,12 +7,57 @@
 //! - no hidden defaults
 //! - reproducible roots across machines
 //!
-//! This implementation is append-only and intended for small to medium leaf sets
-//! used in schema/manifest proofs.
+//! `MerkleTree` is append-only and intended for small to medium leaf sets used in
+//! schema/manifest proofs. For very large leaf counts (e.g. dataset chunks), use
+//! `StreamingMerkleBuilder`, which keeps only O(log n) partial nodes in memory and
+//! produces the same root.
 
 use crate::errors::{SigniaError, SigniaResult};
 
-use crate::determinism::hashing::{hash_merkle_leaf_hex, hash_merkle_node_hex};
+use crate::determinism::hashing::{hash_merkle_empty_hex, hash_merkle_leaf_hex, hash_merkle_node_hex};
+use crate::model::v1::{Side, SiblingV1};
+
+#[cfg(feature = "canonical-json")]
+use serde::{Deserialize, Serialize};
+
+/// How a level with an odd number of nodes is folded into the next one.
+///
+/// The choice changes every root over a leaf count that is not a power of two,
+/// so producers record it in `ProofV1::merkle_odd_policy` and verifiers use the
+/// recorded value.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "lowercase"))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OddNodePolicy {
+    /// Pair the lone last node with itself: `hash(node || node)`.
+    #[default]
+    Duplicate,
+    /// Carry the lone last node up to the next level unchanged.
+    Promote,
+}
+
+impl OddNodePolicy {
+    /// Parse a policy name ("duplicate" | "promote").
+    pub fn parse(s: &str) -> SigniaResult<Self> {
+        match s {
+            "duplicate" => Ok(Self::Duplicate),
+            "promote" => Ok(Self::Promote),
+            _ => Err(SigniaError::invalid_argument(format!("unsupported Merkle odd policy: {s}"))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Duplicate => "duplicate",
+            Self::Promote => "promote",
+        }
+    }
+
+    /// True for the v1 default, which proofs leave implicit on the wire.
+    pub fn is_duplicate(&self) -> bool {
+        *self == Self::Duplicate
+    }
+}
 
 /// Domain constants are defined in `crate::domain`.
 #[derive(Debug, Clone)]
@@ -20,12 +65,23 @@ pub struct MerkleTreeOptions {
     pub hash_alg: String,
     pub domain_leaf: String,
     pub domain_node: String,
+    /// Folding rule for odd-sized levels.
+    pub odd_policy: OddNodePolicy,
+}
+
+/// One step of an inclusion path: a sibling hash and the side it sits on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerklePathStep {
+    pub sibling_hex: String,
+    /// True if the sibling is the left operand of the parent hash.
+    pub sibling_is_left: bool,
 }
 
 /// Deterministic Merkle tree.
 ///
 /// Leaves are hashed in insertion order.
-/// Internal nodes are built bottom-up with left/right concatenation.
+/// Internal nodes are built bottom-up with left/right concatenation; a lone
+/// last node is handled per `MerkleTreeOptions::odd_policy`.
 #[derive(Debug, Clone)]
 pub struct MerkleTree {
     opts: MerkleTreeOptions,
@@ -65,37 +121,247 @@ impl MerkleTree {
         let mut level = self.leaves.clone();
 
         while level.len() > 1 {
-            let mut next = Vec::new();
-            let mut i = 0;
-            while i < level.len() {
-                let left = &level[i];
-                let right = if i + 1 < level.len() {
-                    &level[i + 1]
-                } else {
-                    // Duplicate last hash if odd number of nodes
-                    &level[i]
-                };
-
-                let parent = hash_merkle_node_hex(
-                    self.opts.hash_alg.as_str(),
-                    left,
-                    right,
-                )?;
-                next.push(parent);
-                i += 2;
-            }
-            level = next;
+            level = self.parent_level(&level)?;
         }
 
         Ok(level[0].clone())
     }
 
+    /// Sibling path from leaf `index` up to the root, bottom-up.
+    ///
+    /// At an odd level the last node is paired with itself under
+    /// `OddNodePolicy::Duplicate`, so its sibling is its own hash on the right.
+    /// Under `OddNodePolicy::Promote` it has no sibling and contributes no step.
+    /// Either way this matches `root_hex`.
+    pub fn inclusion_path(&self, index: usize) -> SigniaResult<Vec<MerklePathStep>> {
+        if index >= self.leaves.len() {
+            return Err(SigniaError::invalid_argument(format!(
+                "leaf index {index} out of range ({} leaves)",
+                self.leaves.len()
+            )));
+        }
+
+        let mut path = Vec::new();
+        let mut level = self.leaves.clone();
+        let mut i = index;
+
+        while level.len() > 1 {
+            let sibling = if i % 2 == 1 {
+                Some((level[i - 1].clone(), true))
+            } else if let Some(right) = level.get(i + 1) {
+                Some((right.clone(), false))
+            } else {
+                match self.opts.odd_policy {
+                    OddNodePolicy::Duplicate => Some((level[i].clone(), false)),
+                    OddNodePolicy::Promote => None,
+                }
+            };
+            if let Some((sibling_hex, sibling_is_left)) = sibling {
+                path.push(MerklePathStep {
+                    sibling_hex,
+                    sibling_is_left,
+                });
+            }
+            level = self.parent_level(&level)?;
+            i /= 2;
+        }
+
+        Ok(path)
+    }
+
+    /// Inclusion proof siblings for leaf `index`, in the `ProofV1` wire form.
+    ///
+    /// Applying the siblings in order to the leaf hash (see
+    /// `pipeline::verify::verify_inclusion`) reproduces `root_hex`.
+    pub fn inclusion_proof(&self, leaf_index: usize) -> SigniaResult<Vec<SiblingV1>> {
+        Ok(self
+            .inclusion_path(leaf_index)?
+            .into_iter()
+            .map(|s| SiblingV1 {
+                side: if s.sibling_is_left { Side::Left } else { Side::Right },
+                hash: s.sibling_hex,
+            })
+            .collect())
+    }
+
+    /// Inclusion proof for the first leaf whose payload is `payload`.
+    pub fn inclusion_proof_for_payload(&self, payload: &[u8]) -> SigniaResult<Vec<SiblingV1>> {
+        let h = hash_merkle_leaf_hex(self.opts.hash_alg.as_str(), payload)?;
+        let index = self
+            .leaves
+            .iter()
+            .position(|l| *l == h)
+            .ok_or_else(|| SigniaError::invalid_argument("payload is not a leaf of this tree"))?;
+        self.inclusion_proof(index)
+    }
+
+    fn parent_level(&self, level: &[String]) -> SigniaResult<Vec<String>> {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let alg = self.opts.hash_alg.as_str();
+        for pair in level.chunks(2) {
+            let parent = match pair {
+                [left, right] => hash_merkle_node_hex(alg, left, right)?,
+                // Lone last node of an odd level.
+                [last] => match self.opts.odd_policy {
+                    OddNodePolicy::Duplicate => hash_merkle_node_hex(alg, last, last)?,
+                    OddNodePolicy::Promote => last.clone(),
+                },
+                _ => unreachable!("chunks(2) yields one or two nodes"),
+            };
+            next.push(parent);
+        }
+        Ok(next)
+    }
+
+    /// Return the Merkle root, or the well-defined empty root if there are no leaves.
+    ///
+    /// Proofs over an empty leaf set (e.g. empty repos or datasets) use this so they
+    /// still carry a stable, verifiable root. See `hash_merkle_empty_hex`.
+    pub fn root_hex_or_empty(&self) -> SigniaResult<String> {
+        if self.leaves.is_empty() {
+            return hash_merkle_empty_hex(self.opts.hash_alg.as_str());
+        }
+        self.root_hex()
+    }
+
     /// Return all leaf hashes (hex-encoded) in insertion order.
     pub fn leaf_hashes(&self) -> &[String] {
         &self.leaves
     }
 }
 
+/// Root over keyed `(key, value)` leaves, as used by proofs.
+///
+/// Leaves are sorted by key (stable, so duplicate keys keep their order) and each
+/// is hashed as the payload `key=value`. An empty set yields the empty root (see
+/// `MerkleTree::root_hex_or_empty`).
+pub fn keyed_leaves_root_hex(opts: &MerkleTreeOptions, leaves: &[(&str, &str)]) -> SigniaResult<String> {
+    let mut sorted = leaves.to_vec();
+    sorted.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut tree = MerkleTree::new(opts.clone());
+    for (key, value) in sorted {
+        tree.push_leaf(format!("{key}={value}").as_bytes())?;
+    }
+    tree.root_hex_or_empty()
+}
+
+/// Recompute the root of keyed leaves and compare it with `expected_root`.
+///
+/// Lets callers check a root without building a full `ProofV1`.
+pub fn verify_root(opts: &MerkleTreeOptions, leaves: &[(&str, &str)], expected_root: &str) -> SigniaResult<bool> {
+    Ok(keyed_leaves_root_hex(opts, leaves)? == expected_root)
+}
+
+/// Memory-bounded Merkle builder.
+///
+/// Leaves are consumed in order and folded into a "mountain range" of perfect
+/// subtree roots (at most one per level). Only O(log n) hashes are retained.
+///
+/// The resulting root is identical to `MerkleTree::root_hex` for the same leaves
+/// and options, including the odd-level rule (`MerkleTreeOptions::odd_policy`).
+#[derive(Debug, Clone)]
+pub struct StreamingMerkleBuilder {
+    opts: MerkleTreeOptions,
+    /// `peaks[level]` holds the root of a perfect subtree of 2^level leaves, if any.
+    peaks: Vec<Option<String>>,
+    leaf_count: u64,
+}
+
+impl StreamingMerkleBuilder {
+    /// Create a new empty builder.
+    pub fn new(opts: MerkleTreeOptions) -> Self {
+        Self {
+            opts,
+            peaks: Vec::new(),
+            leaf_count: 0,
+        }
+    }
+
+    /// Number of leaves consumed so far.
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    /// Push a raw leaf payload.
+    ///
+    /// The payload is hashed exactly like `MerkleTree::push_leaf`.
+    pub fn push_leaf(&mut self, payload: &[u8]) -> SigniaResult<()> {
+        let h = hash_merkle_leaf_hex(self.opts.hash_alg.as_str(), payload)?;
+        self.push_leaf_hash(h)
+    }
+
+    /// Push an already hashed leaf (hex-encoded).
+    pub fn push_leaf_hash(&mut self, leaf_hex: String) -> SigniaResult<()> {
+        let mut h = leaf_hex;
+        let mut level = 0;
+
+        loop {
+            if level == self.peaks.len() {
+                self.peaks.push(None);
+            }
+            match self.peaks[level].take() {
+                Some(left) => {
+                    h = hash_merkle_node_hex(self.opts.hash_alg.as_str(), &left, &h)?;
+                    level += 1;
+                }
+                None => {
+                    self.peaks[level] = Some(h);
+                    break;
+                }
+            }
+        }
+
+        self.leaf_count += 1;
+        Ok(())
+    }
+
+    /// Return the Merkle root as a lowercase hex string.
+    ///
+    /// Peaks are folded from the lowest level upwards. A node that is the last one
+    /// on an odd-sized level is paired with itself or carried up unchanged,
+    /// per `odd_policy`, matching `MerkleTree`.
+    pub fn root_hex(&self) -> SigniaResult<String> {
+        if self.leaf_count == 0 {
+            return Err(SigniaError::invalid_argument("cannot compute Merkle root of empty tree"));
+        }
+
+        let alg = self.opts.hash_alg.as_str();
+        let mut carry: Option<String> = None;
+
+        for level in 0..self.peaks.len() {
+            let has_higher = self.peaks[level + 1..].iter().any(|p| p.is_some());
+
+            carry = match (&self.peaks[level], carry) {
+                (Some(peak), Some(c)) => Some(hash_merkle_node_hex(alg, peak, &c)?),
+                (Some(peak), None) => {
+                    if !has_higher {
+                        return Ok(peak.clone());
+                    }
+                    Some(self.lift_lone(peak.clone())?)
+                }
+                (None, Some(c)) => {
+                    if !has_higher {
+                        return Ok(c);
+                    }
+                    Some(self.lift_lone(c)?)
+                }
+                (None, None) => None,
+            };
+        }
+
+        carry.ok_or_else(|| SigniaError::invariant("streaming Merkle builder produced no root"))
+    }
+
+    /// Parent of the lone last node of an odd level.
+    fn lift_lone(&self, node: String) -> SigniaResult<String> {
+        match self.opts.odd_policy {
+            OddNodePolicy::Duplicate => hash_merkle_node_hex(self.opts.hash_alg.as_str(), &node, &node),
+            OddNodePolicy::Promote => Ok(node),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,18 +372,66 @@ mod tests {
             hash_alg: "sha256".to_string(),
             domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
             domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy: OddNodePolicy::Duplicate,
         });
         t.push_leaf(b"hello").unwrap();
         let root = t.root_hex().unwrap();
         assert!(!root.is_empty());
     }
 
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn inclusion_proofs_verify_for_every_leaf_of_odd_tree() {
+        use crate::model::v1::{InclusionProofV1, LeafV1, ProofV1};
+        use crate::pipeline::verify::verify_inclusion;
+
+        let mut t = MerkleTree::new(MerkleTreeOptions {
+            hash_alg: "sha256".to_string(),
+            domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
+            domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy: OddNodePolicy::Duplicate,
+        });
+        let leaves: Vec<LeafV1> = (0..7)
+            .map(|i| LeafV1 {
+                key: format!("meta:k{i}"),
+                value: format!("v{i}"),
+            })
+            .collect();
+        for l in &leaves {
+            t.push_leaf(format!("{}={}", l.key, l.value).as_bytes()).unwrap();
+        }
+        let mut proof = ProofV1::new("sha256", t.root_hex().unwrap());
+        proof.leaves = leaves.clone();
+
+        for (i, l) in leaves.iter().enumerate() {
+            let siblings = t.inclusion_proof(i).unwrap();
+            let payload = format!("{}={}", l.key, l.value);
+            assert_eq!(siblings, t.inclusion_proof_for_payload(payload.as_bytes()).unwrap());
+
+            let inc = InclusionProofV1 {
+                key: l.key.clone(),
+                value: l.value.clone(),
+                siblings,
+            };
+            verify_inclusion(&proof, &inc).unwrap();
+        }
+
+        // The last leaf of the odd level is paired with itself on the right.
+        let last = t.inclusion_proof(6).unwrap();
+        assert_eq!(last[0].side, Side::Right);
+        assert_eq!(last[0].hash, t.leaf_hashes()[6]);
+
+        assert!(t.inclusion_proof(7).is_err());
+        assert!(t.inclusion_proof_for_payload(b"meta:k9=v9").is_err());
+    }
+
     #[test]
     fn merkle_two_leaves_deterministic() {
         let mut t1 = MerkleTree::new(MerkleTreeOptions {
             hash_alg: "sha256".to_string(),
             domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
             domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy: OddNodePolicy::Duplicate,
         });
         let mut t2 = t1.clone();
 
@@ -136,6 +450,7 @@ mod tests {
             hash_alg: "sha256".to_string(),
             domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
             domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy: OddNodePolicy::Duplicate,
         });
         t.push_leaf(b"a").unwrap();
         t.push_leaf(b"b").unwrap();
@@ -144,4 +459,109 @@ mod tests {
         let root = t.root_hex().unwrap();
         assert!(!root.is_empty());
     }
+
+    #[test]
+    fn streaming_builder_matches_tree() {
+        let opts = MerkleTreeOptions {
+            hash_alg: "sha256".to_string(),
+            domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
+            domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy: OddNodePolicy::Duplicate,
+        };
+        let mut tree = MerkleTree::new(opts.clone());
+        let mut streaming = StreamingMerkleBuilder::new(opts);
+
+        // Every leaf count up to 1024 covers all small shapes; beyond that we sample
+        // (recomputing the full tree at every step is quadratic).
+        for i in 1..=10_000u32 {
+            let payload = format!("leaf-{i}");
+            tree.push_leaf(payload.as_bytes()).unwrap();
+            streaming.push_leaf(payload.as_bytes()).unwrap();
+            if i <= 1024 || i % 97 == 0 || i == 10_000 {
+                assert_eq!(tree.root_hex().unwrap(), streaming.root_hex().unwrap(), "leaf count {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn promote_carries_lone_node_up() {
+        let opts = |odd_policy| MerkleTreeOptions {
+            hash_alg: "sha256".to_string(),
+            domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
+            domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy,
+        };
+        let mut dup = MerkleTree::new(opts(OddNodePolicy::Duplicate));
+        let mut pro = MerkleTree::new(opts(OddNodePolicy::Promote));
+        for leaf in [b"a", b"b", b"c", b"d"] {
+            dup.push_leaf(leaf).unwrap();
+            pro.push_leaf(leaf).unwrap();
+        }
+        // Power-of-two leaf counts have no odd level.
+        assert_eq!(dup.root_hex().unwrap(), pro.root_hex().unwrap());
+
+        dup.push_leaf(b"e").unwrap();
+        pro.push_leaf(b"e").unwrap();
+        assert_ne!(dup.root_hex().unwrap(), pro.root_hex().unwrap());
+
+        // [ab, cd, e] -> [abcd, e] -> hash(abcd || e)
+        let l = pro.leaf_hashes();
+        let ab = hash_merkle_node_hex("sha256", &l[0], &l[1]).unwrap();
+        let cd = hash_merkle_node_hex("sha256", &l[2], &l[3]).unwrap();
+        let abcd = hash_merkle_node_hex("sha256", &ab, &cd).unwrap();
+        assert_eq!(pro.root_hex().unwrap(), hash_merkle_node_hex("sha256", &abcd, &l[4]).unwrap());
+
+        // The lone leaf has a single step (its sibling abcd on the left).
+        let path = pro.inclusion_path(4).unwrap();
+        assert_eq!(path, vec![MerklePathStep { sibling_hex: abcd, sibling_is_left: true }]);
+        assert_eq!(dup.inclusion_path(4).unwrap().len(), 3);
+
+        for policy in [OddNodePolicy::Duplicate, OddNodePolicy::Promote] {
+            let mut tree = MerkleTree::new(opts(policy));
+            let mut streaming = StreamingMerkleBuilder::new(opts(policy));
+            for i in 1..=300u32 {
+                let payload = format!("leaf-{i}");
+                tree.push_leaf(payload.as_bytes()).unwrap();
+                streaming.push_leaf(payload.as_bytes()).unwrap();
+                assert_eq!(tree.root_hex().unwrap(), streaming.root_hex().unwrap(), "{policy:?} leaf count {i}");
+            }
+        }
+
+        assert_eq!(OddNodePolicy::parse("promote").unwrap(), OddNodePolicy::Promote);
+        assert_eq!(OddNodePolicy::parse(OddNodePolicy::default().as_str()).unwrap(), OddNodePolicy::Duplicate);
+        assert!(OddNodePolicy::parse("pad").is_err());
+    }
+
+    #[test]
+    fn streaming_builder_empty_is_error() {
+        let b = StreamingMerkleBuilder::new(MerkleTreeOptions {
+            hash_alg: "sha256".to_string(),
+            domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
+            domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy: OddNodePolicy::Duplicate,
+        });
+        assert!(b.root_hex().is_err());
+    }
+
+    #[test]
+    fn empty_root_is_well_defined() {
+        let opts = MerkleTreeOptions {
+            hash_alg: "sha256".to_string(),
+            domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
+            domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy: OddNodePolicy::Duplicate,
+        };
+
+        let empty = MerkleTree::new(opts.clone());
+        assert!(empty.root_hex().is_err());
+        let r1 = empty.root_hex_or_empty().unwrap();
+        let r2 = MerkleTree::new(opts.clone()).root_hex_or_empty().unwrap();
+        assert_eq!(r1, r2);
+        assert_eq!(r1, hash_merkle_empty_hex("sha256").unwrap());
+
+        // Must not collide with a tree holding a single empty leaf.
+        let mut one = MerkleTree::new(opts);
+        one.push_leaf(b"").unwrap();
+        assert_ne!(r1, one.root_hex_or_empty().unwrap());
+    }
 }
@@ -15,6 +15,12 @@
 //!
 //! Determinism is a core invariant of SIGNIA: any structure compiled twice
 //! with the same inputs MUST yield identical outputs.
+//!
+//! Rule: signia-core does not use `HashMap`/`HashSet` outside tests. Their
+//! iteration order is randomized per instance, so one stray loop over a hash
+//! map makes output order vary between runs. Use `BTreeMap`/`BTreeSet`, or
+//! sort explicitly (see `stable_sort`). The `no_hash_collections_outside_tests`
+//! test enforces this.
 
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -51,7 +57,8 @@ pub mod canonical {
     /// Rules:
     /// - Objects: keys sorted lexicographically
     /// - Arrays: order preserved
-    /// - Numbers: preserved as-is (caller must avoid floats if non-deterministic)
+    /// - Numbers: normalized by `canonical_json::canonicalize_number` (`-0.0` -> `0`,
+    ///   integer-valued floats -> integers)
     /// - Strings, bool, null: preserved
     pub fn canonicalize_json(value: &Value) -> SigniaResult<Value> {
         match value {
@@ -72,9 +79,24 @@ pub mod canonical {
                 }
                 Ok(Value::Array(out))
             }
+            Value::Number(n) => Ok(Value::Number(
+                crate::determinism::canonical_json::canonicalize_number(n),
+            )),
             _ => Ok(value.clone()),
         }
     }
+
+    /// Semantic JSON equality: true if both values canonicalize to the same value.
+    ///
+    /// Key order never matters, and numbers compare under the canonical number
+    /// policy: `1`, `1.0` and `1e0` are equal, as are `0` and `-0.0`. Fractional
+    /// floats and integers beyond 2^53 keep their representation.
+    pub fn semantic_equal(a: &Value, b: &Value) -> bool {
+        match (canonicalize_json(a), canonicalize_json(b)) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
 }
 
 /// Deterministic string helpers.
@@ -93,6 +115,24 @@ pub mod strings {
     }
 }
 
+/// Timestamp helpers.
+///
+/// SIGNIA never reads the system clock; timestamps are supplied by the caller
+/// and only validated here.
+pub mod time {
+    use super::*;
+
+    use ::time::format_description::well_known::Rfc3339;
+    use ::time::OffsetDateTime;
+
+    /// Ensure `s` is a well-formed RFC 3339 timestamp (e.g. `1970-01-01T00:00:00Z`).
+    pub fn validate_iso8601(s: &str) -> SigniaResult<()> {
+        OffsetDateTime::parse(s, &Rfc3339)
+            .map(|_| ())
+            .map_err(|e| SigniaError::invalid_argument(format!("invalid RFC 3339 timestamp {s:?}: {e}")))
+    }
+}
+
 /// Deterministic checks for structures.
 ///
 /// These helpers are intended to be used in validation paths.
@@ -117,6 +157,95 @@ pub mod checks {
 mod tests {
     use super::*;
 
+    /// Evaluate `$e` `$runs` times and assert every result equals the first.
+    macro_rules! assert_stable_across_runs {
+        ($runs:expr, $e:expr) => {{
+            let first = $e;
+            for run in 1..$runs {
+                assert_eq!($e, first, "run {run} differs from run 0");
+            }
+        }};
+    }
+
+    /// Grep-based lint for the module-level rule: no `HashMap`/`HashSet` in
+    /// non-test code anywhere under `src/`.
+    ///
+    /// Code after a file's first `#[cfg(test)]` and comments are ignored.
+    #[test]
+    fn no_hash_collections_outside_tests() {
+        fn visit(dir: &std::path::Path, files: &mut Vec<std::path::PathBuf>) {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    visit(&path, files);
+                } else if path.extension().is_some_and(|e| e == "rs") {
+                    files.push(path);
+                }
+            }
+        }
+
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut files = Vec::new();
+        visit(&src, &mut files);
+        files.sort();
+
+        let mut offenders = Vec::new();
+        for path in &files {
+            let text = std::fs::read_to_string(path).unwrap();
+            let non_test = text.split("#[cfg(test)]").next().unwrap_or("");
+            for (i, line) in non_test.lines().enumerate() {
+                let code = line.split("//").next().unwrap_or("");
+                if code.contains("HashMap") || code.contains("HashSet") {
+                    let rel = path.strip_prefix(&src).unwrap().display();
+                    offenders.push(format!("src/{rel}:{}: {}", i + 1, line.trim()));
+                }
+            }
+        }
+        assert!(
+            offenders.is_empty(),
+            "HashMap/HashSet in signia-core (use BTreeMap/BTreeSet):\n{}",
+            offenders.join("\n")
+        );
+    }
+
+    /// Feed the same content in hash-map order, which differs per map instance,
+    /// and check the canonical outputs never change.
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn outputs_are_stable_across_insertion_orders() {
+        use std::collections::HashMap;
+
+        use crate::model::ir::{IrEdge, IrGraph, IrNode};
+
+        fn shuffled() -> Vec<(String, i64)> {
+            let m: HashMap<String, i64> = (0..64).map(|i| (format!("k{i}"), i)).collect();
+            m.into_iter().collect()
+        }
+
+        assert_stable_across_runs!(100, {
+            let mut obj = Map::new();
+            for (k, v) in shuffled() {
+                obj.insert(k, Value::from(v));
+            }
+            let c = canonical::canonicalize_json(&Value::Object(obj)).unwrap();
+            serde_json::to_vec(&c).unwrap()
+        });
+
+        assert_stable_across_runs!(100, {
+            // `insert_*` keeps the key-derived ids of `IrNode::new`/`IrEdge::new`.
+            let mut g = IrGraph::new();
+            for (k, _) in shuffled() {
+                g.insert_node(IrNode::new("file", k.as_str())).unwrap();
+            }
+            for (k, v) in shuffled() {
+                let from = IrNode::make_key("file", &k);
+                let to = IrNode::make_key("file", &format!("k{}", (v + 1) % 64));
+                g.insert_edge(IrEdge::new(from, to, "next")).unwrap();
+            }
+            (g.canonical_bytes(), g.hash_hex().unwrap())
+        });
+    }
+
     #[test]
     #[cfg(feature = "canonical-json")]
     fn canonicalize_json_sorts_keys() {
@@ -130,6 +259,51 @@ mod tests {
         assert_eq!(keys, vec!["a".to_string(), "b".to_string()]);
     }
 
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn semantic_equal_ignores_key_order_and_number_form() {
+        use serde_json::json;
+
+        let a: Value = serde_json::from_str(r#"{"b":[1,{"y":2,"x":-0.0}],"a":1.0}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"a":1,"b":[1e0,{"x":0,"y":2.0}]}"#).unwrap();
+        assert!(canonical::semantic_equal(&a, &b));
+
+        assert!(canonical::semantic_equal(&json!(0.5), &json!(5e-1)));
+        assert!(!canonical::semantic_equal(&json!(1), &json!(1.5)));
+        assert!(!canonical::semantic_equal(&json!(1), &json!("1")));
+        // Array order is significant.
+        assert!(!canonical::semantic_equal(&json!([1, 2]), &json!([2, 1])));
+    }
+
+    #[test]
+    fn validate_iso8601_accepts_rfc3339() {
+        for s in [
+            "1970-01-01T00:00:00Z",
+            "2024-02-29T23:59:59Z",
+            "2024-06-01T12:30:00.123456Z",
+            "2024-06-01T12:30:00+02:00",
+            "2024-06-01T12:30:00-07:30",
+        ] {
+            time::validate_iso8601(s).unwrap_or_else(|e| panic!("{s}: {e}"));
+        }
+    }
+
+    #[test]
+    fn validate_iso8601_rejects_malformed() {
+        for s in [
+            "",
+            "not-a-date",
+            "1970-01-01",
+            "1970-01-01T00:00:00",
+            "2023-02-29T00:00:00Z",
+            "2024-13-01T00:00:00Z",
+            "2024-01-01T24:00:00Z",
+            " 1970-01-01T00:00:00Z",
+        ] {
+            assert!(time::validate_iso8601(s).is_err(), "{s:?}");
+        }
+    }
+
     #[test]
     fn normalize_strings() {
         let s = "a\r\nb\r\n";
@@ -148,6 +148,7 @@ pub fn to_pipeline_diagnostic(d: &Diagnostic) -> crate::pipeline::context::Pipel
         level,
         code: d.code.clone(),
         message: d.message.clone(),
+        data: d.fields.clone(),
     }
 }
 
@@ -161,7 +162,12 @@ pub fn from_pipeline_diagnostic(
         crate::pipeline::context::DiagnosticLevel::Error => DiagLevel::Error,
     };
 
-    Diagnostic::new(level, d.code.clone(), d.message.clone())
+    Diagnostic {
+        level,
+        code: d.code.clone(),
+        message: d.message.clone(),
+        fields: d.data.clone(),
+    }
 }
 
 /// Utility: fail if diagnostics has errors.
@@ -187,11 +193,12 @@ mod tests {
 
     #[test]
     fn conversion_roundtrip() {
-        let d = Diagnostic::new(DiagLevel::Error, "code", "msg");
+        let d = Diagnostic::new(DiagLevel::Error, "code", "msg").with_field("path", "a.rs");
         let p = to_pipeline_diagnostic(&d);
         let back = from_pipeline_diagnostic(&p);
         assert_eq!(back.level, DiagLevel::Error);
         assert_eq!(back.code, "code");
         assert_eq!(back.message, "msg");
+        assert_eq!(back.fields, d.fields);
     }
 }
@@ -3,48 +3,73 @@
 //! Errors are structured, explicit, and stable. Messages are intended to be
 //! human-readable while preserving machine-level categorization.
 
-use std::fmt::{self, Display};
-
 /// Result type used throughout signia-core.
 pub type SigniaResult<T> = Result<T, SigniaError>;
 
 /// Top-level error type for signia-core.
-#[derive(Debug)]
+///
+/// Consumers can `match` on variants; each variant has a thin constructor below.
+#[derive(Debug, thiserror::Error)]
 pub enum SigniaError {
     /// Invalid or unsupported argument.
+    #[error("invalid argument: {message}")]
     InvalidArgument {
         message: String,
     },
 
     /// Canonicalization failure.
+    #[error("canonicalization error: {message}")]
     Canonicalization {
         message: String,
     },
 
     /// Hashing failure.
+    #[error("hashing error: {message}")]
     Hashing {
         message: String,
     },
 
     /// Merkle tree construction or verification failure.
+    #[error("merkle error: {message}")]
     Merkle {
         message: String,
     },
 
     /// Path normalization or validation failure.
+    #[error("path error: {message}")]
     Path {
         message: String,
     },
 
     /// Serialization or deserialization failure.
+    #[error("serialization error: {message}")]
     Serialization {
         message: String,
     },
 
     /// Internal invariant violation.
+    #[error("invariant violation: {message}")]
     Invariant {
         message: String,
     },
+
+    /// An operation exceeded its time budget.
+    #[error("timeout: {message}")]
+    Timeout {
+        message: String,
+    },
+
+    /// A referenced item does not exist.
+    #[error("not found: {message}")]
+    NotFound {
+        message: String,
+    },
+
+    /// A configured limit (bytes, files, nodes, ...) was exceeded.
+    #[error("limit exceeded: {message}")]
+    LimitExceeded {
+        message: String,
+    },
 }
 
 impl SigniaError {
@@ -96,37 +121,60 @@ impl SigniaError {
             message: message.into(),
         }
     }
-}
 
-impl Display for SigniaError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// Construct a timeout error.
+    pub fn timeout<M: Into<String>>(message: M) -> Self {
+        Self::Timeout {
+            message: message.into(),
+        }
+    }
+
+    /// Construct a not-found error.
+    pub fn not_found<M: Into<String>>(message: M) -> Self {
+        Self::NotFound {
+            message: message.into(),
+        }
+    }
+
+    /// Construct a limit-exceeded error.
+    pub fn limit_exceeded<M: Into<String>>(message: M) -> Self {
+        Self::LimitExceeded {
+            message: message.into(),
+        }
+    }
+
+    /// Stable machine-readable error code.
+    pub fn code(&self) -> &'static str {
         match self {
-            Self::InvalidArgument { message } => {
-                write!(f, "invalid argument: {message}")
-            }
-            Self::Canonicalization { message } => {
-                write!(f, "canonicalization error: {message}")
-            }
-            Self::Hashing { message } => {
-                write!(f, "hashing error: {message}")
-            }
-            Self::Merkle { message } => {
-                write!(f, "merkle error: {message}")
-            }
-            Self::Path { message } => {
-                write!(f, "path error: {message}")
-            }
-            Self::Serialization { message } => {
-                write!(f, "serialization error: {message}")
-            }
-            Self::Invariant { message } => {
-                write!(f, "invariant violation: {message}")
-            }
+            Self::InvalidArgument { .. } => "invalid_argument",
+            Self::Canonicalization { .. } => "canonicalization",
+            Self::Hashing { .. } => "hashing",
+            Self::Merkle { .. } => "merkle",
+            Self::Path { .. } => "path",
+            Self::Serialization { .. } => "serialization",
+            Self::Invariant { .. } => "invariant",
+            Self::Timeout { .. } => "timeout",
+            Self::NotFound { .. } => "not_found",
+            Self::LimitExceeded { .. } => "limit_exceeded",
         }
     }
 }
 
-impl std::error::Error for SigniaError {}
+#[cfg(feature = "canonical-json")]
+impl From<serde_json::Error> for SigniaError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::serialization(e.to_string())
+    }
+}
+
+/// Standard error for functionality compiled out by a disabled `canonical-json` feature.
+///
+/// Use this instead of ad-hoc messages so the wording stays stable across call sites.
+pub fn canonical_json_required(component: &str) -> SigniaError {
+    SigniaError::invalid_argument(format!(
+        "canonical-json feature is required for {component}"
+    ))
+}
 
 #[cfg(test)]
 mod tests {
@@ -149,4 +197,41 @@ mod tests {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<SigniaError>();
     }
+
+    #[test]
+    fn canonical_json_required_message_is_stable() {
+        let e = canonical_json_required("EmitSchemaV1Stage");
+        assert_eq!(
+            format!("{e}"),
+            "invalid argument: canonical-json feature is required for EmitSchemaV1Stage"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn serde_json_error_converts_to_serialization() {
+        fn parse(s: &str) -> SigniaResult<serde_json::Value> {
+            Ok(serde_json::from_str(s)?)
+        }
+        let e = parse("{").unwrap_err();
+        assert_eq!(e.code(), "serialization");
+        assert!(format!("{e}").starts_with("serialization error: "));
+    }
+
+    #[test]
+    fn constructors_yield_variants() {
+        assert!(matches!(SigniaError::invalid_argument("m"), SigniaError::InvalidArgument { message } if message == "m"));
+        assert!(matches!(SigniaError::serialization("m"), SigniaError::Serialization { message } if message == "m"));
+        assert!(matches!(SigniaError::invariant("m"), SigniaError::Invariant { message } if message == "m"));
+        assert!(matches!(SigniaError::timeout("m"), SigniaError::Timeout { message } if message == "m"));
+        assert!(matches!(SigniaError::not_found("m"), SigniaError::NotFound { message } if message == "m"));
+        assert!(matches!(SigniaError::limit_exceeded("m"), SigniaError::LimitExceeded { message } if message == "m"));
+    }
+
+    #[test]
+    fn display_new_variants() {
+        assert_eq!(format!("{}", SigniaError::timeout("5s")), "timeout: 5s");
+        assert_eq!(format!("{}", SigniaError::not_found("obj")), "not found: obj");
+        assert_eq!(format!("{}", SigniaError::limit_exceeded("max_files")), "limit exceeded: max_files");
+    }
 }
@@ -12,12 +12,14 @@ pub mod errors;
 pub mod hash;
 pub mod merkle;
 pub mod model;
+pub mod proof;
 // pub mod path;
 
 pub use crate::errors::{SigniaError, SigniaResult};
 
 /// Common version strings.
 pub const SCHEMA_VERSION_V1: &str = "v1";
+pub const SCHEMA_VERSION_V2: &str = "v2";
 
 /// Default domain separation labels.
 /// These must remain stable across versions.
@@ -27,6 +29,8 @@ pub mod domain {
     pub const PROOF: &str = "signia.v1.proof";
     pub const MERKLE_LEAF: &str = "signia.v1.merkle.leaf";
     pub const MERKLE_NODE: &str = "signia.v1.merkle.node";
+    pub const MERKLE_EMPTY: &str = "signia.v1.merkle.empty";
+    pub const IR: &str = "signia.v1.ir";
 }
 
 /// Default canonicalization settings.
@@ -39,6 +43,14 @@ pub mod defaults {
     pub const PATH_ROOT: &str = "artifact:/";
 }
 
+/// Cargo features this build was compiled with.
+pub mod features {
+    pub const CANONICAL_JSON: bool = cfg!(feature = "canonical-json");
+    pub const SHA256: bool = cfg!(feature = "sha256");
+    pub const BLAKE3: bool = cfg!(feature = "blake3");
+    pub const PARALLEL: bool = cfg!(feature = "parallel");
+}
+
 /// Convenience re-exports.
 pub mod prelude {
     pub use crate::canonical::{canonical_json_bytes, canonical_json_value, CanonicalJsonOptions};
@@ -0,0 +1,113 @@
+//! SIGNIA bundle descriptor (v1).
+//!
+//! A bundle is a schema, a manifest and a proof emitted together. `BundleInfoV1`
+//! is the single entry point describing one: the object ids of the three
+//! artifacts, the proof's canonical hash and the digests of the bundle files.
+//!
+//! It is written as `bundle.json` next to `schema.json`, `manifest.json` and
+//! `proof.json`. It is derived entirely from those artifacts, so the same bundle
+//! always yields the same descriptor.
+//!
+//! This is a wire-level model. Do not introduce breaking changes for v1.
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "canonical-json")]
+use serde::{Deserialize, Serialize};
+
+/// Descriptor listing every artifact id and file digest of one bundle.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleInfoV1 {
+    /// Bundle descriptor version. Must be "v1".
+    pub version: String,
+
+    /// Object id of the schema artifact.
+    pub schema_id: String,
+
+    /// Object id of the manifest artifact.
+    pub manifest_id: String,
+
+    /// Object id of the proof artifact.
+    pub proof_id: String,
+
+    /// Domain-separated canonical proof hash (`hash_proof_v1_hex`), lowercase
+    /// hex. Not an object id, so it carries no algorithm prefix. Absent in
+    /// descriptors written before it was recorded.
+    #[cfg_attr(feature = "canonical-json", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub proof_hash: Option<String>,
+
+    /// Lowercase sha256 hex of each bundle file, keyed by file name
+    /// (e.g. `"schema.json"`).
+    pub hashes: BTreeMap<String, String>,
+}
+
+impl BundleInfoV1 {
+    /// Assemble the descriptor for a bundle.
+    pub fn from_bundle(
+        schema_id: impl Into<String>,
+        manifest_id: impl Into<String>,
+        proof_id: impl Into<String>,
+        hashes: BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            version: "v1".to_string(),
+            schema_id: schema_id.into(),
+            manifest_id: manifest_id.into(),
+            proof_id: proof_id.into(),
+            proof_hash: None,
+            hashes,
+        }
+    }
+
+    /// Record the proof's canonical hash (`hash_proof_v1_hex`).
+    pub fn with_proof_hash(mut self, proof_hash: impl Into<String>) -> Self {
+        self.proof_hash = Some(proof_hash.into());
+        self
+    }
+
+    /// The three artifact ids in bundle order: schema, manifest, proof.
+    pub fn artifact_ids(&self) -> [&str; 3] {
+        [&self.schema_id, &self.manifest_id, &self.proof_id]
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "canonical-json")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_info_references_artifact_ids() {
+        let mut hashes = BTreeMap::new();
+        hashes.insert("schema.json".to_string(), "a".repeat(64));
+        hashes.insert("proof.json".to_string(), "c".repeat(64));
+        hashes.insert("manifest.json".to_string(), "b".repeat(64));
+
+        let info = BundleInfoV1::from_bundle("sch", "man", "prf", hashes);
+        assert_eq!(info.artifact_ids(), ["sch", "man", "prf"]);
+
+        let v = serde_json::to_value(&info).unwrap();
+        assert_eq!(v["version"], "v1");
+        assert_eq!(v["schemaId"], "sch");
+        assert_eq!(v["manifestId"], "man");
+        assert_eq!(v["proofId"], "prf");
+        assert_eq!(v["hashes"]["manifest.json"], "b".repeat(64));
+
+        // File digests serialize in name order regardless of insertion order.
+        let s = serde_json::to_string(&info).unwrap();
+        let pos = |k: &str| s.find(k).unwrap();
+        assert!(pos("manifest.json") < pos("proof.json") && pos("proof.json") < pos("schema.json"));
+
+        let back: BundleInfoV1 = serde_json::from_str(&s).unwrap();
+        assert_eq!(back, info);
+
+        // The proof hash is optional on the wire.
+        assert!(v.get("proofHash").is_none());
+        let info = info.with_proof_hash("d".repeat(64));
+        let v = serde_json::to_value(&info).unwrap();
+        assert_eq!(v["proofHash"], "d".repeat(64));
+        assert_eq!(serde_json::from_value::<BundleInfoV1>(v).unwrap(), info);
+    }
+}
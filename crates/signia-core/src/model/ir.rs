@@ -27,6 +27,16 @@ use std::collections::{BTreeMap, BTreeSet};
 use crate::errors::{SigniaError, SigniaResult};
 use crate::model::v1::{EdgeV1, EntityV1, SchemaV1};
 
+#[cfg(feature = "canonical-json")]
+use serde::Serialize;
+
+/// Version tag written as `irVersion` by every serialized form of `IrGraph`.
+///
+/// Bump it whenever the JSON shape changes; `IrGraph::from_canonical_json`
+/// rejects any other value, so stored IR (`--emit-ir` output, caches) never
+/// loads silently in a drifted format.
+pub const IR_VERSION: &str = "ir.v1";
+
 /// Canonical string identifier for IR nodes and edges.
 ///
 /// In IR, ids may be temporary. The compiler will assign final stable ids during
@@ -36,11 +46,15 @@ pub type IrId = String;
 /// A stable-ish key used for ordering and deduplicating IR nodes.
 ///
 /// Plugins should aim to produce stable `key`s because they map cleanly to final ids.
+/// Build them with `IrNode::make_key` / `IrEdge::make_key` rather than by hand.
 pub type IrKey = String;
 
 /// A single IR attribute value.
 ///
-/// We intentionally keep this compatible with JSON types.
+/// We intentionally keep this compatible with JSON types (and serialize as the
+/// plain JSON value).
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(untagged))]
 #[derive(Debug, Clone, PartialEq)]
 pub enum IrValue {
     Null,
@@ -77,11 +91,33 @@ impl IrValue {
     }
 }
 
+#[cfg(feature = "canonical-json")]
+impl IrValue {
+    /// Inverse of `to_json`: integers that fit `i64` become `I64`, other numbers `F64`.
+    pub fn from_json(v: &serde_json::Value) -> Self {
+        match v {
+            serde_json::Value::Null => IrValue::Null,
+            serde_json::Value::Bool(b) => IrValue::Bool(*b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => IrValue::I64(i),
+                None => IrValue::F64(n.as_f64().unwrap_or(0.0)),
+            },
+            serde_json::Value::String(s) => IrValue::String(s.clone()),
+            serde_json::Value::Array(a) => IrValue::Array(a.iter().map(IrValue::from_json).collect()),
+            serde_json::Value::Object(o) => {
+                IrValue::Object(o.iter().map(|(k, v)| (k.clone(), IrValue::from_json(v))).collect())
+            }
+        }
+    }
+}
+
 /// Provenance information for an IR node/edge.
 ///
 /// This helps explain "where did this come from" in Console UX.
 /// Provenance fields are optional and must never affect canonical hashing unless
 /// explicitly included by the compiler.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct Provenance {
     pub source: ProvenanceSource,
@@ -90,6 +126,8 @@ pub struct Provenance {
 }
 
 /// The primary source category of a compiler output item.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub enum ProvenanceSource {
     FilePath(String),
@@ -99,6 +137,8 @@ pub enum ProvenanceSource {
 }
 
 /// A loose source span for UX (not for hashing).
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct SourceSpan {
     pub start_line: u32,
@@ -108,6 +148,8 @@ pub struct SourceSpan {
 }
 
 /// IR node representation.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct IrNode {
     /// Temporary id used within IR graph.
@@ -136,6 +178,8 @@ pub struct IrNode {
 }
 
 /// IR edge representation.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct IrEdge {
     pub id: IrId,
@@ -148,7 +192,75 @@ pub struct IrEdge {
     pub diagnostics: Vec<Diagnostic>,
 }
 
+impl IrNode {
+    /// Deterministic node key: `<node_type>:<name>` with separators escaped.
+    ///
+    /// `\` and `:` inside each component are escaped as `\\` and `\:`, so distinct
+    /// `(node_type, name)` pairs never produce the same key.
+    pub fn make_key(node_type: &str, name: &str) -> IrKey {
+        join_key(&[node_type, name])
+    }
+
+    /// Node with no attributes whose id and key are both `make_key(node_type, name)`.
+    pub fn new(node_type: impl Into<String>, name: impl Into<String>) -> Self {
+        let (node_type, name) = (node_type.into(), name.into());
+        let key = Self::make_key(&node_type, &name);
+        Self {
+            id: key.clone(),
+            key,
+            node_type,
+            name,
+            attrs: BTreeMap::new(),
+            digests: Vec::new(),
+            provenance: None,
+            diagnostics: Vec::new(),
+        }
+    }
+}
+
+impl IrEdge {
+    /// Deterministic edge key: `<edge_type>:<from>:<to>` with separators escaped
+    /// as in `IrNode::make_key`.
+    pub fn make_key(edge_type: &str, from: &str, to: &str) -> IrKey {
+        join_key(&[edge_type, from, to])
+    }
+
+    /// Edge with no attributes whose id and key are both `make_key(edge_type, from, to)`.
+    pub fn new(from: impl Into<IrId>, to: impl Into<IrId>, edge_type: impl Into<String>) -> Self {
+        let (from, to, edge_type) = (from.into(), to.into(), edge_type.into());
+        let key = Self::make_key(&edge_type, &from, &to);
+        Self {
+            id: key.clone(),
+            key,
+            edge_type,
+            from,
+            to,
+            attrs: BTreeMap::new(),
+            provenance: None,
+            diagnostics: Vec::new(),
+        }
+    }
+}
+
+/// Join key components with `:`, escaping `\` and `:` inside each component.
+fn join_key(parts: &[&str]) -> IrKey {
+    let mut out = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            out.push(':');
+        }
+        for c in part.chars() {
+            if c == '\\' || c == ':' {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+    }
+    out
+}
+
 /// A digest attached to an IR node.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
 #[derive(Debug, Clone)]
 pub struct IrDigest {
     pub alg: String, // "sha256" | "blake3"
@@ -156,6 +268,7 @@ pub struct IrDigest {
 }
 
 /// Compiler diagnostic for UX and debugging.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
 #[derive(Debug, Clone)]
 pub struct Diagnostic {
     pub level: DiagnosticLevel,
@@ -164,6 +277,8 @@ pub struct Diagnostic {
     pub details: BTreeMap<String, IrValue>,
 }
 
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "lowercase"))]
 #[derive(Debug, Clone, Copy)]
 pub enum DiagnosticLevel {
     Info,
@@ -172,10 +287,32 @@ pub enum DiagnosticLevel {
 }
 
 /// IR graph container.
+///
+/// Serializes as `{irVersion, nodes, edges}` (id-keyed maps); the key indexes
+/// are derived state and are skipped.
 #[derive(Debug, Clone)]
 pub struct IrGraph {
     pub nodes: BTreeMap<IrId, IrNode>,
     pub edges: BTreeMap<IrId, IrEdge>,
+    /// Key -> id of everything inserted through `insert_*`/`add_*`.
+    ///
+    /// Direct edits of `nodes`/`edges` bypass this index; `validate_basic` still
+    /// rejects duplicate keys in that case.
+    node_keys: BTreeMap<IrKey, IrId>,
+    edge_keys: BTreeMap<IrKey, IrId>,
+}
+
+#[cfg(feature = "canonical-json")]
+impl Serialize for IrGraph {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut st = serializer.serialize_struct("IrGraph", 3)?;
+        st.serialize_field("irVersion", IR_VERSION)?;
+        st.serialize_field("nodes", &self.nodes)?;
+        st.serialize_field("edges", &self.edges)?;
+        st.end()
+    }
 }
 
 impl IrGraph {
@@ -184,10 +321,12 @@ impl IrGraph {
         Self {
             nodes: BTreeMap::new(),
             edges: BTreeMap::new(),
+            node_keys: BTreeMap::new(),
+            edge_keys: BTreeMap::new(),
         }
     }
 
-    /// Insert a node. Fails if id already exists.
+    /// Insert a node. Fails if its id or its key already exists.
     pub fn insert_node(&mut self, node: IrNode) -> SigniaResult<()> {
         if self.nodes.contains_key(&node.id) {
             return Err(SigniaError::invalid_argument(format!(
@@ -195,11 +334,18 @@ impl IrGraph {
                 node.id
             )));
         }
+        if let Some(existing) = self.node_keys.get(&node.key) {
+            return Err(SigniaError::invalid_argument(format!(
+                "duplicate IR node key: {} (node {} collides with node {existing})",
+                node.key, node.id
+            )));
+        }
+        self.node_keys.insert(node.key.clone(), node.id.clone());
         self.nodes.insert(node.id.clone(), node);
         Ok(())
     }
 
-    /// Insert an edge. Fails if id already exists.
+    /// Insert an edge. Fails if its id or its key already exists.
     pub fn insert_edge(&mut self, edge: IrEdge) -> SigniaResult<()> {
         if self.edges.contains_key(&edge.id) {
             return Err(SigniaError::invalid_argument(format!(
@@ -207,10 +353,35 @@ impl IrGraph {
                 edge.id
             )));
         }
+        if let Some(existing) = self.edge_keys.get(&edge.key) {
+            return Err(SigniaError::invalid_argument(format!(
+                "duplicate IR edge key: {} (edge {} collides with edge {existing})",
+                edge.key, edge.id
+            )));
+        }
+        self.edge_keys.insert(edge.key.clone(), edge.id.clone());
         self.edges.insert(edge.id.clone(), edge);
         Ok(())
     }
 
+    /// Insert a node under a fresh id (`n<N>`, first unused N >= node count) and
+    /// return that id. Any id already set on `node` is replaced; the key is still
+    /// checked for duplicates.
+    pub fn add_node(&mut self, mut node: IrNode) -> SigniaResult<IrId> {
+        node.id = fresh_id("n", &self.nodes);
+        let id = node.id.clone();
+        self.insert_node(node)?;
+        Ok(id)
+    }
+
+    /// Insert an edge under a fresh id (`e<N>`), like `add_node`.
+    pub fn add_edge(&mut self, mut edge: IrEdge) -> SigniaResult<IrId> {
+        edge.id = fresh_id("e", &self.edges);
+        let id = edge.id.clone();
+        self.insert_edge(edge)?;
+        Ok(id)
+    }
+
     /// Basic validation:
     /// - all edge endpoints exist
     /// - stable keys are unique (best-effort)
@@ -358,6 +529,280 @@ impl IrGraph {
     }
 }
 
+/// Canonical content encoding.
+///
+/// Two graphs are considered equal when they contain the same nodes and edges by
+/// stable key, regardless of insertion order or temporary IR ids:
+/// - nodes: key, type, name, attrs, digests
+/// - edges: key, type, endpoint node keys, attrs
+///
+/// Provenance and diagnostics are UX-only and never participate.
+impl IrGraph {
+    /// Deterministic, length-prefixed byte encoding of the graph content.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        let nodes = self.ordered_nodes();
+        write_len(&mut buf, nodes.len());
+        for n in nodes {
+            write_str(&mut buf, &n.key);
+            write_str(&mut buf, &n.node_type);
+            write_str(&mut buf, &n.name);
+            write_attrs(&mut buf, &n.attrs);
+
+            let mut digests: Vec<(&str, &str)> =
+                n.digests.iter().map(|d| (d.alg.as_str(), d.hex.as_str())).collect();
+            digests.sort();
+            write_len(&mut buf, digests.len());
+            for (alg, hex) in digests {
+                write_str(&mut buf, alg);
+                write_str(&mut buf, hex);
+            }
+        }
+
+        let edges = self.ordered_edges();
+        write_len(&mut buf, edges.len());
+        for e in edges {
+            write_str(&mut buf, &e.key);
+            write_str(&mut buf, &e.edge_type);
+            write_str(&mut buf, self.endpoint_key(&e.from));
+            write_str(&mut buf, self.endpoint_key(&e.to));
+            write_attrs(&mut buf, &e.attrs);
+        }
+
+        buf
+    }
+
+    /// Domain-separated SHA-256 of `canonical_bytes` as lowercase hex.
+    ///
+    /// Suitable as a persistent cache key. `PartialEq` and `Hash` are consistent with it.
+    pub fn hash_hex(&self) -> SigniaResult<String> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(crate::domain::IR.as_bytes());
+        buf.extend_from_slice(&self.canonical_bytes());
+        crate::hash::hash_bytes_hex(&buf)
+    }
+
+    /// Resolve an edge endpoint id to its node key (falls back to the raw id).
+    fn endpoint_key<'a>(&'a self, id: &'a str) -> &'a str {
+        self.nodes.get(id).map(|n| n.key.as_str()).unwrap_or(id)
+    }
+}
+
+impl PartialEq for IrGraph {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical_bytes() == other.canonical_bytes()
+    }
+}
+
+impl Eq for IrGraph {}
+
+impl std::hash::Hash for IrGraph {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.canonical_bytes().hash(state);
+    }
+}
+
+/// JSON form of the canonical content, for debugging (`signia compile --emit-ir`).
+///
+/// Carries what `canonical_bytes` encodes, in the same order, plus an `irVersion`
+/// tag that is not part of the hashed bytes:
+/// `{"irVersion":"ir.v1","nodes":[{key,type,name,attrs,digests}],"edges":[{key,type,from,to,attrs}]}`
+/// with edge endpoints given as node keys. Numbers are written as-is rather than
+/// canonicalized, so `F64(1.0)` stays distinct from `I64(1)`; non-finite floats
+/// are written as `0` (see `IrValue::to_json`) and do not round-trip.
+#[cfg(feature = "canonical-json")]
+impl IrGraph {
+    /// Serialize the canonical content. `from_canonical_json` of the result is `==` to `self`.
+    pub fn to_canonical_json(&self) -> serde_json::Value {
+        fn attrs_json(attrs: &BTreeMap<String, IrValue>) -> serde_json::Value {
+            serde_json::Value::Object(attrs.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+        }
+
+        let nodes: Vec<serde_json::Value> = self
+            .ordered_nodes()
+            .into_iter()
+            .map(|n| {
+                let mut digests: Vec<(&str, &str)> =
+                    n.digests.iter().map(|d| (d.alg.as_str(), d.hex.as_str())).collect();
+                digests.sort();
+                serde_json::json!({
+                    "key": n.key,
+                    "type": n.node_type,
+                    "name": n.name,
+                    "attrs": attrs_json(&n.attrs),
+                    "digests": digests
+                        .into_iter()
+                        .map(|(alg, hex)| serde_json::json!({"alg": alg, "hex": hex}))
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+
+        let edges: Vec<serde_json::Value> = self
+            .ordered_edges()
+            .into_iter()
+            .map(|e| {
+                serde_json::json!({
+                    "key": e.key,
+                    "type": e.edge_type,
+                    "from": self.endpoint_key(&e.from),
+                    "to": self.endpoint_key(&e.to),
+                    "attrs": attrs_json(&e.attrs),
+                })
+            })
+            .collect();
+
+        serde_json::json!({ "irVersion": IR_VERSION, "nodes": nodes, "edges": edges })
+    }
+
+    /// Rebuild a graph from `to_canonical_json` output.
+    ///
+    /// Node ids are their keys and edge ids are their keys; provenance and
+    /// diagnostics are empty. Fails unless `irVersion` is `IR_VERSION`.
+    pub fn from_canonical_json(v: &serde_json::Value) -> SigniaResult<Self> {
+        fn str_field<'a>(v: &'a serde_json::Value, field: &str) -> SigniaResult<&'a str> {
+            v.get(field)
+                .and_then(|x| x.as_str())
+                .ok_or_else(|| SigniaError::serialization(format!("IR JSON: missing string field {field}")))
+        }
+        fn attrs_field(v: &serde_json::Value) -> SigniaResult<BTreeMap<String, IrValue>> {
+            match v.get("attrs") {
+                None => Ok(BTreeMap::new()),
+                Some(serde_json::Value::Object(o)) => {
+                    Ok(o.iter().map(|(k, v)| (k.clone(), IrValue::from_json(v))).collect())
+                }
+                Some(_) => Err(SigniaError::serialization("IR JSON: attrs must be an object")),
+            }
+        }
+        fn array_field<'a>(v: &'a serde_json::Value, field: &str) -> SigniaResult<&'a Vec<serde_json::Value>> {
+            v.get(field)
+                .and_then(|x| x.as_array())
+                .ok_or_else(|| SigniaError::serialization(format!("IR JSON: missing array field {field}")))
+        }
+
+        match str_field(v, "irVersion")? {
+            IR_VERSION => {}
+            other => {
+                return Err(SigniaError::serialization(format!(
+                    "IR JSON: unsupported irVersion {other} (expected {IR_VERSION})"
+                )))
+            }
+        }
+
+        let mut g = IrGraph::new();
+        for n in array_field(v, "nodes")? {
+            let key = str_field(n, "key")?.to_string();
+            let digests = match n.get("digests") {
+                None => Vec::new(),
+                Some(_) => array_field(n, "digests")?
+                    .iter()
+                    .map(|d| {
+                        Ok(IrDigest {
+                            alg: str_field(d, "alg")?.to_string(),
+                            hex: str_field(d, "hex")?.to_string(),
+                        })
+                    })
+                    .collect::<SigniaResult<Vec<_>>>()?,
+            };
+            g.insert_node(IrNode {
+                id: key.clone(),
+                key,
+                node_type: str_field(n, "type")?.to_string(),
+                name: str_field(n, "name")?.to_string(),
+                attrs: attrs_field(n)?,
+                digests,
+                provenance: None,
+                diagnostics: Vec::new(),
+            })?;
+        }
+        for e in array_field(v, "edges")? {
+            let key = str_field(e, "key")?.to_string();
+            g.insert_edge(IrEdge {
+                id: key.clone(),
+                key,
+                edge_type: str_field(e, "type")?.to_string(),
+                from: str_field(e, "from")?.to_string(),
+                to: str_field(e, "to")?.to_string(),
+                attrs: attrs_field(e)?,
+                provenance: None,
+                diagnostics: Vec::new(),
+            })?;
+        }
+        Ok(g)
+    }
+}
+
+/// First `<prefix><N>` not present in `map`, starting at N = `map.len()`.
+fn fresh_id<V>(prefix: &str, map: &BTreeMap<IrId, V>) -> IrId {
+    let mut n = map.len();
+    loop {
+        let id = format!("{prefix}{n}");
+        if !map.contains_key(&id) {
+            return id;
+        }
+        n += 1;
+    }
+}
+
+fn write_len(buf: &mut Vec<u8>, n: usize) {
+    buf.extend_from_slice(&(n as u64).to_le_bytes());
+}
+
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+    write_len(buf, s.len());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn write_attrs(buf: &mut Vec<u8>, attrs: &BTreeMap<String, IrValue>) {
+    write_len(buf, attrs.len());
+    for (k, v) in attrs {
+        write_str(buf, k);
+        write_value(buf, v);
+    }
+}
+
+fn write_value(buf: &mut Vec<u8>, v: &IrValue) {
+    match v {
+        IrValue::Null => buf.push(0),
+        IrValue::Bool(b) => {
+            buf.push(1);
+            buf.push(*b as u8);
+        }
+        IrValue::I64(n) => {
+            buf.push(2);
+            buf.extend_from_slice(&n.to_le_bytes());
+        }
+        IrValue::F64(x) => {
+            // Collapse -0.0 and NaN payloads so equal-looking values encode identically.
+            let x = if *x == 0.0 {
+                0.0
+            } else if x.is_nan() {
+                f64::NAN
+            } else {
+                *x
+            };
+            buf.push(3);
+            buf.extend_from_slice(&x.to_bits().to_le_bytes());
+        }
+        IrValue::String(s) => {
+            buf.push(4);
+            write_str(buf, s);
+        }
+        IrValue::Array(a) => {
+            buf.push(5);
+            write_len(buf, a.len());
+            for x in a {
+                write_value(buf, x);
+            }
+        }
+        IrValue::Object(o) => {
+            buf.push(6);
+            write_attrs(buf, o);
+        }
+    }
+}
+
 /// Strategy for assigning stable final ids.
 ///
 /// The simplest strategy:
@@ -463,6 +908,83 @@ mod tests {
         g.validate_basic().unwrap();
     }
 
+    #[test]
+    fn make_key_escapes_separators() {
+        assert_eq!(IrNode::make_key("file", "README.md"), "file:README.md");
+        assert_eq!(IrNode::make_key("file", "a:b"), "file:a\\:b");
+        assert_eq!(IrNode::make_key("file", "a\\"), "file:a\\\\");
+        assert_eq!(IrEdge::make_key("contains", "repo:x", "file:y"), "contains:repo\\:x:file\\:y");
+
+        // Splitting the same characters differently never collides.
+        assert_ne!(IrNode::make_key("file", "a:b"), IrNode::make_key("file:a", "b"));
+        assert_ne!(IrNode::make_key("a\\", "b"), IrNode::make_key("a", "\\b"));
+        assert_ne!(IrNode::make_key("a\\", ":b"), IrNode::make_key("a\\:", "b"));
+        assert_ne!(
+            IrEdge::make_key("contains", "a:b", "c"),
+            IrEdge::make_key("contains", "a", "b:c")
+        );
+        assert_ne!(
+            IrEdge::make_key("contains:a", "b", "c"),
+            IrEdge::make_key("contains", "a:b", "c")
+        );
+    }
+
+    #[test]
+    fn constructors_use_make_key() {
+        let root = IrNode::new("repo", "demo");
+        let file = IrNode::new("file", "src:lib.rs");
+        assert_eq!(root.key, IrNode::make_key("repo", "demo"));
+        assert_eq!(root.id, root.key);
+
+        let e = IrEdge::new(root.id.clone(), file.id.clone(), "contains");
+        assert_eq!(e.key, IrEdge::make_key("contains", &root.id, &file.id));
+        assert_eq!((e.from.as_str(), e.to.as_str()), (root.id.as_str(), file.id.as_str()));
+
+        let mut g = IrGraph::new();
+        g.insert_node(root).unwrap();
+        g.insert_node(file).unwrap();
+        g.insert_edge(e).unwrap();
+        g.validate_basic().unwrap();
+    }
+
+    #[test]
+    fn duplicate_keys_are_rejected_on_insert() {
+        let mut g = IrGraph::new();
+        g.insert_node(node("n1", "a")).unwrap();
+
+        let err = g.insert_node(node("n2", "a")).unwrap_err();
+        assert!(err.to_string().contains("duplicate IR node key: a"), "{err}");
+        // Duplicate id is reported as such, not as a key collision.
+        let err = g.insert_node(node("n1", "b")).unwrap_err();
+        assert!(err.to_string().contains("duplicate IR node id: n1"), "{err}");
+        assert_eq!(g.nodes.len(), 1);
+
+        g.insert_node(node("n2", "b")).unwrap();
+        g.insert_edge(edge("e1", "n1", "n2")).unwrap();
+        let err = g.insert_edge(edge("e2", "n2", "n1")).unwrap_err();
+        assert!(err.to_string().contains("duplicate IR edge key: contains:a:b"), "{err}");
+        assert_eq!(g.edges.len(), 1);
+    }
+
+    #[test]
+    fn add_assigns_unique_ids_and_checks_keys() {
+        let mut g = IrGraph::new();
+        // An explicitly inserted "n1" must not be reused.
+        g.insert_node(node("n1", "x")).unwrap();
+        let a = g.add_node(IrNode::new("file", "a")).unwrap();
+        let b = g.add_node(IrNode::new("file", "b")).unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("n2", "n3"));
+
+        let err = g.add_node(IrNode::new("file", "a")).unwrap_err();
+        assert!(err.to_string().contains("duplicate IR node key"), "{err}");
+        assert_eq!(g.nodes.len(), 3);
+
+        let e = g.add_edge(IrEdge::new(a.clone(), b.clone(), "contains")).unwrap();
+        assert_eq!(e, "e0");
+        assert!(g.add_edge(IrEdge::new(a, b, "contains")).is_err());
+        g.validate_basic().unwrap();
+    }
+
     #[test]
     fn default_id_strategy_is_deterministic() {
         let s = DefaultIdStrategy::default();
@@ -474,4 +996,111 @@ mod tests {
         let e2 = s.edge_id("k", "contains", &a, "ent:file:x",).unwrap();
         assert_eq!(e1, e2);
     }
+
+    fn node(id: &str, key: &str) -> IrNode {
+        IrNode {
+            id: id.to_string(),
+            key: key.to_string(),
+            node_type: "file".to_string(),
+            name: key.to_string(),
+            attrs: BTreeMap::new(),
+            digests: vec![],
+            provenance: None,
+            diagnostics: vec![],
+        }
+    }
+
+    fn edge(id: &str, from: &str, to: &str) -> IrEdge {
+        IrEdge {
+            id: id.to_string(),
+            key: "contains:a:b".to_string(),
+            edge_type: "contains".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            attrs: BTreeMap::new(),
+            provenance: None,
+            diagnostics: vec![],
+        }
+    }
+
+    #[test]
+    fn equivalent_graphs_compare_and_hash_equal() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut a = IrGraph::new();
+        a.insert_node(node("n1", "a")).unwrap();
+        a.insert_node(node("n2", "b")).unwrap();
+        a.insert_edge(edge("e1", "n1", "n2")).unwrap();
+
+        // Different temporary ids and insertion order.
+        let mut b = IrGraph::new();
+        b.insert_node(node("x9", "b")).unwrap();
+        b.insert_node(node("x1", "a")).unwrap();
+        b.insert_edge(edge("y7", "x1", "x9")).unwrap();
+
+        assert_eq!(a, b);
+        assert_eq!(a.hash_hex().unwrap(), b.hash_hex().unwrap());
+
+        let hash_of = |g: &IrGraph| {
+            let mut h = DefaultHasher::new();
+            g.hash(&mut h);
+            h.finish()
+        };
+        assert_eq!(hash_of(&a), hash_of(&b));
+
+        let mut c = b.clone();
+        c.nodes
+            .get_mut("x1")
+            .unwrap()
+            .attrs
+            .insert("language".to_string(), IrValue::String("rust".to_string()));
+        assert_ne!(a, c);
+        assert_ne!(a.hash_hex().unwrap(), c.hash_hex().unwrap());
+    }
+
+    #[cfg(feature = "canonical-json")]
+    #[test]
+    fn canonical_json_round_trips() {
+        let mut g = IrGraph::new();
+        let mut a = node("n1", "a");
+        a.attrs.insert("size".to_string(), IrValue::I64(3));
+        a.attrs.insert("ratio".to_string(), IrValue::F64(1.0));
+        a.attrs.insert(
+            "tags".to_string(),
+            IrValue::Array(vec![IrValue::String("x".to_string()), IrValue::Null]),
+        );
+        a.digests.push(IrDigest { alg: "sha256".to_string(), hex: "ab".repeat(32) });
+        g.insert_node(a).unwrap();
+        g.insert_node(node("n2", "b")).unwrap();
+        g.insert_edge(edge("e1", "n1", "n2")).unwrap();
+
+        let text = serde_json::to_vec(&g.to_canonical_json()).unwrap();
+        let back = IrGraph::from_canonical_json(&serde_json::from_slice(&text).unwrap()).unwrap();
+        assert_eq!(g.to_canonical_json()["irVersion"], IR_VERSION);
+        assert_eq!(back, g);
+        assert_eq!(back.to_canonical_json(), g.to_canonical_json());
+
+        let mut other = g.clone();
+        other.nodes.get_mut("n1").unwrap().attrs.insert("ratio".to_string(), IrValue::I64(1));
+        assert_ne!(IrGraph::from_canonical_json(&other.to_canonical_json()).unwrap(), g);
+    }
+
+    #[cfg(feature = "canonical-json")]
+    #[test]
+    fn ir_json_carries_version_and_rejects_others() {
+        let mut g = IrGraph::new();
+        g.insert_node(node("n1", "a")).unwrap();
+        assert_eq!(serde_json::to_value(&g).unwrap()["irVersion"], IR_VERSION);
+
+        let mut future = g.to_canonical_json();
+        future["irVersion"] = "ir.v2".into();
+        let err = IrGraph::from_canonical_json(&future).unwrap_err();
+        assert!(err.to_string().contains("unsupported irVersion ir.v2"), "{err}");
+
+        let mut unversioned = g.to_canonical_json();
+        unversioned.as_object_mut().unwrap().remove("irVersion");
+        let err = IrGraph::from_canonical_json(&unversioned).unwrap_err();
+        assert!(err.to_string().contains("irVersion"), "{err}");
+    }
 }
@@ -10,6 +10,9 @@
 //!
 //! This is a wire-level model. Do not introduce breaking changes for v1.
 
+use crate::config::NetworkPolicy;
+use crate::errors::{SigniaError, SigniaResult};
+
 #[cfg(feature = "canonical-json")]
 use serde::{Deserialize, Serialize};
 
@@ -17,7 +20,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// A SIGNIA manifest instance.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct ManifestV1 {
@@ -53,7 +56,7 @@ pub struct ManifestV1 {
 }
 
 /// Reference to a schema artifact.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct SchemaRefV1 {
@@ -62,7 +65,7 @@ pub struct SchemaRefV1 {
 }
 
 /// Reference to a compiler input.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct InputRefV1 {
@@ -73,7 +76,7 @@ pub struct InputRefV1 {
 }
 
 /// Reference to a compiler output.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct OutputRefV1 {
@@ -84,18 +87,28 @@ pub struct OutputRefV1 {
 }
 
 /// Reference to a plugin.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct PluginRefV1 {
     pub name: String,
     pub version: String,
+    /// Resolved plugin id (e.g. "builtin.repo"), when known.
+    #[cfg_attr(feature = "canonical-json", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub id: Option<String>,
     #[cfg_attr(feature = "canonical-json", serde(default))]
     pub config: Option<Value>,
+    /// Stable plugin fingerprint (lowercase hex) for cache keys, when the host provides one.
+    #[cfg_attr(feature = "canonical-json", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub fingerprint: Option<String>,
+    /// Host capabilities (e.g. "network") the plugin wanted and was granted for
+    /// this compile, sorted; `None` when the host did not report them.
+    #[cfg_attr(feature = "canonical-json", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub granted_caps: Option<Vec<String>>,
 }
 
 /// Execution and resource limits.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct LimitsV1 {
@@ -107,6 +120,57 @@ pub struct LimitsV1 {
     pub network: String,
 }
 
+impl LimitsV1 {
+    /// Build limits, rejecting nonsensical combinations.
+    ///
+    /// Rules:
+    /// - `max_files`, `max_bytes`, `max_nodes` and `timeout_ms` must be non-zero
+    /// - `max_bytes >= max_files`: every counted file implies at least one byte
+    /// - `network` must name a `NetworkPolicy` (whitespace and ASCII case are
+    ///   ignored here); it is stored in wire form
+    ///
+    /// The struct fields stay public for serde; this is the validated path.
+    pub fn new_checked(
+        max_files: u64,
+        max_bytes: u64,
+        max_nodes: u64,
+        max_edges: u64,
+        timeout_ms: u64,
+        network: &str,
+    ) -> SigniaResult<Self> {
+        for (name, v) in [
+            ("maxFiles", max_files),
+            ("maxBytes", max_bytes),
+            ("maxNodes", max_nodes),
+            ("timeoutMs", timeout_ms),
+        ] {
+            if v == 0 {
+                return Err(SigniaError::invalid_argument(format!("limits.{name} must be > 0")));
+            }
+        }
+        if max_bytes < max_files {
+            return Err(SigniaError::invalid_argument(format!(
+                "limits.maxBytes ({max_bytes}) is less than limits.maxFiles ({max_files})"
+            )));
+        }
+        let network = NetworkPolicy::parse(&network.trim().to_ascii_lowercase())?;
+
+        Ok(Self {
+            max_files,
+            max_bytes,
+            max_nodes,
+            max_edges,
+            timeout_ms,
+            network: network.as_str().to_string(),
+        })
+    }
+
+    /// The parsed network policy. Only the exact wire values are accepted.
+    pub fn network_policy(&self) -> SigniaResult<NetworkPolicy> {
+        NetworkPolicy::parse(&self.network)
+    }
+}
+
 impl ManifestV1 {
     /// Create a new manifest with empty collections.
     pub fn new(name: impl Into<String>, limits: LimitsV1) -> Self {
@@ -169,4 +233,31 @@ mod tests {
         assert_eq!(back.version, "v1");
         assert_eq!(back.schemas.len(), 1);
     }
+
+    #[test]
+    fn limits_new_checked_normalizes_network() {
+        let l = LimitsV1::new_checked(100, 10_000, 1_000, 0, 5_000, " Allow-Pinned-Only ").unwrap();
+        assert_eq!(l.network, "allow-pinned-only");
+        assert_eq!(l.network_policy().unwrap(), NetworkPolicy::AllowPinnedOnly);
+        assert_eq!(l.max_edges, 0);
+
+        let err = LimitsV1::new_checked(100, 10_000, 1_000, 0, 5_000, "allow").unwrap_err();
+        assert!(err.to_string().contains("unknown network policy"), "{err}");
+    }
+
+    #[test]
+    fn limits_new_checked_rejects_invalid_combinations() {
+        let cases = [
+            ((0, 10_000, 1_000, 5_000), "maxFiles"),
+            ((100, 0, 1_000, 5_000), "maxBytes"),
+            ((100, 10_000, 0, 5_000), "maxNodes"),
+            ((100, 10_000, 1_000, 0), "timeoutMs"),
+            ((100, 99, 1_000, 5_000), "less than limits.maxFiles"),
+        ];
+        for ((files, bytes, nodes, timeout), want) in cases {
+            let err = LimitsV1::new_checked(files, bytes, nodes, 10, timeout, "deny").unwrap_err();
+            assert!(err.to_string().contains(want), "{want}: {err}");
+        }
+        LimitsV1::new_checked(100, 100, 1, 0, 1, "deny").unwrap();
+    }
 }
@@ -124,6 +124,7 @@ impl SchemaMeta {
             .and_then(|x| x.as_str())
             .ok_or_else(|| SigniaError::invalid_argument("schema.meta.createdAt must be a string"))?
             .to_string();
+        crate::determinism::time::validate_iso8601(&created_at)?;
 
         let source_obj = obj
             .get("source")
@@ -295,4 +296,15 @@ mod tests {
         assert_eq!(m.source.locator, "artifact:/demo");
         assert_eq!(m.normalization.newline, "lf");
     }
+
+    #[test]
+    fn from_json_rejects_malformed_created_at() {
+        let j = SchemaMetaBuilder::new("demo")
+            .created_at("not-a-date")
+            .source("path", "artifact:/demo")
+            .build()
+            .to_json();
+        let err = validate_schema_meta_json(&j).unwrap_err();
+        assert!(err.to_string().contains("not-a-date"), "{err}");
+    }
 }
@@ -20,14 +20,32 @@
 //! - Canonical hashing must never rely on default `serde_json::to_vec` because it is not a
 //!   canonical form (ordering/whitespace are not guaranteed). Use `crate::canonical` instead.
 
-// pub mod v1;
+pub mod bundle_v1;
+pub mod manifest_v1;
+pub mod proof_v1;
+pub mod schema_v1;
+pub mod schema_v2;
+
+/// v1 wire types: schema, manifest, proof and bundle descriptor.
+pub mod v1 {
+    pub use super::bundle_v1::BundleInfoV1;
+    pub use super::manifest_v1::*;
+    pub use super::proof_v1::*;
+    pub use super::schema_v1::*;
+}
+
+/// Schema v2 wire types. Manifests and proofs are still v1 only.
+pub mod v2 {
+    pub use super::schema_v2::{EntityV2, ProducerV2, SchemaV2};
+}
 
 pub use v1::{
     EdgeV1, EntityV1, ManifestV1, ProofV1, SchemaV1,
     // Supporting structures
-    BundleInfoV1, HashRefV1, HashSpecV1, InputRefV1, LimitsV1, NormalizationV1, OutputFileV1,
-    OutputStatsV1, PluginRefV1, SourceRefV1,
+    BundleInfoV1, DigestV1, InputRefV1, LimitsV1, NormalizationV1, OutputRefV1, PluginRefV1,
+    SchemaMetaV1, SchemaRefV1, SourceRefV1,
 };
+pub use v2::SchemaV2;
 
 /// A versioned schema enum for ergonomic APIs that want to accept multiple versions.
 ///
@@ -35,6 +53,7 @@ pub use v1::{
 #[derive(Debug, Clone)]
 pub enum AnySchema {
     V1(SchemaV1),
+    V2(SchemaV2),
 }
 
 /// A versioned manifest enum for ergonomic APIs that want to accept multiple versions.
@@ -54,6 +73,15 @@ impl AnySchema {
     pub fn version(&self) -> &'static str {
         match self {
             AnySchema::V1(_) => "v1",
+            AnySchema::V2(_) => "v2",
+        }
+    }
+
+    /// Upconvert to the latest schema version. Never fails: v2 is a superset of v1.
+    pub fn into_v2(self) -> SchemaV2 {
+        match self {
+            AnySchema::V1(s) => s.into(),
+            AnySchema::V2(s) => s,
         }
     }
 }
@@ -96,9 +124,9 @@ pub mod validate {
     /// - edge ids are unique
     /// - edge endpoints reference existing entity ids (best-effort)
     pub fn schema_v1_basic(schema: &SchemaV1) -> SigniaResult<()> {
-        use std::collections::HashSet;
+        use std::collections::BTreeSet;
 
-        let mut ent_ids = HashSet::new();
+        let mut ent_ids = BTreeSet::new();
         for e in &schema.entities {
             if !ent_ids.insert(e.id.clone()) {
                 return Err(SigniaError::invalid_argument(format!(
@@ -108,7 +136,7 @@ pub mod validate {
             }
         }
 
-        let mut edge_ids = HashSet::new();
+        let mut edge_ids = BTreeSet::new();
         for ed in &schema.edges {
             if !edge_ids.insert(ed.id.clone()) {
                 return Err(SigniaError::invalid_argument(format!(
@@ -13,6 +13,9 @@
 //!
 //! This is a wire-level model. Do not introduce breaking changes for v1.
 
+use crate::errors::{SigniaError, SigniaResult};
+use crate::merkle::{MerkleTree, OddNodePolicy};
+
 #[cfg(feature = "canonical-json")]
 use serde::{Deserialize, Serialize};
 
@@ -20,7 +23,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// A SIGNIA proof instance.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct ProofV1 {
@@ -33,6 +36,14 @@ pub struct ProofV1 {
     /// Merkle root for the proof set.
     pub root: String,
 
+    /// Odd-level folding rule the root was built with. Omitted on the wire when
+    /// `duplicate` (the v1 default), so existing proofs keep their bytes and id.
+    #[cfg_attr(
+        feature = "canonical-json",
+        serde(default, skip_serializing_if = "OddNodePolicy::is_duplicate")
+    )]
+    pub merkle_odd_policy: OddNodePolicy,
+
     /// Leaf entries used to construct the Merkle tree.
     pub leaves: Vec<LeafV1>,
 
@@ -52,7 +63,7 @@ pub struct ProofV1 {
 /// - manifestHash
 /// - file:README.md hash
 /// - meta field hash (optional)
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct LeafV1 {
@@ -64,7 +75,7 @@ pub struct LeafV1 {
 }
 
 /// Inclusion proof for a specific leaf.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct InclusionProofV1 {
@@ -79,22 +90,43 @@ pub struct InclusionProofV1 {
 }
 
 /// One Merkle sibling entry.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SiblingV1 {
-    /// "left" or "right" indicates where the sibling hash is placed relative to the running hash.
-    pub side: String,
+    /// Where the sibling hash is placed relative to the running hash.
+    pub side: Side,
     /// Sibling node hash.
     pub hash: String,
 }
 
+/// Side of a Merkle sibling. Serialized as "left" / "right".
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "lowercase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The sibling is the left operand: `hash(sibling || running)`.
+    Left,
+    /// The sibling is the right operand: `hash(running || sibling)`.
+    Right,
+}
+
+impl Side {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Side::Left => "left",
+            Side::Right => "right",
+        }
+    }
+}
+
 impl ProofV1 {
     pub fn new(hash_alg: impl Into<String>, root: impl Into<String>) -> Self {
         Self {
             version: "v1".to_string(),
             hash_alg: hash_alg.into(),
             root: root.into(),
+            merkle_odd_policy: OddNodePolicy::Duplicate,
             leaves: Vec::new(),
             inclusions: None,
             meta: None,
@@ -108,26 +140,79 @@ impl ProofV1 {
     pub fn set_inclusions(&mut self, inc: Vec<InclusionProofV1>) {
         self.inclusions = Some(inc);
     }
+
+    /// Copy with leaves sorted by (key, value) and inclusions by (key, value).
+    ///
+    /// This is the form `hash_proof_v1_hex` hashes, so the proof's content id
+    /// does not depend on the order leaves or inclusions were pushed in.
+    pub fn canonical_form(&self) -> ProofV1 {
+        let mut p = self.clone();
+        p.leaves.sort_by(|a, b| (&a.key, &a.value).cmp(&(&b.key, &b.value)));
+        if let Some(incs) = p.inclusions.as_mut() {
+            incs.sort_by(|a, b| (&a.key, &a.value).cmp(&(&b.key, &b.value)));
+        }
+        p
+    }
+
+    /// Attach inclusion proofs for `keys`, taken from `tree`.
+    ///
+    /// `tree` must hold this proof's leaves in order, each pushed as a
+    /// `key=value` payload (the layout `verify_inclusion` checks against).
+    /// Unknown keys and a tree that does not match `root` are errors.
+    pub fn with_inclusions(mut self, tree: &MerkleTree, keys: &[&str]) -> SigniaResult<ProofV1> {
+        if tree.leaf_count() != self.leaves.len() {
+            return Err(SigniaError::invalid_argument(format!(
+                "tree has {} leaves but proof has {}",
+                tree.leaf_count(),
+                self.leaves.len()
+            )));
+        }
+        if tree.root_hex()? != self.root {
+            return Err(SigniaError::invalid_argument("tree root does not match proof root"));
+        }
+
+        let mut incs = Vec::with_capacity(keys.len());
+        for key in keys {
+            let index = self
+                .leaves
+                .iter()
+                .position(|l| l.key == *key)
+                .ok_or_else(|| SigniaError::invalid_argument(format!("unknown proof leaf key: {key}")))?;
+
+            let siblings = tree.inclusion_proof(index)?;
+
+            let leaf = &self.leaves[index];
+            incs.push(InclusionProofV1 {
+                key: leaf.key.clone(),
+                value: leaf.value.clone(),
+                siblings,
+            });
+        }
+
+        self.set_inclusions(incs);
+        Ok(self)
+    }
 }
 
 #[cfg(test)]
 #[cfg(feature = "canonical-json")]
 mod tests {
     use super::*;
+    use crate::proof::leaf_keys;
 
     #[test]
     fn proof_roundtrip() {
         let mut p = ProofV1::new("sha256", "a".repeat(64));
         p.push_leaf(LeafV1 {
-            key: "digest:schemaHash".to_string(),
+            key: leaf_keys::SCHEMA_HASH.to_string(),
             value: "b".repeat(64),
         });
 
         p.set_inclusions(vec![InclusionProofV1 {
-            key: "digest:schemaHash".to_string(),
+            key: leaf_keys::SCHEMA_HASH.to_string(),
             value: "b".repeat(64),
             siblings: vec![SiblingV1 {
-                side: "left".to_string(),
+                side: Side::Left,
                 hash: "c".repeat(64),
             }],
         }]);
@@ -139,4 +224,58 @@ mod tests {
         assert_eq!(back.leaves.len(), 1);
         assert!(back.inclusions.is_some());
     }
+
+    #[test]
+    fn side_serde_is_compatible_with_legacy_strings() {
+        let s: SiblingV1 = serde_json::from_str(r#"{"side":"left","hash":"aa"}"#).unwrap();
+        assert_eq!(s.side, Side::Left);
+        let s: SiblingV1 = serde_json::from_str(r#"{"side":"right","hash":"aa"}"#).unwrap();
+        assert_eq!(s.side, Side::Right);
+
+        assert_eq!(serde_json::to_string(&Side::Left).unwrap(), r#""left""#);
+        assert_eq!(serde_json::to_string(&Side::Right).unwrap(), r#""right""#);
+
+        for bad in ["\"LEFT\"", "\"up\"", "\"\"", "0"] {
+            assert!(serde_json::from_str::<Side>(bad).is_err(), "accepted {bad}");
+        }
+    }
+
+    #[test]
+    fn with_inclusions_verifies_each_key() {
+        use crate::merkle::MerkleTreeOptions;
+        use crate::pipeline::verify::verify_inclusion;
+
+        let leaves: Vec<LeafV1> = [leaf_keys::MANIFEST_HASH, leaf_keys::SCHEMA_HASH, leaf_keys::META_KIND]
+            .iter()
+            .enumerate()
+            .map(|(i, k)| LeafV1 {
+                key: k.to_string(),
+                value: format!("{i}").repeat(64),
+            })
+            .collect();
+
+        let mut tree = MerkleTree::new(MerkleTreeOptions {
+            hash_alg: "sha256".to_string(),
+            domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
+            domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy: OddNodePolicy::Duplicate,
+        });
+        for l in &leaves {
+            tree.push_leaf(format!("{}={}", l.key, l.value).as_bytes()).unwrap();
+        }
+
+        let mut p = ProofV1::new("sha256", tree.root_hex().unwrap());
+        p.leaves = leaves;
+
+        let keys = [leaf_keys::MANIFEST_HASH, leaf_keys::SCHEMA_HASH, leaf_keys::META_KIND];
+        let p = p.with_inclusions(&tree, &keys).unwrap();
+        let incs = p.inclusions.as_ref().unwrap();
+        assert_eq!(incs.len(), 3);
+        for inc in incs {
+            verify_inclusion(&p, inc).unwrap();
+        }
+
+        let err = p.clone().with_inclusions(&tree, &["file:missing"]).unwrap_err();
+        assert!(err.to_string().contains("unknown proof leaf key"));
+    }
 }
@@ -18,7 +18,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// A SIGNIA schema instance.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct SchemaV1 {
@@ -39,7 +39,7 @@ pub struct SchemaV1 {
 }
 
 /// A graph entity (node).
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct EntityV1 {
@@ -57,7 +57,7 @@ pub struct EntityV1 {
 }
 
 /// Digest information for entities.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct DigestV1 {
@@ -69,7 +69,7 @@ pub struct DigestV1 {
 }
 
 /// A graph edge (relationship).
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct EdgeV1 {
@@ -87,7 +87,7 @@ pub struct EdgeV1 {
 ///
 /// This is used by compilers and verifiers, but `SchemaV1.meta` remains generic JSON.
 /// Keeping meta as generic JSON gives forward compatibility for new meta fields.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct SchemaMetaV1 {
@@ -105,7 +105,7 @@ pub struct SchemaMetaV1 {
 }
 
 /// Source reference for schema compilation.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct SourceRefV1 {
@@ -116,7 +116,7 @@ pub struct SourceRefV1 {
 }
 
 /// Normalization policy recorded in meta.
-#[cfg_attr(feature = "canonical-json", derive(Debug, Clone, Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct NormalizationV1 {
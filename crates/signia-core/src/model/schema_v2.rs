@@ -0,0 +1,241 @@
+//! SIGNIA Schema v2 model.
+//!
+//! Schema v2 is Schema v1 plus two optional additions:
+//! - `annotations` on entities: free-form, non-structural notes keyed by name
+//! - a top-level `producer` block naming the tool that emitted the schema
+//!
+//! Every v1 document upconverts to v2 (`From<SchemaV1>`). A v2 document
+//! downconverts to v1 (`TryFrom<SchemaV2>`) only when it uses neither addition,
+//! so the conversion never drops data silently.
+//!
+//! Edges are unchanged from v1 and reuse `EdgeV1`.
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "canonical-json")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "canonical-json")]
+use serde_json::Value;
+
+use crate::errors::{SigniaError, SigniaResult};
+use crate::model::v1::{DigestV1, EdgeV1, EntityV1, SchemaV1};
+
+/// A SIGNIA schema instance (v2).
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone)]
+pub struct SchemaV2 {
+    /// Schema version. Must be "v2".
+    pub version: String,
+
+    /// High-level schema kind (repo, dataset, openapi, workflow, etc).
+    pub kind: String,
+
+    /// Metadata describing the origin and normalization policy.
+    pub meta: Value,
+
+    /// Tool that produced this schema, if recorded.
+    #[cfg_attr(feature = "canonical-json", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub producer: Option<ProducerV2>,
+
+    /// Graph entities.
+    pub entities: Vec<EntityV2>,
+
+    /// Graph edges.
+    pub edges: Vec<EdgeV1>,
+}
+
+/// The tool that emitted a schema.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProducerV2 {
+    /// Tool name (e.g. "signia-cli").
+    pub name: String,
+
+    /// Tool version (e.g. "0.1.0").
+    pub version: String,
+}
+
+/// A graph entity (node) with optional annotations.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone)]
+pub struct EntityV2 {
+    pub id: String,
+    #[cfg_attr(feature = "canonical-json", serde(rename = "type"))]
+    pub r#type: String,
+    pub name: String,
+
+    /// Deterministic attribute map stored as JSON object.
+    pub attrs: Value,
+
+    /// Optional content digests (e.g. file hashes).
+    #[cfg_attr(feature = "canonical-json", serde(skip_serializing_if = "Option::is_none"))]
+    pub digests: Option<Vec<DigestV1>>,
+
+    /// Free-form annotations. Omitted from the wire form when empty.
+    #[cfg_attr(feature = "canonical-json", serde(default, skip_serializing_if = "BTreeMap::is_empty"))]
+    pub annotations: BTreeMap<String, Value>,
+}
+
+impl SchemaV2 {
+    /// Create a new schema with empty entities/edges and no producer.
+    pub fn new(kind: impl Into<String>, meta: Value) -> Self {
+        Self {
+            version: "v2".to_string(),
+            kind: kind.into(),
+            meta,
+            producer: None,
+            entities: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Add an entity.
+    pub fn push_entity(&mut self, e: EntityV2) {
+        self.entities.push(e);
+    }
+
+    /// Add an edge.
+    pub fn push_edge(&mut self, e: EdgeV1) {
+        self.edges.push(e);
+    }
+}
+
+impl From<EntityV1> for EntityV2 {
+    fn from(e: EntityV1) -> Self {
+        Self {
+            id: e.id,
+            r#type: e.r#type,
+            name: e.name,
+            attrs: e.attrs,
+            digests: e.digests,
+            annotations: BTreeMap::new(),
+        }
+    }
+}
+
+impl From<SchemaV1> for SchemaV2 {
+    fn from(s: SchemaV1) -> Self {
+        Self {
+            version: "v2".to_string(),
+            kind: s.kind,
+            meta: s.meta,
+            producer: None,
+            entities: s.entities.into_iter().map(EntityV2::from).collect(),
+            edges: s.edges,
+        }
+    }
+}
+
+impl TryFrom<SchemaV2> for SchemaV1 {
+    type Error = SigniaError;
+
+    /// Fails if the schema records a producer or any entity has annotations:
+    /// v1 has nowhere to put them.
+    fn try_from(s: SchemaV2) -> SigniaResult<Self> {
+        if s.producer.is_some() {
+            return Err(SigniaError::invalid_argument(
+                "schema v2 has a producer block, which v1 cannot represent",
+            ));
+        }
+
+        let mut entities = Vec::with_capacity(s.entities.len());
+        for e in s.entities {
+            if !e.annotations.is_empty() {
+                return Err(SigniaError::invalid_argument(format!(
+                    "entity {} has annotations, which v1 cannot represent",
+                    e.id
+                )));
+            }
+            entities.push(EntityV1 {
+                id: e.id,
+                r#type: e.r#type,
+                name: e.name,
+                attrs: e.attrs,
+                digests: e.digests,
+            });
+        }
+
+        Ok(SchemaV1 {
+            version: "v1".to_string(),
+            kind: s.kind,
+            meta: s.meta,
+            entities,
+            edges: s.edges,
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "canonical-json")]
+mod tests {
+    use super::*;
+
+    fn v1_doc() -> SchemaV1 {
+        let mut s = SchemaV1::new("repo", serde_json::json!({"name":"demo"}));
+        s.push_entity(EntityV1 {
+            id: "ent:file:a".to_string(),
+            r#type: "file".to_string(),
+            name: "a".to_string(),
+            attrs: serde_json::json!({"path":"artifact:/a"}),
+            digests: Some(vec![DigestV1 { alg: "sha256".to_string(), hex: "a".repeat(64) }]),
+        });
+        s.push_entity(EntityV1 {
+            id: "ent:file:b".to_string(),
+            r#type: "file".to_string(),
+            name: "b".to_string(),
+            attrs: serde_json::json!({}),
+            digests: None,
+        });
+        s.push_edge(EdgeV1 {
+            id: "edge:contains:a:b".to_string(),
+            r#type: "contains".to_string(),
+            from: "ent:file:a".to_string(),
+            to: "ent:file:b".to_string(),
+            attrs: serde_json::json!({}),
+        });
+        s
+    }
+
+    #[test]
+    fn v1_upconverts() {
+        let v1 = v1_doc();
+        let v2 = SchemaV2::from(v1.clone());
+
+        assert_eq!(v2.version, "v2");
+        assert_eq!(v2.kind, v1.kind);
+        assert_eq!(v2.producer, None);
+        assert_eq!(v2.entities.len(), 2);
+        assert!(v2.entities.iter().all(|e| e.annotations.is_empty()));
+        assert_eq!(v2.entities[0].digests.as_ref().unwrap()[0].alg, "sha256");
+
+        // Apart from the version, the wire form is identical: v2 adds nothing.
+        let mut a = serde_json::to_value(&v1).unwrap();
+        a["version"] = "v2".into();
+        assert_eq!(serde_json::to_value(&v2).unwrap(), a);
+    }
+
+    #[test]
+    fn v2_without_new_fields_downconverts_losslessly() {
+        let v1 = v1_doc();
+        let back = SchemaV1::try_from(SchemaV2::from(v1.clone())).unwrap();
+        assert_eq!(back.version, "v1");
+        assert_eq!(serde_json::to_value(&back).unwrap(), serde_json::to_value(&v1).unwrap());
+    }
+
+    #[test]
+    fn v2_only_fields_block_downconversion() {
+        let mut v2 = SchemaV2::from(v1_doc());
+        v2.producer = Some(ProducerV2 { name: "signia-cli".to_string(), version: "0.1.0".to_string() });
+        let err = SchemaV1::try_from(v2.clone()).unwrap_err();
+        assert!(err.to_string().contains("producer"));
+
+        v2.producer = None;
+        v2.entities[1].annotations.insert("owner".to_string(), serde_json::json!("data-team"));
+        let err = SchemaV1::try_from(v2).unwrap_err();
+        assert!(err.to_string().contains("ent:file:b"));
+    }
+}
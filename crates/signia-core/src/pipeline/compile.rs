@@ -25,6 +25,10 @@ use std::collections::BTreeMap;
 
 use crate::errors::{SigniaError, SigniaResult};
 use crate::pipeline::{infer, stages, Pipeline, PipelineContext, PipelineData};
+use crate::proof::leaf_keys;
+
+#[cfg(feature = "canonical-json")]
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "canonical-json")]
 use serde_json::Value;
@@ -96,8 +100,14 @@ pub struct OutputSpec {
 pub struct PluginSpec {
     pub name: String,
     pub version: String,
+    /// Resolved plugin id, if known.
+    pub id: Option<String>,
     #[cfg(feature = "canonical-json")]
     pub config: Option<Value>,
+    /// Plugin fingerprint, if known.
+    pub fingerprint: Option<String>,
+    /// Host capabilities granted to the plugin, if known.
+    pub granted_caps: Option<Vec<String>>,
 }
 
 /// Limits specification.
@@ -109,6 +119,8 @@ pub struct LimitsSpec {
     pub max_edges: u64,
     pub timeout_ms: u64,
     pub network: String,
+    /// Maximum byte length of a single proof leaf value.
+    pub max_leaf_value_bytes: u64,
 }
 
 impl Default for LimitsSpec {
@@ -120,11 +132,13 @@ impl Default for LimitsSpec {
             max_edges: 4_000_000,
             timeout_ms: 60_000,
             network: "deny".to_string(),
+            max_leaf_value_bytes: stages::DEFAULT_MAX_LEAF_VALUE_BYTES,
         }
     }
 }
 
 /// Output bundle for compilation.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct CompileBundle {
     #[cfg(feature = "canonical-json")]
@@ -136,14 +150,24 @@ pub struct CompileBundle {
 }
 
 /// Stats for presentation.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone, Default)]
 pub struct CompileStats {
     pub entities: usize,
     pub edges: usize,
     pub leaf_count: usize,
+    /// Length of the canonical JSON bytes of each artifact (0 if not emitted).
+    pub schema_bytes: usize,
+    pub manifest_bytes: usize,
+    pub proof_bytes: usize,
 }
 
 /// A compile report includes bundle + diagnostics + stats.
+///
+/// With `canonical-json` the report serializes as `{bundle, diagnostics, stats}`;
+/// diagnostics keep emission order and their `data` maps are key-sorted.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct CompileReport {
     pub bundle: CompileBundle,
@@ -193,7 +217,10 @@ impl CompileRequest {
             m.add_plugin(PluginRefV1 {
                 name: p.name.clone(),
                 version: p.version.clone(),
+                id: p.id.clone(),
                 config: p.config.clone(),
+                fingerprint: p.fingerprint.clone(),
+                granted_caps: p.granted_caps.clone(),
             });
         }
 
@@ -205,6 +232,122 @@ impl CompileRequest {
     }
 }
 
+/// A component that turns a raw input into an IR graph.
+///
+/// Core does not execute plugins itself; hosts adapt whichever plugin they selected
+/// to this trait so that `compile_from_input` can record exactly what ran.
+#[cfg(feature = "canonical-json")]
+pub trait IrProducer {
+    /// Resolved id (e.g. "builtin.repo").
+    fn id(&self) -> &str;
+    /// Plugin name as recorded in the manifest.
+    fn name(&self) -> &str;
+    /// Plugin version as recorded in the manifest.
+    fn version(&self) -> &str;
+    /// Plugin fingerprint as recorded in the manifest, if the host has one.
+    fn fingerprint(&self) -> Option<String> {
+        None
+    }
+    /// Host capabilities granted to the plugin for this run, as recorded in the
+    /// manifest, if the host evaluated them.
+    fn granted_caps(&self) -> Option<Vec<String>> {
+        None
+    }
+    /// Produce an IR graph from the input.
+    fn produce(&self, input: &Value) -> SigniaResult<IrGraph>;
+}
+
+/// Compile orchestration from a raw input and the producer selected for it.
+///
+/// The producer is recorded into `manifest.plugins` (name, version, resolved id,
+/// fingerprint and granted capabilities when the producer has them).
+/// Caller-provided entries are reconciled:
+/// - an entry with the same name and version is kept (its config is preserved) and the id is filled in
+/// - an entry with the same name but a different version is rejected
+/// - otherwise the executed plugin is appended
+///
+/// The report starts with a `compile.plugin.selected` info diagnostic whose data
+/// records the kind, the producer id and what structural detection makes of
+/// `input` (`detected_kind`, `detection_confidence`; `unknown`/0 if nothing matched).
+#[cfg(feature = "canonical-json")]
+pub fn compile_from_input(
+    input: &Value,
+    producer: &dyn IrProducer,
+    mut req: CompileRequest,
+    id_strategy: Option<&dyn IdStrategy>,
+) -> SigniaResult<CompileReport> {
+    let ir = producer.produce(input)?;
+    record_executed_plugin(&mut req.plugins, producer)?;
+    let selected = plugin_selected_diagnostic(input, &req.kind, producer.id());
+    let mut rep = compile_from_ir(ir, req, id_strategy)?;
+    rep.diagnostics.insert(0, selected);
+    Ok(rep)
+}
+
+#[cfg(feature = "canonical-json")]
+fn plugin_selected_diagnostic(input: &Value, kind: &str, plugin_id: &str) -> crate::pipeline::PipelineDiagnostic {
+    let detection = crate::pipeline::detect::detect_input_kind(input);
+    let mut data = BTreeMap::new();
+    data.insert("kind".to_string(), kind.to_string());
+    data.insert("plugin_id".to_string(), plugin_id.to_string());
+    data.insert("detected_kind".to_string(), detection.kind.as_str().to_string());
+    data.insert("detection_confidence".to_string(), detection.confidence.to_string());
+    crate::pipeline::PipelineDiagnostic {
+        level: crate::pipeline::DiagnosticLevel::Info,
+        code: "compile.plugin.selected".to_string(),
+        message: format!(
+            "compiled {kind} input with {plugin_id} (detected {} at {}% confidence)",
+            detection.kind.as_str(),
+            detection.confidence
+        ),
+        data,
+    }
+}
+
+#[cfg(feature = "canonical-json")]
+fn record_executed_plugin(plugins: &mut Vec<PluginSpec>, producer: &dyn IrProducer) -> SigniaResult<()> {
+    for p in plugins.iter_mut() {
+        if p.name != producer.name() {
+            continue;
+        }
+        if p.version != producer.version() {
+            return Err(SigniaError::invalid_argument(format!(
+                "plugin {} recorded with version {} but {} was executed",
+                p.name,
+                p.version,
+                producer.version()
+            )));
+        }
+        if let Some(id) = &p.id {
+            if id != producer.id() {
+                return Err(SigniaError::invalid_argument(format!(
+                    "plugin {} recorded with id {id} but {} was executed",
+                    p.name,
+                    producer.id()
+                )));
+            }
+        }
+        p.id = Some(producer.id().to_string());
+        if let Some(fp) = producer.fingerprint() {
+            p.fingerprint = Some(fp);
+        }
+        if let Some(caps) = producer.granted_caps() {
+            p.granted_caps = Some(caps);
+        }
+        return Ok(());
+    }
+
+    plugins.push(PluginSpec {
+        name: producer.name().to_string(),
+        version: producer.version().to_string(),
+        id: Some(producer.id().to_string()),
+        config: None,
+        fingerprint: producer.fingerprint(),
+        granted_caps: producer.granted_caps(),
+    });
+    Ok(())
+}
+
 /// Compile orchestration from IR graph.
 ///
 /// You may optionally supply a custom id strategy. If not supplied, a default stable strategy is used.
@@ -216,6 +359,7 @@ pub fn compile_from_ir(
 ) -> SigniaResult<CompileReport> {
     // Basic IR sanity
     ir.validate_basic()?;
+    crate::determinism::time::validate_iso8601(&req.created_at)?;
 
     // Enforce limits early
     if (ir.nodes.len() as u64) > req.limits.max_nodes {
@@ -245,6 +389,7 @@ pub fn compile_from_ir(
     ctx.clock.now_iso8601 = req.created_at.clone();
     ctx.set_param("schema.kind", req.kind.clone());
     ctx.set_json_param("schema.meta", req.meta.clone());
+    ctx.set_param("limits.max_leaf_value_bytes", req.limits.max_leaf_value_bytes.to_string());
 
     // Construct pipeline to emit schema
     let mut p = Pipeline::new();
@@ -252,7 +397,7 @@ pub fn compile_from_ir(
     p.push_stage(stages::NormalizeIrStage::new("ir.normalize"));
     p.push_stage(stages::EmitSchemaV1Stage::new("emit.schema_v1"));
 
-    let report_schema = p.run(ctx.clone(), PipelineData::Ir(ir))?;
+    let report_schema = p.run(ctx.fork(), PipelineData::Ir(ir))?;
     let schema = match report_schema.output {
         PipelineData::SchemaV1(s) => s,
         other => {
@@ -275,31 +420,37 @@ pub fn compile_from_ir(
     let proof = if req.build_proof {
         let mut leaves: Vec<crate::model::v1::LeafV1> = Vec::new();
         leaves.push(crate::model::v1::LeafV1 {
-            key: "digest:schemaHash".to_string(),
+            key: leaf_keys::SCHEMA_HASH.to_string(),
             value: schema_hash_hex.clone(),
         });
         leaves.push(crate::model::v1::LeafV1 {
-            key: "digest:manifestHash".to_string(),
+            key: leaf_keys::MANIFEST_HASH.to_string(),
             value: manifest_hash_hex.clone(),
         });
 
         // Optional: include kind and createdAt for traceability (hashed as values)
         leaves.push(crate::model::v1::LeafV1 {
-            key: "meta:kind".to_string(),
+            key: leaf_keys::META_KIND.to_string(),
             value: crate::hash::hash_bytes_hex(req.kind.as_bytes())?,
         });
         leaves.push(crate::model::v1::LeafV1 {
-            key: "meta:createdAt".to_string(),
+            key: leaf_keys::META_CREATED_AT.to_string(),
             value: crate::hash::hash_bytes_hex(req.created_at.as_bytes())?,
         });
 
+        for leaf in &mut leaves {
+            leaf.value = crate::proof::normalize_leaf_value(&leaf.value);
+        }
+
         // Deterministic ordering
         leaves.sort_by(|a, b| a.key.cmp(&b.key));
+        stages::check_leaf_values(&leaves, req.limits.max_leaf_value_bytes)?;
 
         let mut tree = crate::merkle::MerkleTree::new(crate::merkle::MerkleTreeOptions {
             hash_alg: "sha256".to_string(),
             domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
             domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy: crate::merkle::OddNodePolicy::Duplicate,
         });
 
         for leaf in &leaves {
@@ -321,6 +472,12 @@ pub fn compile_from_ir(
         entities: schema.entities.len(),
         edges: schema.edges.len(),
         leaf_count: proof.as_ref().map(|p| p.leaves.len()).unwrap_or(0),
+        schema_bytes: canonical_len(&schema)?,
+        manifest_bytes: canonical_len(&manifest)?,
+        proof_bytes: match &proof {
+            Some(p) => canonical_len(p)?,
+            None => 0,
+        },
     };
 
     Ok(CompileReport {
@@ -334,6 +491,13 @@ pub fn compile_from_ir(
     })
 }
 
+/// Size of a value's canonical JSON encoding (the bytes that get hashed).
+#[cfg(feature = "canonical-json")]
+fn canonical_len<T: serde::Serialize>(v: &T) -> SigniaResult<usize> {
+    let bytes = crate::determinism::canonical_json::to_canonical_bytes(&serde_json::to_value(v)?)?;
+    Ok(bytes.len())
+}
+
 #[cfg(test)]
 #[cfg(feature = "canonical-json")]
 mod tests {
@@ -341,8 +505,7 @@ mod tests {
     use crate::model::ir::{IrEdge, IrNode};
     use serde_json::json;
 
-    #[test]
-    fn compile_from_ir_emits_bundle() {
+    fn demo_ir() -> IrGraph {
         let mut ir = IrGraph::new();
         ir.insert_node(IrNode {
             id: "n1".to_string(),
@@ -377,8 +540,11 @@ mod tests {
             diagnostics: vec![],
         })
         .unwrap();
+        ir
+    }
 
-        let req = CompileRequest {
+    fn demo_request() -> CompileRequest {
+        CompileRequest {
             kind: "repo".to_string(),
             meta: json!({
                 "name":"demo",
@@ -401,18 +567,218 @@ mod tests {
             plugins: vec![PluginSpec {
                 name: "repo".to_string(),
                 version: "v1".to_string(),
+                id: None,
                 config: None,
+                fingerprint: None,
+                granted_caps: None,
             }],
             limits: LimitsSpec::default(),
             run_inference: true,
             build_proof: true,
-        };
+        }
+    }
 
-        let rep = compile_from_ir(ir, req, Some(&DefaultIdStrategy::default())).unwrap();
+    #[test]
+    fn compile_from_ir_emits_bundle() {
+        let rep = compile_from_ir(demo_ir(), demo_request(), Some(&DefaultIdStrategy::default())).unwrap();
         assert_eq!(rep.bundle.schema.version, "v1");
         assert_eq!(rep.bundle.manifest.version, "v1");
         assert!(rep.bundle.proof.is_some());
         assert!(rep.stats.entities >= 2);
         assert!(rep.stats.leaf_count >= 2);
     }
+
+    #[test]
+    fn compile_emits_registered_leaf_keys() {
+        let rep = compile_from_ir(demo_ir(), demo_request(), Some(&DefaultIdStrategy::default())).unwrap();
+        let proof = rep.bundle.proof.unwrap();
+        let keys: Vec<&str> = proof.leaves.iter().map(|l| l.key.as_str()).collect();
+        assert_eq!(keys, leaf_keys::COMPILE_KEYS);
+
+        let opts = crate::pipeline::verify::VerifyOptions::default();
+        for k in keys {
+            assert!(opts.allowed_leaf_prefixes.iter().any(|p| k.starts_with(p.as_str())), "{k}");
+        }
+    }
+
+    #[test]
+    fn compile_rejects_oversized_leaf_value() {
+        let mut req = demo_request();
+        req.limits.max_leaf_value_bytes = 8;
+        let err = compile_from_ir(demo_ir(), req, Some(&DefaultIdStrategy::default())).unwrap_err();
+        assert!(err.to_string().contains(leaf_keys::MANIFEST_HASH), "{err}");
+    }
+
+    #[test]
+    fn compile_report_round_trips_through_json() {
+        let mut rep = compile_from_ir(demo_ir(), demo_request(), Some(&DefaultIdStrategy::default())).unwrap();
+        rep.diagnostics.push(crate::pipeline::PipelineDiagnostic {
+            level: crate::pipeline::DiagnosticLevel::Warning,
+            code: "demo.warning".to_string(),
+            message: "demo".to_string(),
+            data: BTreeMap::from([("b".to_string(), "2".to_string()), ("a".to_string(), "1".to_string())]),
+        });
+
+        let text = serde_json::to_string(&rep).unwrap();
+        let v: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(v["stats"]["leafCount"], rep.stats.leaf_count);
+        let last = v["diagnostics"].as_array().unwrap().last().unwrap();
+        assert_eq!(last["level"], "warning");
+        assert_eq!(serde_json::to_string(&last["data"]).unwrap(), r#"{"a":"1","b":"2"}"#);
+
+        let back: CompileReport = serde_json::from_str(&text).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), text);
+
+        // A second compile serializes to the same bytes.
+        let mut again = compile_from_ir(demo_ir(), demo_request(), Some(&DefaultIdStrategy::default())).unwrap();
+        again.diagnostics.push(rep.diagnostics.last().unwrap().clone());
+        assert_eq!(serde_json::to_string(&again).unwrap(), text);
+    }
+
+    #[test]
+    fn compile_stats_report_canonical_sizes() {
+        use crate::determinism::canonical_json::to_canonical_bytes;
+
+        let rep = compile_from_ir(demo_ir(), demo_request(), Some(&DefaultIdStrategy::default())).unwrap();
+        let len = |v: serde_json::Value| to_canonical_bytes(&v).unwrap().len();
+
+        let b = &rep.bundle;
+        assert_eq!(rep.stats.schema_bytes, len(serde_json::to_value(&b.schema).unwrap()));
+        assert_eq!(rep.stats.manifest_bytes, len(serde_json::to_value(&b.manifest).unwrap()));
+        assert_eq!(
+            rep.stats.proof_bytes,
+            len(serde_json::to_value(b.proof.as_ref().unwrap()).unwrap())
+        );
+        assert!(rep.stats.schema_bytes > 0);
+
+        let again = compile_from_ir(demo_ir(), demo_request(), Some(&DefaultIdStrategy::default())).unwrap();
+        assert_eq!(again.stats.schema_bytes, rep.stats.schema_bytes);
+        assert_eq!(again.stats.proof_bytes, rep.stats.proof_bytes);
+
+        let mut req = demo_request();
+        req.build_proof = false;
+        let rep = compile_from_ir(demo_ir(), req, Some(&DefaultIdStrategy::default())).unwrap();
+        assert_eq!(rep.stats.proof_bytes, 0);
+    }
+
+    #[test]
+    fn fractional_attrs_are_rejected_and_string_encoded_ones_hash() {
+        use crate::model::ir::IrValue;
+
+        // Hashing paths reject non-integer floats; producers encode them as strings.
+        let mut ir = demo_ir();
+        ir.nodes.get_mut("n2").unwrap().attrs.insert("mean".to_string(), IrValue::F64(2.5));
+        assert!(compile_from_ir(ir, demo_request(), Some(&DefaultIdStrategy::default())).is_err());
+
+        let mut ir = demo_ir();
+        ir.nodes.get_mut("n2").unwrap().attrs.insert("mean".to_string(), IrValue::String("2.5".to_string()));
+        let rep = compile_from_ir(ir, demo_request(), Some(&DefaultIdStrategy::default())).unwrap();
+        assert!(rep.stats.schema_bytes > 0);
+        crate::determinism::hashing::hash_schema_v1_hex(&rep.bundle.schema).unwrap();
+    }
+
+    struct DemoProducer;
+
+    impl IrProducer for DemoProducer {
+        fn id(&self) -> &str {
+            "builtin.demo"
+        }
+        fn name(&self) -> &str {
+            "demo"
+        }
+        fn version(&self) -> &str {
+            "0.1.0"
+        }
+        fn fingerprint(&self) -> Option<String> {
+            Some("f".repeat(64))
+        }
+        fn produce(&self, _input: &Value) -> SigniaResult<IrGraph> {
+            Ok(demo_ir())
+        }
+    }
+
+    #[test]
+    fn compile_from_input_records_executed_plugin() {
+        let rep = compile_from_input(&json!({}), &DemoProducer, demo_request(), None).unwrap();
+        let plugins = &rep.bundle.manifest.plugins;
+        assert_eq!(plugins.len(), 2);
+        assert_eq!(plugins[1].name, "demo");
+        assert_eq!(plugins[1].version, "0.1.0");
+        assert_eq!(plugins[1].id.as_deref(), Some("builtin.demo"));
+        assert_eq!(plugins[1].fingerprint.as_deref(), Some("f".repeat(64).as_str()));
+        assert_eq!(plugins[0].fingerprint, None);
+        assert_eq!(plugins[1].granted_caps, None);
+    }
+
+    struct NetworkProducer;
+
+    impl IrProducer for NetworkProducer {
+        fn id(&self) -> &str {
+            "ext.fetch"
+        }
+        fn name(&self) -> &str {
+            "fetch"
+        }
+        fn version(&self) -> &str {
+            "0.1.0"
+        }
+        fn granted_caps(&self) -> Option<Vec<String>> {
+            Some(vec!["network".to_string()])
+        }
+        fn produce(&self, _input: &Value) -> SigniaResult<IrGraph> {
+            Ok(demo_ir())
+        }
+    }
+
+    #[test]
+    fn compile_from_input_records_granted_caps() {
+        let rep = compile_from_input(&json!({}), &NetworkProducer, demo_request(), None).unwrap();
+        let p = rep.bundle.manifest.plugins.iter().find(|p| p.name == "fetch").unwrap();
+        assert_eq!(p.granted_caps, Some(vec!["network".to_string()]));
+
+        let v = serde_json::to_value(&rep.bundle.manifest).unwrap();
+        let plugins = v["plugins"].as_array().unwrap();
+        assert_eq!(plugins[1]["grantedCaps"], json!(["network"]));
+        assert!(plugins[0].get("grantedCaps").is_none());
+    }
+
+    #[test]
+    fn compile_from_input_records_plugin_selection() {
+        let input = json!({"files": [{"path": "README.md"}]});
+        let rep = compile_from_input(&input, &DemoProducer, demo_request(), None).unwrap();
+        let d = rep.diagnostics.iter().find(|d| d.code == "compile.plugin.selected").unwrap();
+        assert!(matches!(d.level, crate::pipeline::DiagnosticLevel::Info));
+        assert_eq!(d.data["plugin_id"], "builtin.demo");
+        assert_eq!(d.data["kind"], "repo");
+        assert_eq!(d.data["detected_kind"], "repo");
+        assert_eq!(d.data["detection_confidence"], "70");
+
+        let rep = compile_from_input(&json!({}), &DemoProducer, demo_request(), None).unwrap();
+        let d = &rep.diagnostics[0];
+        assert_eq!(d.code, "compile.plugin.selected");
+        assert_eq!(d.data["detected_kind"], "unknown");
+        assert_eq!(d.data["detection_confidence"], "0");
+    }
+
+    #[test]
+    fn compile_from_input_rejects_mismatched_version() {
+        let mut req = demo_request();
+        req.plugins.push(PluginSpec {
+            name: "demo".to_string(),
+            version: "9.9.9".to_string(),
+            id: None,
+            config: None,
+            fingerprint: None,
+            granted_caps: None,
+        });
+        assert!(compile_from_input(&json!({}), &DemoProducer, req, None).is_err());
+    }
+
+    #[test]
+    fn compile_from_ir_rejects_malformed_created_at() {
+        let mut req = demo_request();
+        req.created_at = "not-a-date".to_string();
+        let err = compile_from_ir(demo_ir(), req, None).unwrap_err();
+        assert!(err.to_string().contains("RFC 3339"), "{err}");
+    }
 }
@@ -1,45 +1,91 @@
 //! Pipeline execution context for SIGNIA.
 //!
 //! PipelineContext carries:
+//! - the `PipelineConfig` it was created from
 //! - deterministic clock input
 //! - string and JSON parameters
-//! - diagnostics produced by stages
+//! - plugin inputs keyed by input type, and the IR a plugin produces
+//! - diagnostics produced by stages and plugins, capped by `truncate_diagnostics`
 //! - execution-scoped metadata
 //!
+//! This is the single context type: pipeline stages, plugins and hosts (CLI,
+//! API) all use it, and `crate::pipeline` re-exports it.
+//!
 //! It is explicitly mutable and passed between stages.
-//! It must remain serializable and deterministic-friendly.
+//! It must remain serializable and deterministic-friendly: with `canonical-json`
+//! it derives `Serialize`, and every map is a `BTreeMap`, so two equal contexts
+//! serialize to identical JSON.
 
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use crate::errors::{SigniaError, SigniaResult};
+use crate::config::{LimitsConfig, NormalizationConfig};
+use crate::model::ir::IrGraph;
+use crate::pipeline::DeterministicClock;
 
+#[cfg(feature = "canonical-json")]
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "canonical-json")]
 use serde_json::Value;
 
-/// Deterministic clock input.
+/// Default for `PipelineContext::max_diagnostics`.
+pub const DEFAULT_MAX_DIAGNOSTICS: usize = 10_000;
+
+/// Code of the entry appended by `PipelineContext::truncate_diagnostics`.
+pub const DIAGNOSTICS_TRUNCATED_CODE: &str = "pipeline.diagnostics.truncated";
+
+/// Static determinism settings a `PipelineContext` is created from.
 ///
-/// Core never reads system time. Callers must inject timestamps.
-#[derive(Debug, Clone)]
-pub struct Clock {
-    pub now_iso8601: String,
-}
+/// Two contexts built from equal configs must produce equal outputs.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PipelineConfig {
+    /// Deterministic clock (caller-injected, never the wall clock).
+    pub clock: DeterministicClock,
 
-impl Default for Clock {
-    fn default() -> Self {
-        Self {
-            now_iso8601: "1970-01-01T00:00:00Z".to_string(),
-        }
-    }
+    /// Resource and complexity limits.
+    pub limits: LimitsConfig,
+
+    /// Path/text normalization settings.
+    pub normalization: NormalizationConfig,
+
+    /// Initial string parameters.
+    pub params: BTreeMap<String, String>,
 }
 
-/// Diagnostic emitted by pipeline stages.
+/// A structured diagnostic emitted by pipeline stages and plugins.
+///
+/// Diagnostics are intended for:
+/// - CLI printing
+/// - API response payloads
+/// - Console display
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct PipelineDiagnostic {
     pub level: DiagnosticLevel,
     pub code: String,
     pub message: String,
+    /// Structured details (e.g. `omitted` for a truncation notice).
+    #[cfg_attr(feature = "canonical-json", serde(default))]
+    pub data: BTreeMap<String, String>,
+}
+
+impl PipelineDiagnostic {
+    /// A diagnostic without structured data.
+    pub fn new(level: DiagnosticLevel, code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            code: code.into(),
+            message: message.into(),
+            data: BTreeMap::new(),
+        }
+    }
 }
 
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "snake_case"))]
 #[derive(Debug, Clone, Copy)]
 pub enum DiagnosticLevel {
     Info,
@@ -47,39 +93,110 @@ pub enum DiagnosticLevel {
     Error,
 }
 
+impl DiagnosticLevel {
+    /// Retention priority when diagnostics are truncated (lower is kept first).
+    fn retention_rank(self) -> u8 {
+        match self {
+            DiagnosticLevel::Error => 0,
+            DiagnosticLevel::Warning => 1,
+            DiagnosticLevel::Info => 2,
+        }
+    }
+}
+
 /// Shared pipeline execution context.
+///
+/// Params are shared behind `Arc` and copied on write, so `clone` and `fork`
+/// do not duplicate large configs.
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
 #[derive(Debug, Clone)]
 pub struct PipelineContext {
-    /// Deterministic clock.
-    pub clock: Clock,
+    /// Configuration this context was created from.
+    pub config: PipelineConfig,
 
-    /// String parameters.
-    pub params: BTreeMap<String, String>,
+    /// Deterministic clock (no system reads).
+    pub clock: DeterministicClock,
+
+    /// Caller-defined parameters. Keys should be stable and documented.
+    pub params: Arc<BTreeMap<String, String>>,
 
-    /// JSON parameters.
+    /// JSON parameters for more complex configs (plugin configs).
     #[cfg(feature = "canonical-json")]
-    pub json_params: BTreeMap<String, Value>,
+    pub json_params: Arc<BTreeMap<String, Value>>,
+
+    /// Plugin inputs keyed by input type (e.g. "repo", "openapi").
+    #[cfg(feature = "canonical-json")]
+    pub inputs: BTreeMap<String, Value>,
+
+    /// IR produced by the plugin, if any.
+    pub ir: Option<IrGraph>,
+
+    /// Execution-scoped metadata recorded by plugins (e.g. fingerprints).
+    ///
+    /// Write through `set_metadata_checked` or `set_metadata`, not directly:
+    /// - content-derived values use `set_metadata_checked`, so two plugins
+    ///   disagreeing about one key is an error instead of a silent overwrite.
+    ///   The builtin `dataset` (`datasetFingerprint`, `datasetMerkleRoot`),
+    ///   `openapi` (`openapiFingerprint`) and `workflow` (`workflowFingerprint`)
+    ///   plugins do this.
+    /// - informational values that a later stage may legitimately replace use
+    ///   `set_metadata` (last write wins).
+    pub metadata: BTreeMap<String, String>,
 
     /// Collected diagnostics.
     pub diagnostics: Vec<PipelineDiagnostic>,
+
+    /// Diagnostics kept by `truncate_diagnostics` (default `DEFAULT_MAX_DIAGNOSTICS`).
+    pub max_diagnostics: usize,
 }
 
 impl Default for PipelineContext {
     fn default() -> Self {
+        Self::new(PipelineConfig::default())
+    }
+}
+
+impl PipelineContext {
+    /// Create an empty context; clock and params are taken from `config`.
+    pub fn new(config: PipelineConfig) -> Self {
         Self {
-            clock: Clock::default(),
-            params: BTreeMap::new(),
+            clock: config.clock.clone(),
+            params: Arc::new(config.params.clone()),
+            config,
             #[cfg(feature = "canonical-json")]
-            json_params: BTreeMap::new(),
+            json_params: Arc::default(),
+            #[cfg(feature = "canonical-json")]
+            inputs: BTreeMap::new(),
+            ir: None,
+            metadata: BTreeMap::new(),
             diagnostics: Vec::new(),
+            max_diagnostics: DEFAULT_MAX_DIAGNOSTICS,
+        }
+    }
+
+    /// Context for an isolated sub-run: same config, clock, params (shared, not
+    /// copied) and inputs; no IR, metadata or diagnostics. Params set on the
+    /// fork do not leak back.
+    pub fn fork(&self) -> PipelineContext {
+        Self {
+            config: self.config.clone(),
+            clock: self.clock.clone(),
+            params: Arc::clone(&self.params),
+            #[cfg(feature = "canonical-json")]
+            json_params: Arc::clone(&self.json_params),
+            #[cfg(feature = "canonical-json")]
+            inputs: self.inputs.clone(),
+            ir: None,
+            metadata: BTreeMap::new(),
+            diagnostics: Vec::new(),
+            max_diagnostics: self.max_diagnostics,
         }
     }
-}
 
-impl PipelineContext {
     /// Set a string parameter.
     pub fn set_param(&mut self, key: impl Into<String>, value: impl Into<String>) {
-        self.params.insert(key.into(), value.into());
+        Arc::make_mut(&mut self.params).insert(key.into(), value.into());
     }
 
     /// Get a string parameter.
@@ -90,7 +207,7 @@ impl PipelineContext {
     /// Set a JSON parameter.
     #[cfg(feature = "canonical-json")]
     pub fn set_json_param(&mut self, key: impl Into<String>, value: Value) {
-        self.json_params.insert(key.into(), value);
+        Arc::make_mut(&mut self.json_params).insert(key.into(), value);
     }
 
     /// Get a JSON parameter.
@@ -99,31 +216,43 @@ impl PipelineContext {
         self.json_params.get(key)
     }
 
+    /// Record metadata, replacing any previous value (last write wins).
+    pub fn set_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.metadata.insert(key.into(), value.into());
+    }
+
+    /// Record metadata, failing if `key` already holds a different value.
+    ///
+    /// Writing the same value again is allowed, so a plugin that runs twice on
+    /// the same input stays idempotent. On error the existing value is kept.
+    pub fn set_metadata_checked(&mut self, key: impl Into<String>, value: impl Into<String>) -> SigniaResult<()> {
+        let key = key.into();
+        let value = value.into();
+        match self.metadata.get(&key) {
+            Some(existing) if *existing != value => Err(SigniaError::invariant(format!(
+                "conflicting metadata for {key}: {existing} vs {value}"
+            ))),
+            Some(_) => Ok(()),
+            None => {
+                self.metadata.insert(key, value);
+                Ok(())
+            }
+        }
+    }
+
     /// Push an info diagnostic.
     pub fn push_info(&mut self, code: impl Into<String>, message: impl Into<String>) {
-        self.diagnostics.push(PipelineDiagnostic {
-            level: DiagnosticLevel::Info,
-            code: code.into(),
-            message: message.into(),
-        });
+        self.diagnostics.push(PipelineDiagnostic::new(DiagnosticLevel::Info, code, message));
     }
 
     /// Push a warning diagnostic.
     pub fn push_warning(&mut self, code: impl Into<String>, message: impl Into<String>) {
-        self.diagnostics.push(PipelineDiagnostic {
-            level: DiagnosticLevel::Warning,
-            code: code.into(),
-            message: message.into(),
-        });
+        self.diagnostics.push(PipelineDiagnostic::new(DiagnosticLevel::Warning, code, message));
     }
 
     /// Push an error diagnostic.
     pub fn push_error(&mut self, code: impl Into<String>, message: impl Into<String>) {
-        self.diagnostics.push(PipelineDiagnostic {
-            level: DiagnosticLevel::Error,
-            code: code.into(),
-            message: message.into(),
-        });
+        self.diagnostics.push(PipelineDiagnostic::new(DiagnosticLevel::Error, code, message));
     }
 
     /// Return true if any error diagnostics exist.
@@ -132,6 +261,48 @@ impl PipelineContext {
             .iter()
             .any(|d| matches!(d.level, DiagnosticLevel::Error))
     }
+
+    /// Cap `diagnostics` at `max_diagnostics` entries.
+    ///
+    /// Errors are kept before warnings and warnings before infos; within a level
+    /// the earliest win. Kept entries stay in emission order and a single
+    /// `pipeline.diagnostics.truncated` warning recording the omitted count is
+    /// appended. Safe to call repeatedly: an earlier notice is folded into the
+    /// new one. Does nothing when under the cap.
+    pub fn truncate_diagnostics(&mut self) {
+        let mut omitted: usize = 0;
+        self.diagnostics.retain(|d| {
+            if d.code != DIAGNOSTICS_TRUNCATED_CODE {
+                return true;
+            }
+            omitted += d.data.get("omitted").and_then(|n| n.parse::<usize>().ok()).unwrap_or(0);
+            false
+        });
+
+        let total = self.diagnostics.len();
+        if total > self.max_diagnostics {
+            let mut order: Vec<usize> = (0..total).collect();
+            order.sort_by_key(|&i| (self.diagnostics[i].level.retention_rank(), i));
+            let mut keep = vec![false; total];
+            for &i in &order[..self.max_diagnostics] {
+                keep[i] = true;
+            }
+            let mut kept = keep.iter();
+            self.diagnostics.retain(|_| *kept.next().unwrap_or(&false));
+            omitted += total - self.max_diagnostics;
+        }
+        if omitted == 0 {
+            return;
+        }
+
+        let mut notice = PipelineDiagnostic::new(
+            DiagnosticLevel::Warning,
+            DIAGNOSTICS_TRUNCATED_CODE,
+            format!("diagnostics truncated ({omitted} more)"),
+        );
+        notice.data.insert("omitted".to_string(), omitted.to_string());
+        self.diagnostics.push(notice);
+    }
 }
 
 #[cfg(test)]
@@ -151,4 +322,114 @@ mod tests {
         assert_eq!(ctx.diagnostics.len(), 3);
         assert!(ctx.has_errors());
     }
+
+    #[test]
+    fn metadata_checked_writes_reject_conflicts() {
+        let mut ctx = PipelineContext::default();
+        ctx.set_metadata_checked("fingerprint", "ab").unwrap();
+        ctx.set_metadata_checked("fingerprint", "ab").unwrap();
+
+        let err = ctx.set_metadata_checked("fingerprint", "cd").unwrap_err();
+        assert!(err.to_string().contains("conflicting metadata for fingerprint"), "{err}");
+        assert_eq!(ctx.metadata["fingerprint"], "ab");
+
+        ctx.set_metadata("fingerprint", "cd");
+        assert_eq!(ctx.metadata["fingerprint"], "cd");
+    }
+
+    #[test]
+    fn default_configs_compare_equal() {
+        assert_eq!(PipelineConfig::default(), PipelineConfig::default());
+        assert_eq!(PipelineContext::default().config, PipelineContext::new(PipelineConfig::default()).config);
+
+        let mut tighter = PipelineConfig::default();
+        tighter.limits.max_nodes = 10;
+        assert_ne!(tighter, PipelineConfig::default());
+    }
+
+    #[test]
+    fn new_applies_config() {
+        let mut cfg = PipelineConfig::default();
+        cfg.clock.now_iso8601 = "2024-01-01T00:00:00Z".to_string();
+        cfg.params.insert("a".to_string(), "b".to_string());
+
+        let ctx = PipelineContext::new(cfg.clone());
+        assert_eq!(ctx.config, cfg);
+        assert_eq!(ctx.clock.now_iso8601, "2024-01-01T00:00:00Z");
+        assert_eq!(ctx.get_param("a"), Some("b"));
+        assert!(ctx.ir.is_none());
+        assert!(ctx.metadata.is_empty());
+
+        let d = PipelineContext::default();
+        assert_eq!(d.config, PipelineConfig::default());
+        assert_eq!(d.clock, DeterministicClock::default());
+    }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn inputs_ir_and_metadata_are_carried() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        assert!(ctx.inputs.is_empty());
+        ctx.inputs.insert("repo".to_string(), serde_json::json!({"name": "r"}));
+        ctx.ir = Some(IrGraph::new());
+        ctx.metadata.insert("fingerprint".to_string(), "ab".to_string());
+
+        let copy = ctx.clone();
+        assert_eq!(copy.inputs["repo"]["name"], "r");
+        assert!(copy.ir.is_some());
+        assert_eq!(copy.metadata.get("fingerprint").map(String::as_str), Some("ab"));
+
+        // A fork re-runs the same inputs from scratch.
+        let fork = ctx.fork();
+        assert_eq!(fork.inputs, ctx.inputs);
+        assert_eq!(fork.config, ctx.config);
+        assert!(fork.ir.is_none());
+        assert!(fork.metadata.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn context_with_inputs_and_ir_serializes_deterministically() {
+        use crate::model::ir::{IrEdge, IrNode, IrValue};
+
+        let build = |input_order: &[&str]| {
+            let mut ctx = PipelineContext::new(PipelineConfig::default());
+            for k in input_order {
+                ctx.inputs.insert(k.to_string(), serde_json::json!({"k": k}));
+            }
+            let mut g = IrGraph::new();
+            let mut a = IrNode::new("file", "a.rs");
+            a.attrs.insert("size".to_string(), IrValue::I64(3));
+            let a = g.add_node(a).unwrap();
+            let b = g.add_node(IrNode::new("file", "b.rs")).unwrap();
+            g.add_edge(IrEdge::new(a, b, "imports")).unwrap();
+            ctx.ir = Some(g);
+            ctx.metadata.insert("fingerprint".to_string(), "ab".to_string());
+            ctx.push_info("i", "info");
+            ctx
+        };
+
+        let v1 = serde_json::to_value(build(&["repo", "dataset"])).unwrap();
+        let v2 = serde_json::to_value(build(&["dataset", "repo"])).unwrap();
+        assert_eq!(v1, v2);
+        assert_eq!(
+            serde_json::to_string(&build(&["repo", "dataset"])).unwrap(),
+            serde_json::to_string(&build(&["dataset", "repo"])).unwrap()
+        );
+
+        assert_eq!(v1["config"]["clock"]["nowIso8601"], "1970-01-01T00:00:00Z");
+        assert_eq!(v1["config"]["normalization"]["encoding"], "utf-8");
+        assert_eq!(v1["inputs"]["repo"]["k"], "repo");
+        assert_eq!(v1["metadata"]["fingerprint"], "ab");
+        assert_eq!(v1["diagnostics"][0]["level"], "info");
+
+        assert_eq!(v1["ir"]["irVersion"], crate::model::ir::IR_VERSION);
+        let nodes = v1["ir"]["nodes"].as_object().unwrap();
+        assert_eq!(nodes.len(), 2);
+        let file_a = nodes.values().find(|n| n["name"] == "a.rs").unwrap();
+        assert_eq!(file_a["nodeType"], "file");
+        assert_eq!(file_a["attrs"]["size"], 3);
+        assert_eq!(v1["ir"]["edges"].as_object().unwrap().len(), 1);
+        assert!(v1["ir"].get("nodeKeys").is_none());
+    }
 }
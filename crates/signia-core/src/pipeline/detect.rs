@@ -0,0 +1,275 @@
+//! Input kind detection.
+//!
+//! Routes a JSON payload to the plugin that should compile it when the caller did
+//! not say which kind it is. Detection is structural, deterministic and
+//! exhaustive: every kind is checked, and an input that matches no kind or more
+//! than one kind is `Unknown`. The result carries a confidence and the reasons
+//! behind it, so a wrong guess can be explained. Plugins still perform strict
+//! validation.
+//!
+//! Heuristics (top-level keys):
+//! - repo: `files` array (and no dataset markers), or a `repo` object with
+//!   `owner` and `name` strings
+//! - dataset: `content`, `columns` or a `records` array; a `dataset` object with a
+//!   `name` string; or `files` together with a `version` string or a file entry
+//!   carrying `format`/`columns`
+//! - workflow: `nodes` array; `edges`, if present, is an array whose every edge
+//!   `kind` is data/control/event
+//! - openapi: `openapi` string or `paths` object
+
+#[cfg(feature = "canonical-json")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "canonical-json")]
+use serde_json::Value;
+
+/// Detected input kind.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectedKind {
+    Repo,
+    Dataset,
+    Workflow,
+    OpenApi,
+    Unknown,
+}
+
+impl DetectedKind {
+    /// Kind key as used by `CompileRequest::kind` ("repo", "openapi", ...).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DetectedKind::Repo => "repo",
+            DetectedKind::Dataset => "dataset",
+            DetectedKind::Workflow => "workflow",
+            DetectedKind::OpenApi => "openapi",
+            DetectedKind::Unknown => "unknown",
+        }
+    }
+}
+
+/// Outcome of kind detection, with the evidence behind it.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectionResult {
+    pub kind: DetectedKind,
+    /// 0..=100; 0 for `Unknown`.
+    pub confidence: u8,
+    /// Which fields drove the decision (or why nothing matched), in check order.
+    pub reasons: Vec<String>,
+}
+
+/// Detect the kind of a JSON input payload.
+#[cfg(feature = "canonical-json")]
+pub fn detect_input_kind(value: &Value) -> DetectionResult {
+    let Some(obj) = value.as_object() else {
+        return unknown(vec!["input is not a JSON object".to_string()]);
+    };
+
+    let mut candidates: Vec<(DetectedKind, u8, Vec<String>)> = Vec::new();
+
+    let files = obj.get("files").and_then(Value::as_array);
+
+    let mut dataset_reasons: Vec<String> = ["content", "columns"]
+        .into_iter()
+        .filter(|k| obj.contains_key(*k))
+        .map(|k| format!("has `{k}`"))
+        .collect();
+    if obj.get("records").is_some_and(Value::is_array) {
+        dataset_reasons.push("`records` is an array".to_string());
+    }
+    if obj.get("dataset").and_then(|d| d.get("name")).is_some_and(Value::is_string) {
+        dataset_reasons.push("`dataset.name` is a string".to_string());
+    }
+    if let Some(files) = files {
+        if obj.get("version").is_some_and(Value::is_string) {
+            dataset_reasons.push("`files` with a `version` string".to_string());
+        }
+        if files.iter().any(|f| f.get("format").is_some() || f.get("columns").is_some()) {
+            dataset_reasons.push("a file entry has `format` or `columns`".to_string());
+        }
+    }
+
+    let mut repo_reasons = Vec::new();
+    if dataset_reasons.is_empty() && files.is_some() {
+        repo_reasons.push("`files` is an array".to_string());
+    }
+    if let Some(repo) = obj.get("repo") {
+        if repo.get("owner").is_some_and(Value::is_string) && repo.get("name").is_some_and(Value::is_string) {
+            repo_reasons.push("`repo.owner` and `repo.name` are strings".to_string());
+        }
+    }
+    if !repo_reasons.is_empty() {
+        let confidence = if repo_reasons.len() >= 2 { 85 } else { 70 };
+        candidates.push((DetectedKind::Repo, confidence, repo_reasons));
+    }
+
+    if !dataset_reasons.is_empty() {
+        let confidence = if dataset_reasons.len() >= 2 { 90 } else { 80 };
+        candidates.push((DetectedKind::Dataset, confidence, dataset_reasons));
+    }
+
+    if obj.get("nodes").is_some_and(Value::is_array) {
+        match obj.get("edges") {
+            None => candidates.push((
+                DetectedKind::Workflow,
+                75,
+                vec!["`nodes` is an array".to_string(), "no `edges`".to_string()],
+            )),
+            Some(Value::Array(edges)) => {
+                let kinds_ok = edges.iter().all(|e| {
+                    matches!(
+                        e.get("kind").and_then(Value::as_str),
+                        Some("data" | "control" | "event")
+                    )
+                });
+                if kinds_ok {
+                    candidates.push((
+                        DetectedKind::Workflow,
+                        90,
+                        vec![
+                            "`nodes` and `edges` are arrays".to_string(),
+                            "every edge `kind` is data, control or event".to_string(),
+                        ],
+                    ));
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    let has_openapi = obj.get("openapi").is_some_and(Value::is_string);
+    let has_paths = obj.get("paths").is_some_and(Value::is_object);
+    if has_openapi || has_paths {
+        let mut reasons = Vec::new();
+        if has_openapi {
+            reasons.push("`openapi` is a string".to_string());
+        }
+        if has_paths {
+            reasons.push("`paths` is an object".to_string());
+        }
+        let confidence = if has_openapi && has_paths { 95 } else { 75 };
+        candidates.push((DetectedKind::OpenApi, confidence, reasons));
+    }
+
+    match candidates.len() {
+        0 => unknown(vec!["no known input shape matched".to_string()]),
+        1 => {
+            let (kind, confidence, reasons) = candidates.remove(0);
+            DetectionResult { kind, confidence, reasons }
+        }
+        _ => {
+            let names: Vec<String> = candidates.iter().map(|(k, _, _)| format!("{k:?}")).collect();
+            let mut reasons = vec![format!("ambiguous: matched {}", names.join(", "))];
+            for (kind, _, rs) in candidates {
+                reasons.extend(rs.into_iter().map(|r| format!("{kind:?}: {r}")));
+            }
+            unknown(reasons)
+        }
+    }
+}
+
+#[cfg(feature = "canonical-json")]
+fn unknown(reasons: Vec<String>) -> DetectionResult {
+    DetectionResult {
+        kind: DetectedKind::Unknown,
+        confidence: 0,
+        reasons,
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "canonical-json")]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn detects_each_kind() {
+        let cases = [
+            (json!({"name": "r", "files": [{"path": "README.md"}]}), DetectedKind::Repo),
+            (json!({"name": "r", "files": []}), DetectedKind::Repo),
+            (json!({"name": "d", "content": "a,b\n1,2\n"}), DetectedKind::Dataset),
+            (json!({"columns": ["a"], "files": [{"path": "x.csv"}]}), DetectedKind::Dataset),
+            (
+                json!({"name": "d", "version": "v1", "files": [{"path": "train.jsonl", "size": 10}]}),
+                DetectedKind::Dataset,
+            ),
+            (json!({"files": [{"path": "x.csv", "format": "csv"}]}), DetectedKind::Dataset),
+            (json!({"records": [{"a": 1}, {"a": 2}]}), DetectedKind::Dataset),
+            (json!({"dataset": {"name": "x"}, "files": [{"path": "a.csv"}]}), DetectedKind::Dataset),
+            (json!({"dataset": {"name": "x"}}), DetectedKind::Dataset),
+            (json!({"repo": {"owner": "o", "name": "r"}}), DetectedKind::Repo),
+            (json!({"repo": {"owner": "o", "name": "r"}, "files": [{"path": "a.rs"}]}), DetectedKind::Repo),
+            (json!({"name": "w", "nodes": [{"id": "a"}]}), DetectedKind::Workflow),
+            (
+                json!({"name": "w", "nodes": [{"id": "a"}, {"id": "b"}],
+                       "edges": [{"from": "a", "to": "b", "kind": "data"}]}),
+                DetectedKind::Workflow,
+            ),
+            (json!({"openapi": "3.0.0", "paths": {}}), DetectedKind::OpenApi),
+            (json!({"paths": {"/pets": {}}}), DetectedKind::OpenApi),
+        ];
+        for (input, kind) in cases {
+            assert_eq!(detect_input_kind(&input).kind, kind, "{input}");
+        }
+    }
+
+    #[test]
+    fn ambiguous_or_unmatched_inputs_are_unknown() {
+        let cases = [
+            json!(null),
+            json!([]),
+            json!({}),
+            json!({"name": "x"}),
+            // repo + openapi
+            json!({"files": [], "openapi": "3.0.0"}),
+            // dataset + workflow
+            json!({"columns": [], "nodes": [], "edges": []}),
+            // edge kind outside data/control/event
+            json!({"nodes": [], "edges": [{"kind": "bogus"}]}),
+            // `edges` present but not an array
+            json!({"nodes": [], "edges": {}}),
+            // records + workflow
+            json!({"records": [], "nodes": []}),
+            // `repo` without an owner, `dataset` without a name
+            json!({"repo": {"name": "r"}}),
+            json!({"dataset": {"rows": 3}}),
+        ];
+        for input in cases {
+            let r = detect_input_kind(&input);
+            assert_eq!(r.kind, DetectedKind::Unknown, "{input}");
+            assert_eq!(r.confidence, 0);
+            assert!(!r.reasons.is_empty());
+        }
+
+        let r = detect_input_kind(&json!({"files": [], "openapi": "3.0.0"}));
+        assert!(r.reasons[0].starts_with("ambiguous: matched Repo, OpenApi"), "{:?}", r.reasons);
+    }
+
+    #[test]
+    fn reasons_explain_repo_vs_dataset() {
+        let repo = detect_input_kind(&json!({"name": "r", "files": [{"path": "a.rs"}]}));
+        assert_eq!(repo.kind, DetectedKind::Repo);
+        assert_eq!(repo.reasons, vec!["`files` is an array".to_string()]);
+        assert!(repo.confidence > 0);
+
+        // `files` alone would be a repo; `columns` makes it a dataset.
+        let dataset = detect_input_kind(&json!({"files": [{"path": "x.csv"}], "columns": ["a"]}));
+        assert_eq!(dataset.kind, DetectedKind::Dataset);
+        assert_eq!(dataset.reasons, vec!["has `columns`".to_string()]);
+    }
+
+    #[test]
+    fn descriptor_objects_route_inputs() {
+        // `dataset.name` marks a dataset even when `files` alone would say repo.
+        let dataset = detect_input_kind(&json!({"dataset": {"name": "x"}, "files": [{"path": "a.csv"}]}));
+        assert_eq!(dataset.kind, DetectedKind::Dataset);
+        assert_eq!(dataset.reasons, vec!["`dataset.name` is a string".to_string()]);
+
+        let repo = detect_input_kind(&json!({"repo": {"owner": "o", "name": "r"}, "files": []}));
+        assert_eq!(repo.kind, DetectedKind::Repo);
+        assert_eq!(repo.reasons.len(), 2);
+        assert_eq!(repo.confidence, 85);
+    }
+}
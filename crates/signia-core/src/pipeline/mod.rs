@@ -15,21 +15,29 @@
 //!
 //! This module defines:
 //! - `Pipeline` and `Stage` traits
-//! - `PipelineContext` (deterministic config, tracing, diagnostics)
+//! - `PipelineContext` (re-exported from `context`: config, inputs, IR, diagnostics)
 //! - `PipelineReport` (structured outputs + warnings)
 //!
 //! The core crate does not do network or filesystem I/O. Higher-level crates
 //! perform I/O and pass bytes/structures into the pipeline.
 
-use std::collections::BTreeMap;
-
 use crate::errors::{SigniaError, SigniaResult};
 
+#[cfg(feature = "canonical-json")]
+use serde::{Deserialize, Serialize};
+
 #[cfg(feature = "canonical-json")]
 use serde_json::Value;
 
+pub mod context;
+pub mod detect;
 pub mod stages;
 
+pub use context::{
+    DiagnosticLevel, PipelineConfig, PipelineContext, PipelineDiagnostic, DEFAULT_MAX_DIAGNOSTICS,
+    DIAGNOSTICS_TRUNCATED_CODE,
+};
+
 /// A stable identifier for a pipeline stage.
 ///
 /// Use dot-delimited namespaces:
@@ -40,32 +48,13 @@ pub mod stages;
 /// - `proof.merkle`
 pub type StageId = String;
 
-/// A structured diagnostic emitted by pipeline stages.
-///
-/// Diagnostics are intended for:
-/// - CLI printing
-/// - API response payloads
-/// - Console display
-#[derive(Debug, Clone)]
-pub struct PipelineDiagnostic {
-    pub level: DiagnosticLevel,
-    pub code: String,
-    pub message: String,
-    pub data: BTreeMap<String, String>,
-}
-
-#[derive(Debug, Clone, Copy)]
-pub enum DiagnosticLevel {
-    Info,
-    Warning,
-    Error,
-}
-
 /// A deterministic clock abstraction.
 ///
 /// Core does not read system time. If a stage needs a timestamp,
 /// the higher-level caller must inject it via the context.
-#[derive(Debug, Clone)]
+#[cfg_attr(feature = "canonical-json", derive(Serialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DeterministicClock {
     /// An ISO-8601 timestamp chosen by the caller.
     pub now_iso8601: String,
@@ -79,88 +68,6 @@ impl Default for DeterministicClock {
     }
 }
 
-/// Pipeline context shared by all stages.
-///
-/// This is the main mechanism for passing:
-/// - deterministic configuration
-/// - stage parameters
-/// - compiler hints
-/// - diagnostics collection
-#[derive(Debug, Clone)]
-pub struct PipelineContext {
-    /// Deterministic clock values (no system reads).
-    pub clock: DeterministicClock,
-
-    /// Caller-defined parameters. Keys should be stable and documented.
-    pub params: BTreeMap<String, String>,
-
-    /// Optional JSON params for more complex configs (plugin configs).
-    #[cfg(feature = "canonical-json")]
-    pub json_params: BTreeMap<String, Value>,
-
-    /// Collected diagnostics.
-    pub diagnostics: Vec<PipelineDiagnostic>,
-}
-
-impl Default for PipelineContext {
-    fn default() -> Self {
-        Self {
-            clock: DeterministicClock::default(),
-            params: BTreeMap::new(),
-            #[cfg(feature = "canonical-json")]
-            json_params: BTreeMap::new(),
-            diagnostics: Vec::new(),
-        }
-    }
-}
-
-impl PipelineContext {
-    pub fn push_info(&mut self, code: impl Into<String>, message: impl Into<String>) {
-        self.diagnostics.push(PipelineDiagnostic {
-            level: DiagnosticLevel::Info,
-            code: code.into(),
-            message: message.into(),
-            data: BTreeMap::new(),
-        });
-    }
-
-    pub fn push_warning(&mut self, code: impl Into<String>, message: impl Into<String>) {
-        self.diagnostics.push(PipelineDiagnostic {
-            level: DiagnosticLevel::Warning,
-            code: code.into(),
-            message: message.into(),
-            data: BTreeMap::new(),
-        });
-    }
-
-    pub fn push_error(&mut self, code: impl Into<String>, message: impl Into<String>) {
-        self.diagnostics.push(PipelineDiagnostic {
-            level: DiagnosticLevel::Error,
-            code: code.into(),
-            message: message.into(),
-            data: BTreeMap::new(),
-        });
-    }
-
-    pub fn set_param(&mut self, k: impl Into<String>, v: impl Into<String>) {
-        self.params.insert(k.into(), v.into());
-    }
-
-    pub fn get_param(&self, k: &str) -> Option<&str> {
-        self.params.get(k).map(|s| s.as_str())
-    }
-
-    #[cfg(feature = "canonical-json")]
-    pub fn set_json_param(&mut self, k: impl Into<String>, v: Value) {
-        self.json_params.insert(k.into(), v);
-    }
-
-    #[cfg(feature = "canonical-json")]
-    pub fn get_json_param(&self, k: &str) -> Option<&Value> {
-        self.json_params.get(k)
-    }
-}
-
 /// A stage input/output carrier.
 ///
 /// Stages may operate on different data shapes. To keep the pipeline generic,
@@ -193,6 +100,39 @@ pub enum PipelineData {
     ProofV1(crate::model::v1::ProofV1),
 }
 
+/// The variant of a `PipelineData`, used for static stage contracts.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "canonical-json", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineDataKind {
+    None,
+    Json,
+    Bytes,
+    Ir,
+    SchemaV1,
+    ManifestV1,
+    ProofV1,
+}
+
+impl PipelineData {
+    pub fn kind(&self) -> PipelineDataKind {
+        match self {
+            PipelineData::None => PipelineDataKind::None,
+            #[cfg(feature = "canonical-json")]
+            PipelineData::Json(_) => PipelineDataKind::Json,
+            PipelineData::Bytes(_) => PipelineDataKind::Bytes,
+            #[cfg(feature = "canonical-json")]
+            PipelineData::Ir(_) => PipelineDataKind::Ir,
+            #[cfg(feature = "canonical-json")]
+            PipelineData::SchemaV1(_) => PipelineDataKind::SchemaV1,
+            #[cfg(feature = "canonical-json")]
+            PipelineData::ManifestV1(_) => PipelineDataKind::ManifestV1,
+            #[cfg(feature = "canonical-json")]
+            PipelineData::ProofV1(_) => PipelineDataKind::ProofV1,
+        }
+    }
+}
+
 /// A pipeline stage.
 ///
 /// Stages should be deterministic: do not read system time, env, random, network.
@@ -200,6 +140,30 @@ pub enum PipelineData {
 pub trait Stage {
     fn id(&self) -> &str;
     fn run(&self, ctx: &mut PipelineContext, input: PipelineData) -> SigniaResult<PipelineData>;
+
+    /// The only input variant this stage accepts, if it declares one.
+    ///
+    /// `None` means "not declared": the stage is not checked by `Pipeline::validate`.
+    fn input_kind(&self) -> Option<PipelineDataKind> {
+        None
+    }
+
+    /// The output variant this stage always produces, if it declares one.
+    fn output_kind(&self) -> Option<PipelineDataKind> {
+        None
+    }
+}
+
+/// Static description of one configured stage, as returned by `Pipeline::describe`.
+///
+/// Input/output are the stage's declared contract (`Stage::input_kind` /
+/// `Stage::output_kind`), `None` when undeclared.
+#[cfg_attr(feature = "canonical-json", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageDescriptor {
+    pub id: String,
+    pub input: Option<PipelineDataKind>,
+    pub output: Option<PipelineDataKind>,
 }
 
 /// A pipeline is an ordered list of stages.
@@ -222,11 +186,71 @@ impl Pipeline {
         self.stages.len()
     }
 
+    /// Describe the configured stages in execution order, without running them.
+    pub fn describe(&self) -> Vec<StageDescriptor> {
+        self.stages
+            .iter()
+            .map(|st| StageDescriptor {
+                id: st.id().to_string(),
+                input: st.input_kind(),
+                output: st.output_kind(),
+            })
+            .collect()
+    }
+
+    /// Check that adjacent stages have compatible declared contracts.
+    ///
+    /// A pair is only checked when the earlier stage declares an output kind and the
+    /// later one declares an input kind. `run`/`run_until` call this before running
+    /// any stage.
+    pub fn validate(&self) -> SigniaResult<()> {
+        for pair in self.stages.windows(2) {
+            let (prev, next) = (&pair[0], &pair[1]);
+            if let (Some(out), Some(inp)) = (prev.output_kind(), next.input_kind()) {
+                if out != inp {
+                    return Err(SigniaError::invalid_argument(format!(
+                        "stage {} outputs {out:?} but the next stage {} expects {inp:?}",
+                        prev.id(),
+                        next.id()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Run the pipeline and return a structured report.
-    pub fn run(&self, mut ctx: PipelineContext, input: PipelineData) -> SigniaResult<PipelineReport> {
+    pub fn run(&self, ctx: PipelineContext, input: PipelineData) -> SigniaResult<PipelineReport> {
+        self.run_stages(ctx, input, self.stages.len())
+    }
+
+    /// Run stages up to and including `stage_id`, returning that stage's output.
+    ///
+    /// Intended for debugging: later stages are not run. Fails before running
+    /// anything if no stage has this id (the first match wins if ids repeat).
+    pub fn run_until(&self, ctx: PipelineContext, input: PipelineData, stage_id: &str) -> SigniaResult<PipelineReport> {
+        let pos = self
+            .stages
+            .iter()
+            .position(|st| st.id() == stage_id)
+            .ok_or_else(|| SigniaError::invalid_argument(format!("pipeline has no stage with id {stage_id:?}")))?;
+        self.run_stages(ctx, input, pos + 1)
+    }
+
+    fn run_stages(&self, mut ctx: PipelineContext, input: PipelineData, count: usize) -> SigniaResult<PipelineReport> {
+        self.validate()?;
+        if let Some(expected) = self.stages.first().and_then(|st| st.input_kind()) {
+            if input.kind() != expected {
+                return Err(SigniaError::invalid_argument(format!(
+                    "pipeline input is {:?} but the first stage {} expects {expected:?}",
+                    input.kind(),
+                    self.stages[0].id()
+                )));
+            }
+        }
         let mut data = input;
 
-        for st in &self.stages {
+        for st in &self.stages[..count] {
             ctx.push_info(
                 "pipeline.stage.start",
                 format!("starting stage {}", st.id()),
@@ -240,6 +264,7 @@ impl Pipeline {
             );
         }
 
+        ctx.truncate_diagnostics();
         Ok(PipelineReport {
             output: data,
             diagnostics: ctx.diagnostics,
@@ -284,6 +309,8 @@ impl PipelineReport {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
 
     struct PassThroughStage;
@@ -320,6 +347,163 @@ mod tests {
         assert!(!report.has_errors());
     }
 
+    #[test]
+    fn fork_shares_params_and_isolates_diagnostics() {
+        let mut ctx = PipelineContext::default();
+        ctx.clock.now_iso8601 = "2024-01-01T00:00:00Z".to_string();
+        ctx.set_param("schema.kind", "repo");
+        ctx.push_warning("parent.warn", "parent only");
+
+        let mut fork = ctx.fork();
+        assert!(Arc::ptr_eq(&fork.params, &ctx.params));
+        assert_eq!(fork.get_param("schema.kind"), Some("repo"));
+        assert_eq!(fork.clock.now_iso8601, ctx.clock.now_iso8601);
+        assert!(fork.diagnostics.is_empty());
+
+        fork.push_error("fork.error", "fork only");
+        fork.set_param("extra", "1");
+        assert_eq!(ctx.diagnostics.len(), 1);
+        assert_eq!(ctx.get_param("extra"), None);
+        assert!(!Arc::ptr_eq(&fork.params, &ctx.params));
+
+        // A fork runs exactly like the parent would.
+        let mut p = Pipeline::new();
+        p.push_stage(PassThroughStage);
+        let a = p.run(ctx.fork(), PipelineData::Bytes(vec![7])).unwrap();
+        let b = p.run(ctx.fork(), PipelineData::Bytes(vec![7])).unwrap();
+        assert_eq!(a.diagnostics.len(), b.diagnostics.len());
+        assert!(a.diagnostics.iter().all(|d| !d.code.starts_with("parent.")));
+    }
+
+    struct EmitBytesStage;
+    impl Stage for EmitBytesStage {
+        fn id(&self) -> &str {
+            "test.emit"
+        }
+        fn run(&self, _ctx: &mut PipelineContext, _input: PipelineData) -> SigniaResult<PipelineData> {
+            Ok(PipelineData::Bytes(vec![9]))
+        }
+    }
+
+    struct DropStage;
+    impl Stage for DropStage {
+        fn id(&self) -> &str {
+            "test.drop"
+        }
+        fn run(&self, _ctx: &mut PipelineContext, _input: PipelineData) -> SigniaResult<PipelineData> {
+            Ok(PipelineData::None)
+        }
+    }
+
+    #[test]
+    fn run_until_stops_after_named_stage() {
+        let mut p = Pipeline::new();
+        p.push_stage(PassThroughStage);
+        p.push_stage(EmitBytesStage);
+        p.push_stage(DropStage);
+        p.push_stage(ErrorStage);
+        assert!(p.run(PipelineContext::default(), PipelineData::None).is_err());
+
+        let report = p.run_until(PipelineContext::default(), PipelineData::None, "test.emit").unwrap();
+        match report.output {
+            PipelineData::Bytes(b) => assert_eq!(b, vec![9]),
+            other => panic!("expected Bytes, got {other:?}"),
+        }
+        let ends: Vec<_> = report
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == "pipeline.stage.end")
+            .map(|d| d.message.as_str())
+            .collect();
+        assert_eq!(ends, ["completed stage test.pass", "completed stage test.emit"]);
+
+        let report = p.run_until(PipelineContext::default(), PipelineData::None, "test.drop").unwrap();
+        assert!(matches!(report.output, PipelineData::None));
+
+        let err = p.run_until(PipelineContext::default(), PipelineData::None, "test.missing").unwrap_err();
+        assert!(err.to_string().contains("test.missing"), "{err}");
+    }
+
+    #[test]
+    fn describe_lists_stage_ids_in_order() {
+        let mut p = Pipeline::new();
+        assert!(p.describe().is_empty());
+
+        p.push_stage(EmitBytesStage);
+        p.push_stage(PassThroughStage);
+        p.push_stage(DropStage);
+        let ids: Vec<_> = p.describe().into_iter().map(|d| d.id).collect();
+        assert_eq!(ids, ["test.emit", "test.pass", "test.drop"]);
+    }
+
+    static BYTES_ONLY_RUNS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    /// Declares Bytes -> Bytes and counts how often it actually ran.
+    struct BytesOnlyStage;
+    impl Stage for BytesOnlyStage {
+        fn id(&self) -> &str {
+            "test.bytes_only"
+        }
+        fn run(&self, _ctx: &mut PipelineContext, input: PipelineData) -> SigniaResult<PipelineData> {
+            BYTES_ONLY_RUNS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(input)
+        }
+        fn input_kind(&self) -> Option<PipelineDataKind> {
+            Some(PipelineDataKind::Bytes)
+        }
+        fn output_kind(&self) -> Option<PipelineDataKind> {
+            Some(PipelineDataKind::Bytes)
+        }
+    }
+
+    /// Declares output None.
+    struct DeclaredDropStage;
+    impl Stage for DeclaredDropStage {
+        fn id(&self) -> &str {
+            "test.declared_drop"
+        }
+        fn run(&self, _ctx: &mut PipelineContext, _input: PipelineData) -> SigniaResult<PipelineData> {
+            Ok(PipelineData::None)
+        }
+        fn output_kind(&self) -> Option<PipelineDataKind> {
+            Some(PipelineDataKind::None)
+        }
+    }
+
+    #[test]
+    fn validate_rejects_incompatible_stages_without_running() {
+        let runs = || BYTES_ONLY_RUNS.load(std::sync::atomic::Ordering::SeqCst);
+
+        // Undeclared stages are compatible with anything.
+        let mut ok = Pipeline::new();
+        ok.push_stage(BytesOnlyStage);
+        ok.push_stage(PassThroughStage);
+        ok.push_stage(BytesOnlyStage);
+        ok.validate().unwrap();
+
+        let mut bad = Pipeline::new();
+        bad.push_stage(BytesOnlyStage);
+        bad.push_stage(DeclaredDropStage);
+        bad.push_stage(BytesOnlyStage);
+        let err = bad.validate().unwrap_err();
+        assert!(err.to_string().contains("test.declared_drop outputs None"), "{err}");
+
+        assert!(bad.run(PipelineContext::default(), PipelineData::Bytes(vec![1])).is_err());
+        assert_eq!(runs(), 0);
+
+        // The first stage's declared input is checked against the pipeline input.
+        let err = ok.run(PipelineContext::default(), PipelineData::None).unwrap_err();
+        assert!(err.to_string().contains("pipeline input is None"), "{err}");
+        assert_eq!(runs(), 0);
+
+        ok.run(PipelineContext::default(), PipelineData::Bytes(vec![1])).unwrap();
+        assert_eq!(runs(), 2);
+
+        let d = bad.describe();
+        assert_eq!((d[0].input, d[0].output), (Some(PipelineDataKind::Bytes), Some(PipelineDataKind::Bytes)));
+        assert_eq!((d[1].input, d[1].output), (None, Some(PipelineDataKind::None)));
+    }
+
     #[test]
     fn pipeline_propagates_error() {
         let mut p = Pipeline::new();
@@ -328,4 +512,53 @@ mod tests {
         let r = p.run(PipelineContext::default(), PipelineData::None);
         assert!(r.is_err());
     }
+
+    #[test]
+    fn truncation_keeps_errors_and_reports_omitted_count() {
+        let mut ctx = PipelineContext { max_diagnostics: 3, ..PipelineContext::default() };
+        for i in 0..5 {
+            ctx.push_info("i", format!("info {i}"));
+        }
+        ctx.push_error("e", "error 0");
+        ctx.push_warning("w", "warn 0");
+        ctx.push_error("e", "error 1");
+        let before = ctx.clone();
+
+        ctx.truncate_diagnostics();
+        let messages: Vec<&str> = ctx.diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages, ["error 0", "warn 0", "error 1", "diagnostics truncated (5 more)"]);
+        let last = ctx.diagnostics.last().unwrap();
+        assert_eq!(last.code, DIAGNOSTICS_TRUNCATED_CODE);
+        assert_eq!(last.data["omitted"], "5");
+
+        let mut again = before;
+        again.truncate_diagnostics();
+        let again: Vec<&str> = again.diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(again, messages);
+
+        // Truncating again folds the earlier notice instead of stacking one.
+        ctx.push_warning("w", "warn 1");
+        ctx.truncate_diagnostics();
+        let messages: Vec<&str> = ctx.diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages, ["error 0", "warn 0", "error 1", "diagnostics truncated (6 more)"]);
+
+        // Under the cap nothing changes.
+        let mut small = PipelineContext::default();
+        small.push_info("i", "only");
+        small.truncate_diagnostics();
+        assert_eq!(small.diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn pipeline_report_applies_diagnostics_cap() {
+        let mut p = Pipeline::new();
+        p.push_stage(PassThroughStage);
+        p.push_stage(PassThroughStage);
+
+        // Two stages emit four start/end infos.
+        let ctx = PipelineContext { max_diagnostics: 2, ..PipelineContext::default() };
+        let report = p.run(ctx, PipelineData::None).unwrap();
+        assert_eq!(report.diagnostics.len(), 3);
+        assert_eq!(report.diagnostics[2].message, "diagnostics truncated (2 more)");
+    }
 }
@@ -7,8 +7,9 @@
 //!
 //! This module provides:
 //! - strict JSON parsing with size limits
+//! - streaming NDJSON record parsing with per-record limits
 //! - format detection (schema/manifest/proof)
-//! - version dispatch (currently v1)
+//! - version dispatch (schemas: v1 and v2; manifests and proofs: v1)
 //! - helpful error messages for API/CLI consumers
 //!
 //! Determinism note:
@@ -20,6 +21,15 @@ use crate::errors::{SigniaError, SigniaResult};
 #[cfg(feature = "canonical-json")]
 use crate::model::v1::{ManifestV1, ProofV1, SchemaV1};
 
+#[cfg(feature = "canonical-json")]
+use crate::model::v2::SchemaV2;
+
+#[cfg(feature = "canonical-json")]
+use crate::model::AnySchema;
+
+#[cfg(feature = "canonical-json")]
+use crate::version::SchemaVersion;
+
 #[cfg(feature = "canonical-json")]
 use serde_json::Value;
 
@@ -35,21 +45,151 @@ pub enum ArtifactKind {
     Unknown,
 }
 
+/// JSON parsing options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonParseOptions {
+    /// Hard size limit in bytes.
+    pub max_bytes: usize,
+
+    /// If true, reject objects that contain the same key more than once.
+    ///
+    /// `serde_json` silently keeps the last value for duplicate keys, and
+    /// canonicalization would then hide the ambiguity. Strict mode surfaces it.
+    pub reject_duplicate_keys: bool,
+}
+
+impl Default for JsonParseOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_JSON_BYTES,
+            reject_duplicate_keys: false,
+        }
+    }
+}
+
+impl JsonParseOptions {
+    /// Strict options: duplicate keys are rejected.
+    pub fn strict(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            reject_duplicate_keys: true,
+        }
+    }
+}
+
 /// Parse JSON bytes into `serde_json::Value` with a hard size limit.
+///
+/// This is lenient about duplicate object keys (last value wins).
+/// Use `parse_json_bytes_with` and `JsonParseOptions::strict` to reject them.
 #[cfg(feature = "canonical-json")]
 pub fn parse_json_bytes(bytes: &[u8], max_bytes: usize) -> SigniaResult<Value> {
-    if bytes.len() > max_bytes {
+    parse_json_bytes_with(
+        bytes,
+        &JsonParseOptions {
+            max_bytes,
+            reject_duplicate_keys: false,
+        },
+    )
+}
+
+/// Parse JSON bytes into `serde_json::Value` using explicit options.
+#[cfg(feature = "canonical-json")]
+pub fn parse_json_bytes_with(bytes: &[u8], opts: &JsonParseOptions) -> SigniaResult<Value> {
+    if bytes.len() > opts.max_bytes {
         return Err(SigniaError::invalid_argument(format!(
             "JSON payload too large ({} bytes > limit {})",
             bytes.len(),
-            max_bytes
+            opts.max_bytes
         )));
     }
 
+    if opts.reject_duplicate_keys {
+        return serde_json::from_slice::<strict::StrictValue>(bytes)
+            .map(|v| v.0)
+            .map_err(|e| SigniaError::serialization(format!("failed to parse JSON: {e}")));
+    }
+
     serde_json::from_slice(bytes)
         .map_err(|e| SigniaError::serialization(format!("failed to parse JSON: {e}")))
 }
 
+/// Duplicate-key-rejecting deserialization into `serde_json::Value`.
+#[cfg(feature = "canonical-json")]
+mod strict {
+    use std::fmt;
+
+    use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+    use serde_json::{Map, Number, Value};
+
+    pub struct StrictValue(pub Value);
+
+    impl<'de> Deserialize<'de> for StrictValue {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(StrictVisitor).map(StrictValue)
+        }
+    }
+
+    struct StrictVisitor;
+
+    impl<'de> Visitor<'de> for StrictVisitor {
+        type Value = Value;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("any JSON value")
+        }
+
+        fn visit_unit<E>(self) -> Result<Value, E> {
+            Ok(Value::Null)
+        }
+
+        fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+            Ok(Value::Bool(v))
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+            Ok(Value::Number(v.into()))
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+            Ok(Value::Number(v.into()))
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+            Number::from_f64(v)
+                .map(Value::Number)
+                .ok_or_else(|| E::custom("non-finite number"))
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+            Ok(Value::String(v.to_string()))
+        }
+
+        fn visit_string<E>(self, v: String) -> Result<Value, E> {
+            Ok(Value::String(v))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+            let mut out = Vec::new();
+            while let Some(StrictValue(v)) = seq.next_element()? {
+                out.push(v);
+            }
+            Ok(Value::Array(out))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+            let mut out = Map::new();
+            while let Some(key) = map.next_key::<String>()? {
+                if out.contains_key(&key) {
+                    return Err(de::Error::custom(format!("duplicate object key: {key}")));
+                }
+                let StrictValue(v) = map.next_value()?;
+                out.insert(key, v);
+            }
+            Ok(Value::Object(out))
+        }
+    }
+}
+
 /// Detect artifact kind from parsed JSON.
 ///
 /// We use lightweight heuristics:
@@ -113,6 +253,24 @@ pub fn parse_schema_v1(bytes: &[u8], max_bytes: usize) -> SigniaResult<SchemaV1>
     serde_json::from_value(v).map_err(|e| SigniaError::serialization(format!("failed to decode SchemaV1: {e}")))
 }
 
+/// Parse bytes into a schema of any supported version, dispatching on `version`.
+#[cfg(feature = "canonical-json")]
+pub fn parse_schema(bytes: &[u8], max_bytes: usize) -> SigniaResult<AnySchema> {
+    let v = parse_json_bytes(bytes, max_bytes)?;
+    let kind = detect_kind(&v);
+    if kind != ArtifactKind::Schema {
+        return Err(SigniaError::invalid_argument("input is not a schema"));
+    }
+    match SchemaVersion::parse(&read_version(&v)?)? {
+        SchemaVersion::V1 => serde_json::from_value::<SchemaV1>(v)
+            .map(AnySchema::V1)
+            .map_err(|e| SigniaError::serialization(format!("failed to decode SchemaV1: {e}"))),
+        SchemaVersion::V2 => serde_json::from_value::<SchemaV2>(v)
+            .map(AnySchema::V2)
+            .map_err(|e| SigniaError::serialization(format!("failed to decode SchemaV2: {e}"))),
+    }
+}
+
 /// Parse bytes into a ManifestV1.
 #[cfg(feature = "canonical-json")]
 pub fn parse_manifest_v1(bytes: &[u8], max_bytes: usize) -> SigniaResult<ManifestV1> {
@@ -152,6 +310,124 @@ pub fn parse_any(bytes: &[u8], max_bytes: usize) -> SigniaResult<(ArtifactKind,
     Ok((kind, v, ver))
 }
 
+/// Streaming NDJSON parser returned by `parse_ndjson_records`.
+#[cfg(feature = "canonical-json")]
+pub struct NdjsonRecords<R> {
+    reader: R,
+    max_records: usize,
+    max_record_bytes: usize,
+    records: usize,
+    line: usize,
+    buf: Vec<u8>,
+    done: bool,
+}
+
+/// Parse newline-delimited JSON from `reader`, one object per line.
+///
+/// Unlike `parse_json_bytes`, the input as a whole is unbounded: only one line
+/// is held in memory at a time, so a host can stream a large dataset manifest.
+/// Limits apply per record instead:
+/// - a line longer than `max_record_bytes` (excluding its `\n` / `\r\n`) is an
+///   error, detected without reading the rest of the line
+/// - more than `max_records` records is an error
+///
+/// Empty lines (including the one after a trailing newline) are skipped. Every
+/// record must be a JSON object. Errors name the 1-based line and end the
+/// iteration.
+#[cfg(feature = "canonical-json")]
+pub fn parse_ndjson_records<R: std::io::BufRead>(
+    reader: R,
+    max_records: usize,
+    max_record_bytes: usize,
+) -> NdjsonRecords<R> {
+    NdjsonRecords {
+        reader,
+        max_records,
+        max_record_bytes,
+        records: 0,
+        line: 0,
+        buf: Vec::new(),
+        done: false,
+    }
+}
+
+#[cfg(feature = "canonical-json")]
+impl<R: std::io::BufRead> NdjsonRecords<R> {
+    /// Read the next non-empty line into `buf`. `Ok(false)` at end of input.
+    fn next_line(&mut self) -> SigniaResult<bool> {
+        use std::io::{BufRead, Read};
+
+        loop {
+            self.buf.clear();
+            self.line += 1;
+            // Room for the record, "\r\n", and one byte to detect overflow.
+            let cap = self.max_record_bytes.saturating_add(3) as u64;
+            let n = (&mut self.reader)
+                .take(cap)
+                .read_until(b'\n', &mut self.buf)
+                .map_err(|e| SigniaError::serialization(format!("NDJSON line {}: read failed: {e}", self.line)))?;
+            if n == 0 {
+                return Ok(false);
+            }
+
+            let terminated = self.buf.last() == Some(&b'\n');
+            if terminated {
+                self.buf.pop();
+                if self.buf.last() == Some(&b'\r') {
+                    self.buf.pop();
+                }
+            }
+            if self.buf.len() > self.max_record_bytes {
+                return Err(SigniaError::limit_exceeded(format!(
+                    "NDJSON line {}: record exceeds {} bytes",
+                    self.line, self.max_record_bytes
+                )));
+            }
+            if !self.buf.iter().all(u8::is_ascii_whitespace) {
+                return Ok(true);
+            }
+        }
+    }
+
+    fn next_record(&mut self) -> SigniaResult<Option<Value>> {
+        if !self.next_line()? {
+            return Ok(None);
+        }
+        if self.records == self.max_records {
+            return Err(SigniaError::limit_exceeded(format!(
+                "NDJSON line {}: more than {} records",
+                self.line, self.max_records
+            )));
+        }
+        let v: Value = serde_json::from_slice(&self.buf)
+            .map_err(|e| SigniaError::serialization(format!("NDJSON line {}: {e}", self.line)))?;
+        if !v.is_object() {
+            return Err(SigniaError::invalid_argument(format!(
+                "NDJSON line {}: expected a JSON object",
+                self.line
+            )));
+        }
+        self.records += 1;
+        Ok(Some(v))
+    }
+}
+
+#[cfg(feature = "canonical-json")]
+impl<R: std::io::BufRead> Iterator for NdjsonRecords<R> {
+    type Item = SigniaResult<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let out = self.next_record().transpose();
+        if !matches!(out, Some(Ok(_))) {
+            self.done = true;
+        }
+        out
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "canonical-json")]
 mod tests {
@@ -201,7 +477,101 @@ mod tests {
         assert_eq!(v["version"], "v1");
 
         let err = parse_json_bytes(bytes, 1).err().unwrap();
+        assert_eq!(err.code(), "invalid_argument");
         let s = err.to_string();
         assert!(s.contains("too large"));
     }
+
+    #[test]
+    fn parse_schema_dispatches_on_version_and_round_trips() {
+        let v1 = br#"{"version":"v1","kind":"repo","meta":{},"entities":[{"id":"ent:a","type":"file","name":"a","attrs":{}}],"edges":[]}"#;
+        let v2 = br#"{"version":"v2","kind":"repo","meta":{},"producer":{"name":"signia-cli","version":"0.1.0"},"entities":[{"id":"ent:a","type":"file","name":"a","attrs":{},"annotations":{"owner":"x"}}],"edges":[]}"#;
+
+        let any = parse_schema(v1, 1024).unwrap();
+        assert_eq!(any.version(), "v1");
+        let AnySchema::V1(s) = any else { panic!("expected v1") };
+        let again = serde_json::to_vec(&s).unwrap();
+        assert_eq!(parse_json_bytes(&again, 1024).unwrap(), parse_json_bytes(v1, 1024).unwrap());
+
+        let any = parse_schema(v2, 1024).unwrap();
+        assert_eq!(any.version(), "v2");
+        let AnySchema::V2(s) = any else { panic!("expected v2") };
+        assert_eq!(s.producer.as_ref().unwrap().name, "signia-cli");
+        let again = serde_json::to_vec(&s).unwrap();
+        assert_eq!(parse_json_bytes(&again, 1024).unwrap(), parse_json_bytes(v2, 1024).unwrap());
+
+        let v9 = br#"{"version":"v9","kind":"repo","meta":{},"entities":[],"edges":[]}"#;
+        assert!(parse_schema(v9, 1024).unwrap_err().to_string().contains("unsupported schema version"));
+    }
+
+    #[test]
+    fn strict_mode_rejects_duplicate_keys() {
+        let bytes = br#"{"a":1,"a":2}"#;
+
+        let lenient = parse_json_bytes(bytes, 1024).unwrap();
+        assert_eq!(lenient["a"], 2);
+
+        let err = parse_json_bytes_with(bytes, &JsonParseOptions::strict(1024)).unwrap_err();
+        assert!(err.to_string().contains("duplicate object key: a"));
+
+        let nested = br#"{"x":{"b":1,"b":1}}"#;
+        assert!(parse_json_bytes_with(nested, &JsonParseOptions::strict(1024)).is_err());
+
+        let ok = br#"{"a":1,"b":[{"a":2},{"a":3}]}"#;
+        let v = parse_json_bytes_with(ok, &JsonParseOptions::strict(1024)).unwrap();
+        assert_eq!(v["b"][1]["a"], 3);
+    }
+
+    fn ndjson(text: &str, max_records: usize, max_record_bytes: usize) -> Vec<SigniaResult<Value>> {
+        parse_ndjson_records(text.as_bytes(), max_records, max_record_bytes).collect()
+    }
+
+    #[test]
+    fn ndjson_yields_one_object_per_line() {
+        let text = "{\"path\":\"a.jsonl\",\"size\":1}\n{\"path\":\"b.jsonl\"}\r\n\n{\"path\":\"c.jsonl\"}";
+        let records: Vec<Value> = ndjson(text, 10, 64).into_iter().map(Result::unwrap).collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0]["size"], 1);
+        assert_eq!(records[1]["path"], "b.jsonl");
+        assert_eq!(records[2]["path"], "c.jsonl");
+
+        // Exactly at the limit is fine.
+        assert!(ndjson("{\"a\":1}", 1, 7)[0].is_ok());
+    }
+
+    #[test]
+    fn ndjson_trailing_newline_adds_no_record() {
+        assert_eq!(ndjson("{\"a\":1}\n", 1, 64).len(), 1);
+        assert_eq!(ndjson("{\"a\":1}\n\n\n", 1, 64).len(), 1);
+        assert!(ndjson("", 0, 64).is_empty());
+        assert!(ndjson("\n", 0, 64).is_empty());
+    }
+
+    #[test]
+    fn ndjson_rejects_oversized_line_and_stops() {
+        let long = format!("{{\"a\":\"{}\"}}", "x".repeat(100));
+        let out = ndjson(&format!("{{\"a\":1}}\n{long}\n{{\"b\":2}}\n"), 10, 32);
+        assert_eq!(out.len(), 2);
+        assert!(out[0].is_ok());
+        let err = out[1].as_ref().unwrap_err().to_string();
+        assert!(err.contains("NDJSON line 2: record exceeds 32 bytes"), "{err}");
+
+        // Unterminated final line too.
+        let err = ndjson(&long, 10, 32)[0].as_ref().unwrap_err().to_string();
+        assert!(err.contains("exceeds"), "{err}");
+    }
+
+    #[test]
+    fn ndjson_enforces_objects_and_record_count() {
+        let err = ndjson("{\"a\":1}\n[1,2]\n", 10, 64)[1].as_ref().unwrap_err().to_string();
+        assert!(err.contains("NDJSON line 2: expected a JSON object"), "{err}");
+
+        let err = ndjson("{\"a\":1}\n{not json}\n", 10, 64)[1].as_ref().unwrap_err().to_string();
+        assert!(err.contains("NDJSON line 2"), "{err}");
+
+        let out = ndjson("{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n", 2, 64);
+        assert_eq!(out.len(), 3);
+        assert!(out[1].is_ok());
+        assert!(out[2].as_ref().unwrap_err().to_string().contains("more than 2 records"));
+    }
 }
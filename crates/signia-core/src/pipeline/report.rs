@@ -55,11 +55,7 @@ mod tests {
     fn report_basic() {
         let report = PipelineReport::new(
             PipelineData::None,
-            vec![PipelineDiagnostic {
-                level: DiagnosticLevel::Info,
-                code: "test".to_string(),
-                message: "ok".to_string(),
-            }],
+            vec![PipelineDiagnostic::new(DiagnosticLevel::Info, "test", "ok")],
             vec!["stage1".to_string(), "stage2".to_string()],
         );
 
@@ -8,14 +8,23 @@
 //! - IR normalization helpers (ordering, id assignment hooks)
 //! - Emission helpers (IR -> SchemaV1)
 //! - Proof construction helper glue (leaves -> Merkle root)
+//! - Manifest binding (schema digest -> ManifestV1.schemas)
+//! - Canonical byte emission (SchemaV1/ManifestV1/ProofV1 -> bytes)
 //!
 //! Note: complex plugin execution lives in `signia-plugins` crate (not core).
 //! Core stages are intentionally minimal.
+//!
+//! Feature note: every stage here needs the `canonical-json` feature (enabled by
+//! default). Without it the stage types are marked deprecated, so using them emits a
+//! compile-time warning, and `run` fails with `errors::canonical_json_required`.
+
+// The deprecation is aimed at downstream users; the impls below still reference the types.
+#![cfg_attr(not(feature = "canonical-json"), allow(deprecated))]
 
 use std::collections::{BTreeMap, BTreeSet};
 
-use crate::errors::{SigniaError, SigniaResult};
-use crate::pipeline::{PipelineContext, PipelineData, Stage};
+use crate::errors::{canonical_json_required, SigniaError, SigniaResult};
+use crate::pipeline::{DiagnosticLevel, PipelineContext, PipelineData, PipelineDataKind, PipelineDiagnostic, Stage};
 
 #[cfg(feature = "canonical-json")]
 use serde_json::Value;
@@ -24,11 +33,15 @@ use serde_json::Value;
 use crate::model::ir::{DefaultIdStrategy, IdStrategy, IrGraph};
 
 #[cfg(feature = "canonical-json")]
-use crate::model::v1::{ProofV1, SchemaV1};
+use crate::model::v1::{ProofV1, SchemaRefV1, SchemaV1};
 
 /// Stage: Validate that a `PipelineData::Json` value is an object.
 ///
 /// Useful for quick sanity checks before plugin code uses `.as_object().unwrap()`.
+#[cfg_attr(
+    not(feature = "canonical-json"),
+    deprecated(note = "requires the `canonical-json` feature of signia-core")
+)]
 pub struct ValidateJsonObjectStage {
     id: String,
 }
@@ -44,14 +57,20 @@ impl Stage for ValidateJsonObjectStage {
         &self.id
     }
 
+    fn input_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::Json)
+    }
+
+    fn output_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::Json)
+    }
+
     fn run(&self, ctx: &mut PipelineContext, input: PipelineData) -> SigniaResult<PipelineData> {
         #[cfg(not(feature = "canonical-json"))]
         {
             let _ = ctx;
             let _ = input;
-            return Err(SigniaError::invalid_argument(
-                "canonical-json feature is required for ValidateJsonObjectStage",
-            ));
+            return Err(canonical_json_required("ValidateJsonObjectStage"));
         }
 
         #[cfg(feature = "canonical-json")]
@@ -73,6 +92,10 @@ impl Stage for ValidateJsonObjectStage {
 }
 
 /// Stage: Validate basic IR invariants.
+#[cfg_attr(
+    not(feature = "canonical-json"),
+    deprecated(note = "requires the `canonical-json` feature of signia-core")
+)]
 pub struct ValidateIrStage {
     id: String,
 }
@@ -88,14 +111,20 @@ impl Stage for ValidateIrStage {
         &self.id
     }
 
+    fn input_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::Ir)
+    }
+
+    fn output_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::Ir)
+    }
+
     fn run(&self, ctx: &mut PipelineContext, input: PipelineData) -> SigniaResult<PipelineData> {
         #[cfg(not(feature = "canonical-json"))]
         {
             let _ = ctx;
             let _ = input;
-            return Err(SigniaError::invalid_argument(
-                "canonical-json feature is required for ValidateIrStage",
-            ));
+            return Err(canonical_json_required("ValidateIrStage"));
         }
 
         #[cfg(feature = "canonical-json")]
@@ -118,6 +147,10 @@ impl Stage for ValidateIrStage {
 ///
 /// For the current IR design using BTreeMap/BTreeSet, ordering is already stable.
 /// This stage is still useful as an explicit step and future hook.
+#[cfg_attr(
+    not(feature = "canonical-json"),
+    deprecated(note = "requires the `canonical-json` feature of signia-core")
+)]
 pub struct NormalizeIrStage {
     id: String,
 }
@@ -133,14 +166,20 @@ impl Stage for NormalizeIrStage {
         &self.id
     }
 
+    fn input_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::Ir)
+    }
+
+    fn output_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::Ir)
+    }
+
     fn run(&self, ctx: &mut PipelineContext, input: PipelineData) -> SigniaResult<PipelineData> {
         #[cfg(not(feature = "canonical-json"))]
         {
             let _ = ctx;
             let _ = input;
-            return Err(SigniaError::invalid_argument(
-                "canonical-json feature is required for NormalizeIrStage",
-            ));
+            return Err(canonical_json_required("NormalizeIrStage"));
         }
 
         #[cfg(feature = "canonical-json")]
@@ -179,6 +218,10 @@ impl Stage for NormalizeIrStage {
 ///
 /// Output:
 /// - PipelineData::SchemaV1
+#[cfg_attr(
+    not(feature = "canonical-json"),
+    deprecated(note = "requires the `canonical-json` feature of signia-core")
+)]
 pub struct EmitSchemaV1Stage {
     id: String,
 }
@@ -188,18 +231,22 @@ impl EmitSchemaV1Stage {
         Self { id: id.into() }
     }
 
+    /// `schema.meta` from the context, canonicalized so that authoring key order
+    /// and number spelling cannot change the schema hash.
     #[cfg(feature = "canonical-json")]
     fn meta_from_ctx(ctx: &PipelineContext) -> SigniaResult<Value> {
+        use crate::determinism::canonical::canonicalize_json;
+
         // Prefer json_params if present
         if let Some(v) = ctx.get_json_param("schema.meta") {
-            return Ok(v.clone());
+            return canonicalize_json(v);
         }
 
         // Fallback to string param
         if let Some(s) = ctx.get_param("schema.meta") {
             let v: Value = serde_json::from_str(s)
                 .map_err(|e| SigniaError::serialization(format!("failed to parse schema.meta JSON: {e}")))?;
-            return Ok(v);
+            return canonicalize_json(&v);
         }
 
         Err(SigniaError::invalid_argument(
@@ -213,14 +260,20 @@ impl Stage for EmitSchemaV1Stage {
         &self.id
     }
 
+    fn input_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::Ir)
+    }
+
+    fn output_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::SchemaV1)
+    }
+
     fn run(&self, ctx: &mut PipelineContext, input: PipelineData) -> SigniaResult<PipelineData> {
         #[cfg(not(feature = "canonical-json"))]
         {
             let _ = ctx;
             let _ = input;
-            return Err(SigniaError::invalid_argument(
-                "canonical-json feature is required for EmitSchemaV1Stage",
-            ));
+            return Err(canonical_json_required("EmitSchemaV1Stage"));
         }
 
         #[cfg(feature = "canonical-json")]
@@ -252,15 +305,44 @@ impl Stage for EmitSchemaV1Stage {
     }
 }
 
+/// Default cap on a single proof leaf value, in bytes.
+///
+/// Leaf values are normally hex digests (64 bytes for sha256).
+pub const DEFAULT_MAX_LEAF_VALUE_BYTES: u64 = 4096;
+
+/// Reject leaves whose value exceeds `max` bytes, naming the offending key.
+#[cfg(feature = "canonical-json")]
+pub fn check_leaf_values(leaves: &[crate::model::v1::LeafV1], max: u64) -> SigniaResult<()> {
+    for leaf in leaves {
+        if leaf.value.len() as u64 > max {
+            return Err(SigniaError::limit_exceeded(format!(
+                "proof leaf {} value exceeds max_leaf_value_bytes ({} > {max})",
+                leaf.key,
+                leaf.value.len()
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Stage: Build a proof Merkle root from given leaf entries.
 ///
 /// Inputs:
 /// - PipelineData::Json containing {"hashAlg":"sha256","leaves":[{"key":"...","value":"..."}, ...]}
+///   and optionally `"merkleOddPolicy": "duplicate" | "promote"` (default duplicate),
+///   which is recorded in the proof
+///
+/// Context params:
+/// - `limits.max_leaf_value_bytes` (optional, default `DEFAULT_MAX_LEAF_VALUE_BYTES`)
 ///
 /// Output:
 /// - PipelineData::ProofV1
 ///
 /// The actual Merkle computation is implemented in `crate::merkle`.
+#[cfg_attr(
+    not(feature = "canonical-json"),
+    deprecated(note = "requires the `canonical-json` feature of signia-core")
+)]
 pub struct BuildProofV1Stage {
     id: String,
 }
@@ -276,14 +358,20 @@ impl Stage for BuildProofV1Stage {
         &self.id
     }
 
+    fn input_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::Json)
+    }
+
+    fn output_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::ProofV1)
+    }
+
     fn run(&self, ctx: &mut PipelineContext, input: PipelineData) -> SigniaResult<PipelineData> {
         #[cfg(not(feature = "canonical-json"))]
         {
             let _ = ctx;
             let _ = input;
-            return Err(SigniaError::invalid_argument(
-                "canonical-json feature is required for BuildProofV1Stage",
-            ));
+            return Err(canonical_json_required("BuildProofV1Stage"));
         }
 
         #[cfg(feature = "canonical-json")]
@@ -303,6 +391,13 @@ impl Stage for BuildProofV1Stage {
                 .and_then(|x| x.as_str())
                 .ok_or_else(|| SigniaError::invalid_argument("proof input missing hashAlg string"))?
                 .to_string();
+            let odd_policy = match obj.get("merkleOddPolicy") {
+                None => crate::merkle::OddNodePolicy::default(),
+                Some(x) => crate::merkle::OddNodePolicy::parse(
+                    x.as_str()
+                        .ok_or_else(|| SigniaError::invalid_argument("proof input merkleOddPolicy must be a string"))?,
+                )?,
+            };
 
             let leaves_val = obj
                 .get("leaves")
@@ -322,18 +417,30 @@ impl Stage for BuildProofV1Stage {
                     .ok_or_else(|| SigniaError::invalid_argument("leaf.value must be a string"))?;
                 leaves.push(crate::model::v1::LeafV1 {
                     key: key.to_string(),
-                    value: value.to_string(),
+                    value: crate::proof::normalize_leaf_value(value),
                 });
             }
 
             // Deterministic ordering of leaves by key.
             leaves.sort_by(|a, b| a.key.cmp(&b.key));
 
+            let max_value = match ctx.get_param("limits.max_leaf_value_bytes") {
+                Some(s) => s.parse::<u64>().map_err(|_| {
+                    SigniaError::invalid_argument(format!("invalid limits.max_leaf_value_bytes: {s}"))
+                })?,
+                None => DEFAULT_MAX_LEAF_VALUE_BYTES,
+            };
+            if let Err(e) = check_leaf_values(&leaves, max_value) {
+                ctx.push_error("proof.leaf.too_large", e.to_string());
+                return Err(e);
+            }
+
             // Build Merkle root from leaf hashes using the core merkle utilities.
             let mut tree = crate::merkle::MerkleTree::new(crate::merkle::MerkleTreeOptions {
                 hash_alg: hash_alg.clone(),
                 domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
                 domain_node: crate::domain::MERKLE_NODE.to_string(),
+                odd_policy,
             });
 
             for leaf in &leaves {
@@ -341,9 +448,10 @@ impl Stage for BuildProofV1Stage {
                 tree.push_leaf(payload.as_bytes())?;
             }
 
-            let root = tree.root_hex()?;
+            let root = tree.root_hex_or_empty()?;
 
             let mut proof = ProofV1::new(hash_alg, root);
+            proof.merkle_odd_policy = odd_policy;
             proof.leaves = leaves;
 
             ctx.push_info("proof.built", "built ProofV1 Merkle root");
@@ -353,6 +461,174 @@ impl Stage for BuildProofV1Stage {
     }
 }
 
+/// Stage: Bind a schema digest into a ManifestV1.
+///
+/// Inputs:
+/// - PipelineData::ManifestV1
+///
+/// Context params:
+/// - `schema.kind` (used as the schema ref name)
+/// - `schema.digest` (hex digest, e.g. from `hash::hash_schema_v1_hex`)
+///
+/// Output:
+/// - PipelineData::ManifestV1 with a `SchemaRefV1 { name: kind, digest }`
+///
+/// Binding is idempotent: an existing ref with the same name and digest is kept.
+/// An existing ref with the same name and a different digest is an error.
+#[cfg_attr(
+    not(feature = "canonical-json"),
+    deprecated(note = "requires the `canonical-json` feature of signia-core")
+)]
+pub struct BindManifestStage {
+    id: String,
+}
+
+impl BindManifestStage {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl Stage for BindManifestStage {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn input_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::ManifestV1)
+    }
+
+    fn output_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::ManifestV1)
+    }
+
+    fn run(&self, ctx: &mut PipelineContext, input: PipelineData) -> SigniaResult<PipelineData> {
+        #[cfg(not(feature = "canonical-json"))]
+        {
+            let _ = ctx;
+            let _ = input;
+            return Err(canonical_json_required("BindManifestStage"));
+        }
+
+        #[cfg(feature = "canonical-json")]
+        {
+            let mut manifest = match input {
+                PipelineData::ManifestV1(m) => m,
+                other => {
+                    return Err(SigniaError::invalid_argument(format!(
+                        "expected PipelineData::ManifestV1, got {other:?}"
+                    )))
+                }
+            };
+
+            let kind = ctx
+                .get_param("schema.kind")
+                .ok_or_else(|| SigniaError::invalid_argument("missing schema.kind in ctx params"))?
+                .to_string();
+            let digest = ctx
+                .get_param("schema.digest")
+                .ok_or_else(|| SigniaError::invalid_argument("missing schema.digest in ctx params"))?
+                .to_string();
+
+            match manifest.schemas.iter().find(|s| s.name == kind) {
+                Some(existing) if existing.digest == digest => {
+                    ctx.push_info("manifest.bind.exists", format!("manifest already binds schema {kind}"));
+                }
+                Some(existing) => {
+                    let msg = format!(
+                        "manifest already binds schema {kind} to a different digest ({} != {digest})",
+                        existing.digest
+                    );
+                    ctx.push_error("manifest.bind.conflict", msg.clone());
+                    return Err(SigniaError::invalid_argument(msg));
+                }
+                None => {
+                    manifest.add_schema(SchemaRefV1 {
+                        name: kind.clone(),
+                        digest,
+                    });
+                    ctx.push_info("manifest.bound", format!("bound schema {kind} into manifest"));
+                }
+            }
+
+            Ok(PipelineData::ManifestV1(manifest))
+        }
+    }
+}
+
+/// Stage: Serialize a v1 model into canonical JSON bytes.
+///
+/// Inputs:
+/// - PipelineData::SchemaV1, PipelineData::ManifestV1 or PipelineData::ProofV1
+///
+/// Output:
+/// - PipelineData::Bytes containing the canonical JSON encoding
+///
+/// The sha256 of the emitted bytes is recorded as an info diagnostic
+/// (`emit.canonical_bytes`, data: `artifact`, `digest`). For a schema this equals
+/// `hash::hash_schema_v1_hex`, so a pipeline can end in ready-to-store bytes.
+#[cfg_attr(
+    not(feature = "canonical-json"),
+    deprecated(note = "requires the `canonical-json` feature of signia-core")
+)]
+pub struct EmitCanonicalBytesStage {
+    id: String,
+}
+
+impl EmitCanonicalBytesStage {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl Stage for EmitCanonicalBytesStage {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn output_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::Bytes)
+    }
+
+    fn run(&self, ctx: &mut PipelineContext, input: PipelineData) -> SigniaResult<PipelineData> {
+        #[cfg(not(feature = "canonical-json"))]
+        {
+            let _ = ctx;
+            let _ = input;
+            return Err(canonical_json_required("EmitCanonicalBytesStage"));
+        }
+
+        #[cfg(feature = "canonical-json")]
+        {
+            let (artifact, value) = match input {
+                PipelineData::SchemaV1(s) => ("schema", serde_json::to_value(&s)?),
+                PipelineData::ManifestV1(m) => ("manifest", serde_json::to_value(&m)?),
+                PipelineData::ProofV1(p) => ("proof", serde_json::to_value(&p)?),
+                other => {
+                    return Err(SigniaError::invalid_argument(format!(
+                        "expected PipelineData::SchemaV1, ManifestV1 or ProofV1, got {other:?}"
+                    )))
+                }
+            };
+
+            let bytes = crate::determinism::canonical_json::to_canonical_bytes(&value)?;
+            let digest = crate::hash::hash_bytes_hex(&bytes)?;
+
+            let mut data = BTreeMap::new();
+            data.insert("artifact".to_string(), artifact.to_string());
+            data.insert("digest".to_string(), digest.clone());
+            ctx.diagnostics.push(PipelineDiagnostic {
+                level: DiagnosticLevel::Info,
+                code: "emit.canonical_bytes".to_string(),
+                message: format!("emitted canonical {artifact} bytes (sha256 {digest})"),
+                data,
+            });
+
+            Ok(PipelineData::Bytes(bytes))
+        }
+    }
+}
+
 /// Stage: Extract a list of unique entity types from a SchemaV1 into JSON.
 ///
 /// Inputs:
@@ -360,6 +636,10 @@ impl Stage for BuildProofV1Stage {
 ///
 /// Output:
 /// - PipelineData::Json: {"entityTypes":[...],"edgeTypes":[...],"entities":N,"edges":M}
+#[cfg_attr(
+    not(feature = "canonical-json"),
+    deprecated(note = "requires the `canonical-json` feature of signia-core")
+)]
 pub struct SchemaSummaryStage {
     id: String,
 }
@@ -375,14 +655,20 @@ impl Stage for SchemaSummaryStage {
         &self.id
     }
 
+    fn input_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::SchemaV1)
+    }
+
+    fn output_kind(&self) -> Option<PipelineDataKind> {
+        Some(PipelineDataKind::Json)
+    }
+
     fn run(&self, ctx: &mut PipelineContext, input: PipelineData) -> SigniaResult<PipelineData> {
         #[cfg(not(feature = "canonical-json"))]
         {
             let _ = ctx;
             let _ = input;
-            return Err(SigniaError::invalid_argument(
-                "canonical-json feature is required for SchemaSummaryStage",
-            ));
+            return Err(canonical_json_required("SchemaSummaryStage"));
         }
 
         #[cfg(feature = "canonical-json")]
@@ -424,11 +710,11 @@ impl Stage for SchemaSummaryStage {
 mod tests {
     use super::*;
     use crate::pipeline::{Pipeline, PipelineContext};
+    use crate::proof::leaf_keys;
 
-    #[test]
+    /// Minimal IR with 2 nodes and 1 edge.
     #[cfg(feature = "canonical-json")]
-    fn stages_emit_schema_and_summary() {
-        // Minimal IR with 2 nodes and 1 edge
+    fn demo_ir() -> IrGraph {
         let mut g = IrGraph::new();
         g.insert_node(crate::model::ir::IrNode {
             id: "n1".to_string(),
@@ -466,6 +752,11 @@ mod tests {
         })
         .unwrap();
 
+        g
+    }
+
+    #[cfg(feature = "canonical-json")]
+    fn demo_ctx() -> PipelineContext {
         let mut ctx = PipelineContext::default();
         ctx.set_param("schema.kind", "repo");
         ctx.set_json_param(
@@ -477,14 +768,39 @@ mod tests {
                 "normalization":{"policyVersion":"v1","pathRoot":"artifact:/","newline":"lf","encoding":"utf-8","symlinks":"deny","network":"deny"}
             }),
         );
+        ctx
+    }
 
+    #[cfg(feature = "canonical-json")]
+    fn schema_pipeline() -> Pipeline {
         let mut p = Pipeline::new();
         p.push_stage(ValidateIrStage::new("ir.validate"));
         p.push_stage(NormalizeIrStage::new("ir.normalize"));
         p.push_stage(EmitSchemaV1Stage::new("emit.schema_v1"));
+        p
+    }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn builtin_stage_contracts_chain() {
+        let mut p = schema_pipeline();
+        p.push_stage(SchemaSummaryStage::new("schema.summary"));
+        p.validate().unwrap();
+
+        let mut p = Pipeline::new();
+        p.push_stage(ValidateIrStage::new("ir.validate"));
+        p.push_stage(SchemaSummaryStage::new("schema.summary"));
+        let err = p.validate().unwrap_err();
+        assert!(err.to_string().contains("schema.summary expects SchemaV1"), "{err}");
+    }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn stages_emit_schema_and_summary() {
+        let mut p = schema_pipeline();
         p.push_stage(SchemaSummaryStage::new("schema.summary"));
 
-        let report = p.run(ctx, PipelineData::Ir(g)).unwrap();
+        let report = p.run(demo_ctx(), PipelineData::Ir(demo_ir())).unwrap();
         match report.output {
             PipelineData::Json(v) => {
                 assert_eq!(v["entities"], 2);
@@ -493,4 +809,141 @@ mod tests {
             _ => panic!("expected json output"),
         }
     }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn schema_hash_ignores_meta_key_order() {
+        let schema_hash = |meta: &str| {
+            let mut ctx = PipelineContext::default();
+            ctx.set_param("schema.kind", "repo");
+            ctx.set_json_param("schema.meta", serde_json::from_str(meta).unwrap());
+            match schema_pipeline().run(ctx, PipelineData::Ir(demo_ir())).unwrap().output {
+                PipelineData::SchemaV1(s) => crate::hash::hash_schema_v1_hex(&s).unwrap(),
+                other => panic!("expected SchemaV1, got {other:?}"),
+            }
+        };
+
+        let a = schema_hash(
+            r#"{"name":"demo","createdAt":"1970-01-01T00:00:00Z","labels":{"tier":1.0,"a":"x"},
+                "source":{"type":"path","locator":"artifact:/demo"},
+                "normalization":{"policyVersion":"v1","pathRoot":"artifact:/","newline":"lf","encoding":"utf-8","symlinks":"deny","network":"deny"}}"#,
+        );
+        let b = schema_hash(
+            r#"{"normalization":{"network":"deny","symlinks":"deny","encoding":"utf-8","newline":"lf","pathRoot":"artifact:/","policyVersion":"v1"},
+                "source":{"locator":"artifact:/demo","type":"path"},
+                "labels":{"a":"x","tier":1},"createdAt":"1970-01-01T00:00:00Z","name":"demo"}"#,
+        );
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn canonical_bytes_digest_matches_schema_hash() {
+        let schema = match schema_pipeline().run(demo_ctx(), PipelineData::Ir(demo_ir())).unwrap().output {
+            PipelineData::SchemaV1(s) => s,
+            _ => panic!("expected schema output"),
+        };
+        let expected = crate::hash::hash_schema_v1_hex(&schema).unwrap();
+
+        let mut ctx = demo_ctx();
+        let out = EmitCanonicalBytesStage::new("emit.bytes")
+            .run(&mut ctx, PipelineData::SchemaV1(schema))
+            .unwrap();
+        let bytes = match out {
+            PipelineData::Bytes(b) => b,
+            _ => panic!("expected bytes output"),
+        };
+
+        assert_eq!(crate::hash::hash_bytes_hex(&bytes).unwrap(), expected);
+        let diag = ctx.diagnostics.iter().find(|d| d.code == "emit.canonical_bytes").unwrap();
+        assert_eq!(diag.data.get("artifact").map(String::as_str), Some("schema"));
+        assert_eq!(diag.data.get("digest"), Some(&expected));
+    }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn bind_manifest_adds_ref_and_rejects_conflict() {
+        use crate::model::v1::{LimitsV1, ManifestV1};
+
+        let manifest = ManifestV1::new(
+            "demo",
+            LimitsV1 {
+                max_files: 1,
+                max_bytes: 1,
+                max_nodes: 1,
+                max_edges: 1,
+                timeout_ms: 1,
+                network: "deny".to_string(),
+            },
+        );
+
+        let mut ctx = demo_ctx();
+        ctx.set_param("schema.digest", "aa".repeat(32));
+        let stage = BindManifestStage::new("manifest.bind");
+
+        let bound = match stage.run(&mut ctx, PipelineData::ManifestV1(manifest)).unwrap() {
+            PipelineData::ManifestV1(m) => m,
+            _ => panic!("expected manifest output"),
+        };
+        assert_eq!(bound.schemas.len(), 1);
+        assert_eq!(bound.schemas[0].name, "repo");
+        assert_eq!(bound.schemas[0].digest, "aa".repeat(32));
+
+        // Re-binding the same digest is a no-op.
+        let again = match stage.run(&mut ctx, PipelineData::ManifestV1(bound.clone())).unwrap() {
+            PipelineData::ManifestV1(m) => m,
+            _ => panic!("expected manifest output"),
+        };
+        assert_eq!(again.schemas.len(), 1);
+
+        // A different digest for the same schema conflicts.
+        ctx.set_param("schema.digest", "bb".repeat(32));
+        assert!(stage.run(&mut ctx, PipelineData::ManifestV1(bound)).is_err());
+        assert!(ctx.diagnostics.iter().any(|d| d.code == "manifest.bind.conflict"));
+    }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn build_proof_rejects_oversized_leaf_value() {
+        let input = serde_json::json!({
+            "hashAlg": "sha256",
+            "leaves": [
+                {"key": leaf_keys::SCHEMA_HASH, "value": "a".repeat(64)},
+                {"key": "file:huge", "value": "b".repeat(65)},
+            ]
+        });
+
+        let mut ctx = PipelineContext::default();
+        ctx.set_param("limits.max_leaf_value_bytes", "64");
+        let stage = BuildProofV1Stage::new("proof.build");
+        let err = stage.run(&mut ctx, PipelineData::Json(input.clone())).unwrap_err();
+        assert!(err.to_string().contains("file:huge"), "{err}");
+        assert!(ctx.diagnostics.iter().any(|d| d.code == "proof.leaf.too_large"));
+
+        // Within the default limit.
+        let mut ctx = PipelineContext::default();
+        assert!(stage.run(&mut ctx, PipelineData::Json(input)).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "canonical-json")]
+    fn build_proof_normalizes_numeric_leaf_values() {
+        let stage = BuildProofV1Stage::new("proof.build");
+        let build = |size: &str| {
+            let input = serde_json::json!({
+                "hashAlg": "sha256",
+                "leaves": [{"key": "size:README.md", "value": size}]
+            });
+            match stage.run(&mut PipelineContext::default(), PipelineData::Json(input)).unwrap() {
+                PipelineData::ProofV1(p) => p,
+                other => panic!("expected ProofV1, got {other:?}"),
+            }
+        };
+
+        let a = build("007");
+        let b = build("7");
+        assert_eq!(a.leaves[0].value, "7");
+        assert_eq!(a.root, b.root);
+        assert_ne!(a.root, build("8").root);
+    }
 }
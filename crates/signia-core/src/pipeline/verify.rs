@@ -21,9 +21,10 @@
 use std::collections::BTreeMap;
 
 use crate::errors::{SigniaError, SigniaResult};
+use crate::proof::leaf_keys;
 
 #[cfg(feature = "canonical-json")]
-use crate::model::v1::{InclusionProofV1, LeafV1, ManifestV1, ProofV1, SchemaV1, SiblingV1};
+use crate::model::v1::{InclusionProofV1, LeafV1, ManifestV1, ProofV1, SchemaV1, Side, SiblingV1};
 
 #[cfg(feature = "canonical-json")]
 use serde_json::Value;
@@ -37,6 +38,9 @@ pub struct VerifyBundle {
     pub manifest: ManifestV1,
     #[cfg(feature = "canonical-json")]
     pub proof: Option<ProofV1>,
+    /// Actual output digests (lowercase hex) keyed by output locator, checked
+    /// against `manifest.outputs[].expectedDigest`.
+    pub output_digests: BTreeMap<String, String>,
 }
 
 /// Verification options.
@@ -50,6 +54,22 @@ pub struct VerifyOptions {
 
     /// If true, require manifest.schemas include the schema digest.
     pub require_manifest_binding: bool,
+
+    /// Leaf key prefixes that are expected in a proof.
+    ///
+    /// Leaves whose key matches none of these produce a `proof.leaf.unexpected`
+    /// warning. Defaults to the keys emitted by `pipeline::compile`.
+    pub allowed_leaf_prefixes: Vec<String>,
+
+    /// Leaf keys that must be present in a proof.
+    ///
+    /// Each missing key produces a `proof.leaf.required.missing` error. Defaults to
+    /// the schema and manifest digests; their values are checked whenever present.
+    pub required_leaf_keys: Vec<String>,
+
+    /// If true, check declared `manifest.inputs` digests are well-formed and
+    /// `manifest.outputs` expected digests match `VerifyBundle::output_digests`.
+    pub verify_io_digests: bool,
 }
 
 impl Default for VerifyOptions {
@@ -58,6 +78,15 @@ impl Default for VerifyOptions {
             require_proof: true,
             validate_inclusions: true,
             require_manifest_binding: true,
+            allowed_leaf_prefixes: leaf_keys::VERIFY_ALLOWED_PREFIXES
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            required_leaf_keys: leaf_keys::VERIFY_REQUIRED_KEYS
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            verify_io_digests: true,
         }
     }
 }
@@ -110,6 +139,25 @@ fn push(
     });
 }
 
+/// Push an error finding carrying both digests (hex) in `data.computed` / `data.expected`.
+fn push_digest_mismatch(
+    findings: &mut Vec<VerifyFinding>,
+    code: impl Into<String>,
+    message: impl Into<String>,
+    computed: &str,
+    expected: &str,
+) {
+    let mut data = BTreeMap::new();
+    data.insert("computed".to_string(), computed.to_string());
+    data.insert("expected".to_string(), expected.to_string());
+    findings.push(VerifyFinding {
+        level: VerifyLevel::Error,
+        code: code.into(),
+        message: message.into(),
+        data,
+    });
+}
+
 /// Verify a bundle deterministically.
 ///
 /// Returns a report even if verification fails (for UI). Use `report.ok` or `report.has_errors()`.
@@ -157,6 +205,10 @@ pub fn verify_bundle(bundle: VerifyBundle, opts: VerifyOptions) -> SigniaResult<
         }
     }
 
+    if opts.verify_io_digests {
+        verify_manifest_io_digests(&bundle.manifest, &bundle.output_digests, &mut findings);
+    }
+
     // 4) Proof
     let mut proof_root = None;
     if opts.require_proof && bundle.proof.is_none() {
@@ -169,42 +221,97 @@ pub fn verify_bundle(bundle: VerifyBundle, opts: VerifyOptions) -> SigniaResult<
     }
 
     if let Some(p) = &bundle.proof {
-        // Leaves must include schemaHash and manifestHash
         let mut leaf_map: BTreeMap<String, String> = BTreeMap::new();
         for l in &p.leaves {
             leaf_map.insert(l.key.clone(), l.value.clone());
         }
 
-        if leaf_map.get("digest:schemaHash") != Some(&schema_hash) {
-            push(
+        // Presence is policy (`required_leaf_keys`); reported in option order.
+        for key in &opts.required_leaf_keys {
+            if !leaf_map.contains_key(key) {
+                let mut data = BTreeMap::new();
+                data.insert("key".to_string(), key.clone());
+                findings.push(VerifyFinding {
+                    level: VerifyLevel::Error,
+                    code: "proof.leaf.required.missing".to_string(),
+                    message: format!("proof is missing required leaf {key}"),
+                    data,
+                });
+            }
+        }
+
+        // Digest leaves that are present must match the recomputed hashes.
+        if let Some(expected) = leaf_map.get(leaf_keys::SCHEMA_HASH).filter(|v| **v != schema_hash) {
+            push_digest_mismatch(
                 &mut findings,
-                VerifyLevel::Error,
                 "proof.leaf.schemaHash.mismatch",
                 "proof leaf digest:schemaHash does not match computed schema hash",
+                &schema_hash,
+                expected,
             );
         }
 
-        if leaf_map.get("digest:manifestHash") != Some(&manifest_hash) {
-            push(
+        if let Some(expected) = leaf_map.get(leaf_keys::MANIFEST_HASH).filter(|v| **v != manifest_hash) {
+            push_digest_mismatch(
                 &mut findings,
-                VerifyLevel::Error,
                 "proof.leaf.manifestHash.mismatch",
                 "proof leaf digest:manifestHash does not match computed manifest hash",
+                &manifest_hash,
+                expected,
             );
         }
 
-        // Recompute root
-        let root = recompute_proof_root_hex(p)?;
-        proof_root = Some(root.clone());
+        // Construction stores leaves sorted by key. The root is recomputed over the
+        // sorted set, so a reordered array would otherwise still match it.
+        if let Some(i) = p.leaves.windows(2).position(|w| w[0].key > w[1].key) {
+            let mut data = BTreeMap::new();
+            data.insert("index".to_string(), (i + 1).to_string());
+            data.insert("key".to_string(), p.leaves[i + 1].key.clone());
+            findings.push(VerifyFinding {
+                level: VerifyLevel::Error,
+                code: "proof.leaves.unsorted".to_string(),
+                message: format!(
+                    "proof leaves are not sorted by key: {} appears after {}",
+                    p.leaves[i + 1].key,
+                    p.leaves[i].key
+                ),
+                data,
+            });
+        }
 
-        if root != p.root {
-            push(
+        // Extra leaves are not fatal, but may indicate tampering or drift.
+        let unexpected: Vec<&str> = p
+            .leaves
+            .iter()
+            .map(|l| l.key.as_str())
+            .filter(|k| !opts.allowed_leaf_prefixes.iter().any(|pre| k.starts_with(pre.as_str())))
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if !unexpected.is_empty() {
+            let mut data = BTreeMap::new();
+            data.insert("keys".to_string(), unexpected.join(","));
+            findings.push(VerifyFinding {
+                level: VerifyLevel::Warning,
+                code: "proof.leaf.unexpected".to_string(),
+                message: format!("proof contains unexpected leaf keys: {}", unexpected.join(", ")),
+                data,
+            });
+        }
+
+        // Recompute root; the recomputed value is only needed when it differs.
+        if !crate::merkle::verify_root(&proof_merkle_options(p), &proof_leaf_pairs(p), &p.root)? {
+            let computed = recompute_proof_root_hex(p)?;
+            push_digest_mismatch(
                 &mut findings,
-                VerifyLevel::Error,
                 "proof.root.mismatch",
                 "recomputed proof root does not match provided root",
+                &computed,
+                &p.root,
             );
+            proof_root = Some(computed);
         } else {
+            proof_root = Some(p.root.clone());
             push(
                 &mut findings,
                 VerifyLevel::Info,
@@ -216,6 +323,21 @@ pub fn verify_bundle(bundle: VerifyBundle, opts: VerifyOptions) -> SigniaResult<
         if opts.validate_inclusions {
             if let Some(incs) = &p.inclusions {
                 for inc in incs {
+                    let stored = p.leaves.iter().find(|l| l.key == inc.key);
+                    if let Some(leaf) = stored.filter(|l| l.value != inc.value) {
+                        let mut data = BTreeMap::new();
+                        data.insert("key".to_string(), inc.key.clone());
+                        findings.push(VerifyFinding {
+                            level: VerifyLevel::Error,
+                            code: "proof.inclusion.value_mismatch".to_string(),
+                            message: format!(
+                                "inclusion value for {} is {} but the proof leaf is {}",
+                                inc.key, inc.value, leaf.value
+                            ),
+                            data,
+                        });
+                        continue;
+                    }
                     if let Err(e) = verify_inclusion(p, inc) {
                         push(
                             &mut findings,
@@ -342,32 +464,119 @@ fn verify_manifest_structure(manifest: &ManifestV1, findings: &mut Vec<VerifyFin
     if manifest.limits.timeout_ms == 0 {
         push(findings, VerifyLevel::Warning, "manifest.limits.timeoutMs", "timeoutMs is 0");
     }
+    if let Err(e) = manifest.limits.network_policy() {
+        push(findings, VerifyLevel::Error, "manifest.limits.network", e.to_string());
+    }
 
     Ok(())
 }
 
+/// Per-item checks of declared input digests and expected output digests.
+///
+/// Digests are 64 lowercase hex chars (sha256 or blake3). Outputs without an
+/// actual digest in `actuals` are reported as unchecked, not as errors.
+#[cfg(feature = "canonical-json")]
+fn verify_manifest_io_digests(
+    manifest: &ManifestV1,
+    actuals: &BTreeMap<String, String>,
+    findings: &mut Vec<VerifyFinding>,
+) {
+    let item = |index: usize, locator: &str| {
+        let mut data = BTreeMap::new();
+        data.insert("index".to_string(), index.to_string());
+        data.insert("locator".to_string(), locator.to_string());
+        data
+    };
+
+    for (i, input) in manifest.inputs.iter().enumerate() {
+        let Some(d) = &input.digest else { continue };
+        if !is_hex_digest32(d) {
+            findings.push(VerifyFinding {
+                level: VerifyLevel::Error,
+                code: "manifest.input.digest.malformed".to_string(),
+                message: format!(
+                    "manifest.inputs[{i}] ({}) digest is not 64 lowercase hex chars",
+                    input.locator
+                ),
+                data: item(i, &input.locator),
+            });
+        }
+    }
+
+    for (i, output) in manifest.outputs.iter().enumerate() {
+        let Some(expected) = &output.expected_digest else { continue };
+        let mut data = item(i, &output.locator);
+        let (level, code, message) = if !is_hex_digest32(expected) {
+            (
+                VerifyLevel::Error,
+                "manifest.output.digest.malformed",
+                format!(
+                    "manifest.outputs[{i}] ({}) expectedDigest is not 64 lowercase hex chars",
+                    output.locator
+                ),
+            )
+        } else {
+            match actuals.get(&output.locator) {
+                None => (
+                    VerifyLevel::Info,
+                    "manifest.output.digest.unchecked",
+                    format!("no actual digest provided for output {}", output.locator),
+                ),
+                Some(actual) if actual == expected => (
+                    VerifyLevel::Info,
+                    "manifest.output.digest.ok",
+                    format!("output {} matches expected digest", output.locator),
+                ),
+                Some(actual) => {
+                    data.insert("computed".to_string(), actual.clone());
+                    data.insert("expected".to_string(), expected.clone());
+                    (
+                        VerifyLevel::Error,
+                        "manifest.output.digest.mismatch",
+                        format!("output {} digest does not match expectedDigest", output.locator),
+                    )
+                }
+            }
+        };
+        findings.push(VerifyFinding {
+            level,
+            code: code.to_string(),
+            message,
+            data,
+        });
+    }
+}
+
+#[cfg(feature = "canonical-json")]
+fn is_hex_digest32(s: &str) -> bool {
+    s.len() == 64 && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
 /// Recompute a proof root from its leaves.
 ///
 /// This matches the construction in `pipeline::compile` and `pipeline::stages::BuildProofV1Stage`:
 /// - leaf payload: "key=value"
 /// - leaf hash: domain-separated using merkle tree options
+/// - odd levels: folded per `proof.merkle_odd_policy`
+/// - empty leaf set: `hash(MERKLE_EMPTY)`
 #[cfg(feature = "canonical-json")]
 pub fn recompute_proof_root_hex(proof: &ProofV1) -> SigniaResult<String> {
-    let mut leaves = proof.leaves.clone();
-    leaves.sort_by(|a, b| a.key.cmp(&b.key));
+    crate::merkle::keyed_leaves_root_hex(&proof_merkle_options(proof), &proof_leaf_pairs(proof))
+}
 
-    let mut tree = crate::merkle::MerkleTree::new(crate::merkle::MerkleTreeOptions {
+#[cfg(feature = "canonical-json")]
+fn proof_merkle_options(proof: &ProofV1) -> crate::merkle::MerkleTreeOptions {
+    crate::merkle::MerkleTreeOptions {
         hash_alg: proof.hash_alg.clone(),
         domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
         domain_node: crate::domain::MERKLE_NODE.to_string(),
-    });
-
-    for leaf in &leaves {
-        let payload = format!("{}={}", leaf.key, leaf.value);
-        tree.push_leaf(payload.as_bytes())?;
+        odd_policy: proof.merkle_odd_policy,
     }
+}
 
-    tree.root_hex()
+#[cfg(feature = "canonical-json")]
+fn proof_leaf_pairs(proof: &ProofV1) -> Vec<(&str, &str)> {
+    proof.leaves.iter().map(|l| (l.key.as_str(), l.value.as_str())).collect()
 }
 
 /// Verify a single inclusion proof.
@@ -378,18 +587,22 @@ pub fn recompute_proof_root_hex(proof: &ProofV1) -> SigniaResult<String> {
 /// Hashing matches the Merkle tree hashing:
 /// - leaf hash is hash(domain_leaf || payload)
 /// - internal node hash is hash(domain_node || left || right)
+///
+/// The inclusion's `(key, value)` must equal the proof leaf stored under `key`.
+/// This is checked before the path, so value drift fails even if the path
+/// happens to reach `proof.root`.
 #[cfg(feature = "canonical-json")]
 pub fn verify_inclusion(proof: &ProofV1, inc: &InclusionProofV1) -> SigniaResult<()> {
-    // Ensure the leaf exists in proof.leaves
-    let mut found = false;
-    for l in &proof.leaves {
-        if l.key == inc.key && l.value == inc.value {
-            found = true;
-            break;
-        }
-    }
-    if !found {
-        return Err(SigniaError::invalid_argument("inclusion leaf not present in proof"));
+    let leaf = proof
+        .leaves
+        .iter()
+        .find(|l| l.key == inc.key)
+        .ok_or_else(|| SigniaError::invalid_argument(format!("inclusion leaf {} not present in proof", inc.key)))?;
+    if leaf.value != inc.value {
+        return Err(SigniaError::invariant(format!(
+            "inclusion value for {} does not match the stored leaf",
+            inc.key
+        )));
     }
 
     // Start with leaf hash
@@ -397,21 +610,10 @@ pub fn verify_inclusion(proof: &ProofV1, inc: &InclusionProofV1) -> SigniaResult
     let mut h = crate::hash::hash_merkle_leaf_hex(proof.hash_alg.as_str(), payload.as_bytes())?;
 
     for s in &inc.siblings {
-        let side = s.side.as_str();
-        if side != "left" && side != "right" {
-            return Err(SigniaError::invalid_argument("sibling.side must be left or right"));
-        }
-
-        let left;
-        let right;
-
-        if side == "left" {
-            left = s.hash.as_str();
-            right = h.as_str();
-        } else {
-            left = h.as_str();
-            right = s.hash.as_str();
-        }
+        let (left, right) = match s.side {
+            Side::Left => (s.hash.as_str(), h.as_str()),
+            Side::Right => (h.as_str(), s.hash.as_str()),
+        };
 
         h = crate::hash::hash_merkle_node_hex(proof.hash_alg.as_str(), left, right)?;
     }
@@ -427,10 +629,11 @@ pub fn verify_inclusion(proof: &ProofV1, inc: &InclusionProofV1) -> SigniaResult
 #[cfg(feature = "canonical-json")]
 mod tests {
     use super::*;
+    use crate::model::v1::{InputRefV1, OutputRefV1};
     use serde_json::json;
 
-    #[test]
-    fn verify_smoke() {
+    /// Minimal consistent bundle; `extra` leaves are added before the root is computed.
+    fn demo_bundle(extra: Vec<LeafV1>) -> VerifyBundle {
         // Minimal schema
         let schema = SchemaV1 {
             version: "v1".to_string(),
@@ -456,33 +659,33 @@ mod tests {
                 network: "deny".to_string(),
             },
         );
-        // Bind schema digest later
-
+        // Bind the schema digest before hashing the manifest.
         let schema_hash = crate::hash::hash_schema_v1_hex(&schema).unwrap();
-        let manifest_hash = crate::hash::hash_manifest_v1_hex(&manifest).unwrap();
-
         manifest.schemas.push(crate::model::v1::SchemaRefV1 {
             name: "repo".to_string(),
             digest: schema_hash.clone(),
         });
+        let manifest_hash = crate::hash::hash_manifest_v1_hex(&manifest).unwrap();
 
         // Proof
         let mut leaves = vec![
             LeafV1 {
-                key: "digest:schemaHash".to_string(),
+                key: leaf_keys::SCHEMA_HASH.to_string(),
                 value: schema_hash.clone(),
             },
             LeafV1 {
-                key: "digest:manifestHash".to_string(),
+                key: leaf_keys::MANIFEST_HASH.to_string(),
                 value: manifest_hash.clone(),
             },
         ];
+        leaves.extend(extra);
         leaves.sort_by(|a, b| a.key.cmp(&b.key));
 
         let mut tree = crate::merkle::MerkleTree::new(crate::merkle::MerkleTreeOptions {
             hash_alg: "sha256".to_string(),
             domain_leaf: crate::domain::MERKLE_LEAF.to_string(),
             domain_node: crate::domain::MERKLE_NODE.to_string(),
+            odd_policy: crate::merkle::OddNodePolicy::Duplicate,
         });
         for leaf in &leaves {
             let payload = format!("{}={}", leaf.key, leaf.value);
@@ -493,14 +696,406 @@ mod tests {
         let mut proof = ProofV1::new("sha256", root);
         proof.leaves = leaves;
 
-        let bundle = VerifyBundle {
+        VerifyBundle {
             schema,
             manifest,
             proof: Some(proof),
-        };
+            output_digests: BTreeMap::new(),
+        }
+    }
 
-        let rep = verify_bundle(bundle, VerifyOptions::default()).unwrap();
+    fn has_finding(rep: &VerifyReport, code: &str) -> bool {
+        rep.findings.iter().any(|f| f.code == code)
+    }
+
+    /// Re-bind the proof after editing the manifest.
+    fn refresh_proof(bundle: &mut VerifyBundle) {
+        let manifest_hash = crate::hash::hash_manifest_v1_hex(&bundle.manifest).unwrap();
+        let proof = bundle.proof.as_mut().unwrap();
+        for l in &mut proof.leaves {
+            if l.key == leaf_keys::MANIFEST_HASH {
+                l.value = manifest_hash.clone();
+            }
+        }
+        proof.root = recompute_proof_root_hex(proof).unwrap();
+    }
+
+    #[test]
+    fn inclusion_value_must_match_stored_leaf() {
+        let mut b = demo_bundle(vec![]);
+        let mut p = b.proof.take().unwrap();
+        p.leaves.sort_by(|x, y| x.key.cmp(&y.key));
+        let mut tree = crate::merkle::MerkleTree::new(proof_merkle_options(&p));
+        for l in &p.leaves {
+            tree.push_leaf(format!("{}={}", l.key, l.value).as_bytes()).unwrap();
+        }
+        let p = p.with_inclusions(&tree, &[leaf_keys::SCHEMA_HASH]).unwrap();
+        b.proof = Some(p.clone());
+        assert!(verify_bundle(b.clone(), VerifyOptions::default()).unwrap().ok);
+
+        // Altered inclusion value.
+        let mut altered = b.clone();
+        altered.proof.as_mut().unwrap().inclusions.as_mut().unwrap()[0].value = "ff".repeat(32);
+        let rep = verify_bundle(altered, VerifyOptions::default()).unwrap();
+        assert!(!rep.ok);
+        let f = rep.findings.iter().find(|f| f.code == "proof.inclusion.value_mismatch").unwrap();
+        assert_eq!(f.data.get("key").map(String::as_str), Some(leaf_keys::SCHEMA_HASH));
+
+        // Drifted stored leaf: the inclusion path still reaches the root.
+        let mut drifted = p;
+        let i = drifted.leaves.iter().position(|l| l.key == leaf_keys::SCHEMA_HASH).unwrap();
+        drifted.leaves[i].value = "ee".repeat(32);
+        let inc = drifted.inclusions.as_ref().unwrap()[0].clone();
+        let err = verify_inclusion(&drifted, &inc).unwrap_err();
+        assert!(err.to_string().contains("does not match the stored leaf"), "{err}");
+    }
+
+    #[test]
+    fn verify_smoke() {
+        let rep = verify_bundle(demo_bundle(vec![]), VerifyOptions::default()).unwrap();
         assert!(rep.ok);
         assert!(!rep.has_errors());
+        assert!(!has_finding(&rep, "proof.leaf.unexpected"));
+    }
+
+    #[test]
+    #[cfg(feature = "blake3")]
+    fn blake3_proof_verifies_end_to_end() {
+        let mut b = demo_bundle(vec![]);
+        let sha_root = b.proof.as_ref().unwrap().root.clone();
+        let proof = b.proof.as_mut().unwrap();
+        proof.hash_alg = "blake3".to_string();
+
+        // The sha256 root does not verify under blake3.
+        assert!(!verify_bundle(b.clone(), VerifyOptions::default()).unwrap().ok);
+
+        let proof = b.proof.as_mut().unwrap();
+        proof.root = recompute_proof_root_hex(proof).unwrap();
+        assert_ne!(proof.root, sha_root);
+
+        let mut tree = crate::merkle::MerkleTree::new(proof_merkle_options(proof));
+        for l in &proof.leaves {
+            tree.push_leaf(format!("{}={}", l.key, l.value).as_bytes()).unwrap();
+        }
+        assert_eq!(tree.root_hex().unwrap(), proof.root);
+        let p = proof.clone().with_inclusions(&tree, &[leaf_keys::SCHEMA_HASH]).unwrap();
+        b.proof = Some(p);
+
+        let rep = verify_bundle(b, VerifyOptions::default()).unwrap();
+        assert!(rep.ok, "{:?}", rep.findings);
+    }
+
+    #[test]
+    fn merkle_odd_policy_is_taken_from_the_proof() {
+        let extra = vec![LeafV1 {
+            key: leaf_keys::META_KIND.to_string(),
+            value: "c".repeat(64),
+        }];
+        let mut b = demo_bundle(extra);
+        let proof = b.proof.as_mut().unwrap();
+        assert_eq!(proof.leaves.len(), 3);
+        let dup_root = proof.root.clone();
+
+        proof.merkle_odd_policy = crate::merkle::OddNodePolicy::Promote;
+        proof.root = recompute_proof_root_hex(proof).unwrap();
+        assert_ne!(proof.root, dup_root);
+
+        let mut tree = crate::merkle::MerkleTree::new(proof_merkle_options(proof));
+        for l in &proof.leaves {
+            tree.push_leaf(format!("{}={}", l.key, l.value).as_bytes()).unwrap();
+        }
+        let keys: Vec<&str> = proof.leaves.iter().map(|l| l.key.as_str()).collect();
+        let p = proof.clone().with_inclusions(&tree, &keys).unwrap();
+
+        // The policy survives a JSON round trip; duplicate stays implicit.
+        let json = serde_json::to_value(&p).unwrap();
+        assert_eq!(json["merkleOddPolicy"], "promote");
+        let back: ProofV1 = serde_json::from_value(json).unwrap();
+        assert_eq!(back.merkle_odd_policy, crate::merkle::OddNodePolicy::Promote);
+        assert!(serde_json::to_value(ProofV1::new("sha256", "0".repeat(64))).unwrap().get("merkleOddPolicy").is_none());
+
+        b.proof = Some(back);
+        let rep = verify_bundle(b.clone(), VerifyOptions::default()).unwrap();
+        assert!(rep.ok, "{:?}", rep.findings);
+
+        // Dropping the recorded policy makes the root fail to verify.
+        b.proof.as_mut().unwrap().merkle_odd_policy = crate::merkle::OddNodePolicy::Duplicate;
+        let rep = verify_bundle(b, VerifyOptions::default()).unwrap();
+        assert!(has_finding(&rep, "proof.root.mismatch"));
+    }
+
+    #[test]
+    fn unexpected_leaf_is_a_warning() {
+        let expected = LeafV1 {
+            key: leaf_keys::META_KIND.to_string(),
+            value: "00".repeat(32),
+        };
+        let rep = verify_bundle(demo_bundle(vec![expected]), VerifyOptions::default()).unwrap();
+        assert!(!has_finding(&rep, "proof.leaf.unexpected"));
+
+        let unexpected = LeafV1 {
+            key: "extra:payload".to_string(),
+            value: "00".repeat(32),
+        };
+        let rep = verify_bundle(demo_bundle(vec![unexpected.clone()]), VerifyOptions::default()).unwrap();
+        let f = rep.findings.iter().find(|f| f.code == "proof.leaf.unexpected").unwrap();
+        assert!(matches!(f.level, VerifyLevel::Warning));
+        assert_eq!(f.data.get("keys").map(String::as_str), Some("extra:payload"));
+        assert!(rep.ok);
+
+        // Allowing the prefix silences the warning.
+        let mut opts = VerifyOptions::default();
+        opts.allowed_leaf_prefixes.push("extra:".to_string());
+        let rep = verify_bundle(demo_bundle(vec![unexpected]), opts).unwrap();
+        assert!(!has_finding(&rep, "proof.leaf.unexpected"));
+    }
+
+    #[test]
+    fn required_leaf_keys_are_enforced() {
+        let mut opts = VerifyOptions::default();
+        opts.required_leaf_keys.push("meta:source".to_string());
+
+        let rep = verify_bundle(demo_bundle(vec![]), opts.clone()).unwrap();
+        let f = rep.findings.iter().find(|f| f.code == "proof.leaf.required.missing").unwrap();
+        assert!(matches!(f.level, VerifyLevel::Error));
+        assert_eq!(f.data.get("key").map(String::as_str), Some("meta:source"));
+        assert!(!rep.ok);
+
+        let source = LeafV1 {
+            key: "meta:source".to_string(),
+            value: "00".repeat(32),
+        };
+        let rep = verify_bundle(demo_bundle(vec![source]), opts).unwrap();
+        assert!(!has_finding(&rep, "proof.leaf.required.missing"));
+        assert!(rep.ok, "{:?}", rep.findings);
+    }
+
+    #[test]
+    fn digest_mismatches_report_computed_and_expected() {
+        let stale = demo_bundle(vec![]);
+        let leaf = |key: &str| {
+            let p = stale.proof.as_ref().unwrap();
+            p.leaves.iter().find(|l| l.key == key).unwrap().value.clone()
+        };
+
+        let mut bundle = stale.clone();
+        bundle.schema.kind = "dataset".to_string();
+        bundle.manifest.name = "renamed".to_string();
+        bundle.proof.as_mut().unwrap().root = "00".repeat(32);
+        let rep = verify_bundle(bundle.clone(), VerifyOptions::default()).unwrap();
+        assert!(!rep.ok);
+
+        let data = |code: &str| rep.findings.iter().find(|f| f.code == code).unwrap().data.clone();
+        let schema = data("proof.leaf.schemaHash.mismatch");
+        assert_eq!(schema["expected"], leaf(leaf_keys::SCHEMA_HASH));
+        assert_eq!(schema["computed"], crate::hash::hash_schema_v1_hex(&bundle.schema).unwrap());
+
+        let manifest = data("proof.leaf.manifestHash.mismatch");
+        assert_eq!(manifest["expected"], leaf(leaf_keys::MANIFEST_HASH));
+        assert_eq!(manifest["computed"], crate::hash::hash_manifest_v1_hex(&bundle.manifest).unwrap());
+
+        let root = data("proof.root.mismatch");
+        assert_eq!(root["expected"], "00".repeat(32));
+        assert_eq!(root["computed"], stale.proof.as_ref().unwrap().root);
+        assert_eq!(rep.proof_root_hex.as_deref(), Some(root["computed"].as_str()));
+    }
+
+    #[test]
+    fn default_requires_digest_leaves() {
+        let mut bundle = demo_bundle(vec![]);
+        let proof = bundle.proof.as_mut().unwrap();
+        proof.leaves.retain(|l| l.key != leaf_keys::MANIFEST_HASH);
+        proof.root = recompute_proof_root_hex(proof).unwrap();
+
+        let rep = verify_bundle(bundle.clone(), VerifyOptions::default()).unwrap();
+        let missing: Vec<_> = rep
+            .findings
+            .iter()
+            .filter(|f| f.code == "proof.leaf.required.missing")
+            .collect();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].data.get("key").map(String::as_str), Some(leaf_keys::MANIFEST_HASH));
+        assert!(!has_finding(&rep, "proof.leaf.manifestHash.mismatch"));
+
+        // Dropping the requirement makes the absence acceptable.
+        let mut opts = VerifyOptions::default();
+        opts.required_leaf_keys.retain(|k| k != leaf_keys::MANIFEST_HASH);
+        let rep = verify_bundle(bundle, opts).unwrap();
+        assert!(rep.ok, "{:?}", rep.findings);
+    }
+
+    #[test]
+    fn malformed_input_digest_is_reported() {
+        let mut bundle = demo_bundle(vec![]);
+        bundle.manifest.inputs = vec![
+            InputRefV1 {
+                r#type: "path".to_string(),
+                locator: "artifact:/ok".to_string(),
+                digest: Some("ab".repeat(32)),
+            },
+            InputRefV1 {
+                r#type: "path".to_string(),
+                locator: "artifact:/bad".to_string(),
+                digest: Some("AB".repeat(32)),
+            },
+        ];
+        refresh_proof(&mut bundle);
+
+        let rep = verify_bundle(bundle.clone(), VerifyOptions::default()).unwrap();
+        let bad: Vec<_> = rep
+            .findings
+            .iter()
+            .filter(|f| f.code == "manifest.input.digest.malformed")
+            .collect();
+        assert_eq!(bad.len(), 1);
+        assert_eq!(bad[0].data.get("index").map(String::as_str), Some("1"));
+        assert_eq!(bad[0].data.get("locator").map(String::as_str), Some("artifact:/bad"));
+        assert!(!rep.ok);
+
+        let opts = VerifyOptions {
+            verify_io_digests: false,
+            ..VerifyOptions::default()
+        };
+        let rep = verify_bundle(bundle, opts).unwrap();
+        assert!(rep.ok, "{:?}", rep.findings);
+    }
+
+    #[test]
+    fn mismatched_output_digest_is_reported() {
+        let mut bundle = demo_bundle(vec![]);
+        bundle.manifest.outputs = vec![OutputRefV1 {
+            r#type: "schema".to_string(),
+            locator: "artifact:/out/schema.json".to_string(),
+            expected_digest: Some("cd".repeat(32)),
+        }];
+        refresh_proof(&mut bundle);
+
+        let rep = verify_bundle(bundle.clone(), VerifyOptions::default()).unwrap();
+        assert!(has_finding(&rep, "manifest.output.digest.unchecked"));
+        assert!(rep.ok, "{:?}", rep.findings);
+
+        bundle
+            .output_digests
+            .insert("artifact:/out/schema.json".to_string(), "ef".repeat(32));
+        let rep = verify_bundle(bundle.clone(), VerifyOptions::default()).unwrap();
+        let f = rep.findings.iter().find(|f| f.code == "manifest.output.digest.mismatch").unwrap();
+        assert!(matches!(f.level, VerifyLevel::Error));
+        assert_eq!(f.data.get("computed"), Some(&"ef".repeat(32)));
+        assert_eq!(f.data.get("expected"), Some(&"cd".repeat(32)));
+        assert!(!rep.ok);
+
+        bundle
+            .output_digests
+            .insert("artifact:/out/schema.json".to_string(), "cd".repeat(32));
+        let rep = verify_bundle(bundle, VerifyOptions::default()).unwrap();
+        assert!(has_finding(&rep, "manifest.output.digest.ok"));
+        assert!(rep.ok, "{:?}", rep.findings);
+    }
+
+    #[test]
+    fn unknown_network_policy_is_an_error() {
+        for (network, ok) in [("deny", true), ("allow-pinned-only", true), ("Deny", false), ("none", false)] {
+            let mut bundle = demo_bundle(vec![]);
+            bundle.manifest.limits.network = network.to_string();
+            refresh_proof(&mut bundle);
+
+            let rep = verify_bundle(bundle, VerifyOptions::default()).unwrap();
+            assert_eq!(has_finding(&rep, "manifest.limits.network"), !ok, "{network}");
+            assert_eq!(rep.ok, ok, "{network}");
+        }
+    }
+
+    #[test]
+    fn unsorted_leaves_are_rejected() {
+        let mut bundle = demo_bundle(vec![]);
+        bundle.proof.as_mut().unwrap().leaves.reverse();
+
+        let rep = verify_bundle(bundle, VerifyOptions::default()).unwrap();
+        // The root still matches (it is computed over the sorted set); ordering alone fails.
+        assert!(has_finding(&rep, "proof.root.ok"));
+        assert!(has_finding(&rep, "proof.leaves.unsorted"));
+        assert!(!rep.ok);
+    }
+
+    #[test]
+    fn merkle_verify_root_matches_pipeline() {
+        let proof = demo_bundle(vec![]).proof.unwrap();
+        let opts = proof_merkle_options(&proof);
+
+        // Order of the input pairs does not matter; keys are sorted first.
+        let mut pairs = proof_leaf_pairs(&proof);
+        pairs.reverse();
+        assert!(crate::merkle::verify_root(&opts, &pairs, &proof.root).unwrap());
+        assert_eq!(
+            crate::merkle::keyed_leaves_root_hex(&opts, &pairs).unwrap(),
+            recompute_proof_root_hex(&proof).unwrap()
+        );
+        assert!(!crate::merkle::verify_root(&opts, &pairs[1..], &proof.root).unwrap());
+        assert!(crate::merkle::verify_root(&opts, &[], &crate::hash::hash_merkle_empty_hex("sha256").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn empty_leaf_set_has_stable_root() {
+        let proof = ProofV1::new("sha256", crate::hash::hash_merkle_empty_hex("sha256").unwrap());
+        assert!(proof.leaves.is_empty());
+        assert_eq!(recompute_proof_root_hex(&proof).unwrap(), proof.root);
+    }
+
+    #[test]
+    fn empty_repo_compiles_to_verifiable_bundle() {
+        use crate::model::ir::{DefaultIdStrategy, IrGraph, IrNode};
+        use crate::pipeline::compile::{compile_from_ir, CompileRequest, LimitsSpec};
+
+        // An empty repo is a single root node with no files.
+        let mut ir = IrGraph::new();
+        ir.insert_node(IrNode {
+            id: "n1".to_string(),
+            key: "repo:root".to_string(),
+            node_type: "repo".to_string(),
+            name: "empty".to_string(),
+            attrs: BTreeMap::new(),
+            digests: vec![],
+            provenance: None,
+            diagnostics: vec![],
+        })
+        .unwrap();
+
+        let req = CompileRequest {
+            kind: "repo".to_string(),
+            meta: json!({
+                "name":"empty",
+                "createdAt":"1970-01-01T00:00:00Z",
+                "source":{"type":"path","locator":"artifact:/empty"},
+                "normalization":{"policyVersion":"v1","pathRoot":"artifact:/","newline":"lf","encoding":"utf-8","symlinks":"deny","network":"deny"}
+            }),
+            created_at: "1970-01-01T00:00:00Z".to_string(),
+            labels: BTreeMap::new(),
+            inputs: vec![],
+            outputs: vec![],
+            plugins: vec![],
+            limits: LimitsSpec::default(),
+            run_inference: true,
+            build_proof: true,
+        };
+
+        let a = compile_from_ir(ir.clone(), req.clone(), Some(&DefaultIdStrategy::default())).unwrap();
+        let b = compile_from_ir(ir, req, Some(&DefaultIdStrategy::default())).unwrap();
+        assert_eq!(a.bundle.schema.entities.len(), 1);
+        assert!(a.bundle.schema.edges.is_empty());
+
+        let proof = a.bundle.proof.clone().unwrap();
+        assert_eq!(proof.root, b.bundle.proof.clone().unwrap().root);
+
+        let rep = verify_bundle(
+            VerifyBundle {
+                schema: a.bundle.schema,
+                manifest: a.bundle.manifest,
+                proof: Some(proof),
+                output_digests: BTreeMap::new(),
+            },
+            VerifyOptions::default(),
+        )
+        .unwrap();
+        assert!(rep.ok, "{:?}", rep.findings);
     }
 }
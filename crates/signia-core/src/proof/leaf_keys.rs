@@ -0,0 +1,140 @@
+//! Canonical `LeafV1` key namespace.
+//!
+//! Every leaf key emitted by `pipeline::compile` and every key checked by
+//! `pipeline::verify` comes from here, so construction and verification cannot
+//! drift apart. Keys are `<namespace>:<name>`:
+//! - `digest:` digests of bundle components (schema, manifest)
+//! - `meta:` hashed request metadata kept for traceability
+//!
+//! Changing a key changes every proof root; treat these as part of the v1 wire format.
+
+/// Namespace for component digests.
+pub const DIGEST_NS: &str = "digest:";
+
+/// Namespace for hashed request metadata.
+pub const META_NS: &str = "meta:";
+
+/// Canonical schema digest.
+pub const SCHEMA_HASH: &str = "digest:schemaHash";
+
+/// Canonical manifest digest.
+pub const MANIFEST_HASH: &str = "digest:manifestHash";
+
+/// Hash of the compile request kind.
+pub const META_KIND: &str = "meta:kind";
+
+/// Hash of the compile request `createdAt`.
+pub const META_CREATED_AT: &str = "meta:createdAt";
+
+/// Keys emitted by `pipeline::compile`, in proof (sorted) order.
+pub const COMPILE_KEYS: [&str; 4] = [MANIFEST_HASH, SCHEMA_HASH, META_CREATED_AT, META_KIND];
+
+/// Default `VerifyOptions::allowed_leaf_prefixes`.
+pub const VERIFY_ALLOWED_PREFIXES: [&str; 3] = [SCHEMA_HASH, MANIFEST_HASH, META_NS];
+
+/// Default `VerifyOptions::required_leaf_keys`.
+pub const VERIFY_REQUIRED_KEYS: [&str; 2] = [SCHEMA_HASH, MANIFEST_HASH];
+
+// Compile-time guards: every emitted key is namespaced, accepted by the default
+// verify prefixes, and listed in the order compile stores leaves; every key
+// verify requires by default is one compile emits.
+const _: () = {
+    let mut i = 0;
+    while i < COMPILE_KEYS.len() {
+        let k = COMPILE_KEYS[i];
+        assert!(
+            starts_with(k, DIGEST_NS) || starts_with(k, META_NS),
+            "leaf key outside the digest:/meta: namespaces"
+        );
+        assert!(allowed_by_default(k), "leaf key rejected by default verify prefixes");
+        if i > 0 {
+            assert!(less(COMPILE_KEYS[i - 1], k), "COMPILE_KEYS must be sorted");
+        }
+        i += 1;
+    }
+    let mut r = 0;
+    while r < VERIFY_REQUIRED_KEYS.len() {
+        assert!(emitted(VERIFY_REQUIRED_KEYS[r]), "required leaf key not emitted by compile");
+        r += 1;
+    }
+};
+
+const fn emitted(key: &str) -> bool {
+    let mut i = 0;
+    while i < COMPILE_KEYS.len() {
+        if eq(COMPILE_KEYS[i], key) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && starts_with(a, b)
+}
+
+const fn allowed_by_default(key: &str) -> bool {
+    let mut i = 0;
+    while i < VERIFY_ALLOWED_PREFIXES.len() {
+        if starts_with(key, VERIFY_ALLOWED_PREFIXES[i]) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn starts_with(s: &str, prefix: &str) -> bool {
+    let (s, p) = (s.as_bytes(), prefix.as_bytes());
+    if p.len() > s.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < p.len() {
+        if s[i] != p[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Byte-wise `a < b`, matching `str` ordering.
+const fn less(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+        i += 1;
+    }
+    a.len() < b.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_valid_and_sorted() {
+        for k in COMPILE_KEYS {
+            let (ns, name) = k.split_once(':').unwrap();
+            assert!(!ns.is_empty() && !name.is_empty(), "{k}");
+            assert!(allowed_by_default(k), "{k}");
+        }
+        let mut sorted = COMPILE_KEYS;
+        sorted.sort();
+        assert_eq!(sorted, COMPILE_KEYS);
+    }
+
+    #[test]
+    fn const_helpers_match_std() {
+        for (a, b) in [("digest:a", "digest:b"), ("meta:", "meta:kind"), ("a", "a"), ("b", "a")] {
+            assert_eq!(less(a, b), a < b, "{a} {b}");
+            assert_eq!(starts_with(b, a), b.starts_with(a), "{a} {b}");
+            assert_eq!(eq(a, b), a == b, "{a} {b}");
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Proof construction helpers shared by compile and verify.
+
+pub mod leaf_keys;
+
+/// Canonical string form of a leaf value.
+///
+/// Decimal integers (sizes, counts) are rewritten to one spelling so the same
+/// logical value always yields the same leaf:
+/// - leading `+` and leading zeros are dropped (`"+007"` -> `"7"`, `"-0"` -> `"0"`)
+/// - `,` thousands separators are removed when correctly grouped (`"1,024"` -> `"1024"`)
+///
+/// Digits are never parsed into a machine integer, so arbitrarily large values are kept
+/// exactly. Everything else is returned unchanged, including 32/64-char hex digests
+/// that happen to be all decimal digits (their leading zeros are significant).
+pub fn normalize_leaf_value(raw: &str) -> String {
+    if is_hex_digest(raw) {
+        return raw.to_string();
+    }
+
+    let (negative, body) = match raw.as_bytes().first() {
+        Some(b'-') => (true, &raw[1..]),
+        Some(b'+') => (false, &raw[1..]),
+        _ => (false, raw),
+    };
+
+    let Some(digits) = decimal_digits(body) else {
+        return raw.to_string();
+    };
+
+    let trimmed = digits.trim_start_matches('0');
+    if trimmed.is_empty() {
+        "0".to_string()
+    } else if negative {
+        format!("-{trimmed}")
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Digits of a plain (`1234`) or comma-grouped (`1,234`) decimal, else None.
+fn decimal_digits(s: &str) -> Option<String> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit() || b == b',') {
+        return None;
+    }
+    if !s.contains(',') {
+        return Some(s.to_string());
+    }
+
+    let mut groups = s.split(',');
+    let first = groups.next()?;
+    if first.is_empty() || first.len() > 3 {
+        return None;
+    }
+    let mut out = first.to_string();
+    for g in groups {
+        if g.len() != 3 {
+            return None;
+        }
+        out.push_str(g);
+    }
+    Some(out)
+}
+
+fn is_hex_digest(s: &str) -> bool {
+    (s.len() == 32 || s.len() == 64) && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_spellings_collapse() {
+        for raw in ["7", "007", "+7", "+0007"] {
+            assert_eq!(normalize_leaf_value(raw), "7", "{raw}");
+        }
+        assert_eq!(normalize_leaf_value("1,234,567"), "1234567");
+        assert_eq!(normalize_leaf_value("0"), "0");
+        assert_eq!(normalize_leaf_value("000"), "0");
+        assert_eq!(normalize_leaf_value("-0"), "0");
+        assert_eq!(normalize_leaf_value("-042"), "-42");
+
+        let big = "123456789012345678901234567890123456789";
+        assert_eq!(normalize_leaf_value(&format!("000{big}")), big);
+    }
+
+    #[test]
+    fn non_numeric_values_are_unchanged() {
+        let digest = "0".repeat(63) + "1";
+        for raw in [
+            digest.as_str(),
+            "00ff00ff00ff00ff00ff00ff00ff00ff",
+            "",
+            "-",
+            "1,23",
+            "1234,567",
+            ",123",
+            "1.5",
+            " 7",
+            "7a",
+            "README.md",
+        ] {
+            assert_eq!(normalize_leaf_value(raw), raw, "{raw:?}");
+        }
+    }
+}
@@ -9,6 +9,7 @@ use crate::errors::{SigniaError, SigniaResult};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SchemaVersion {
     V1,
+    V2,
 }
 
 impl SchemaVersion {
@@ -16,6 +17,7 @@ impl SchemaVersion {
     pub fn parse(s: &str) -> SigniaResult<Self> {
         match s {
             "v1" => Ok(Self::V1),
+            "v2" => Ok(Self::V2),
             _ => Err(SigniaError::invalid_argument(format!(
                 "unsupported schema version: {s}"
             ))),
@@ -26,6 +28,7 @@ impl SchemaVersion {
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::V1 => "v1",
+            Self::V2 => "v2",
         }
     }
 }
@@ -98,6 +101,7 @@ mod tests {
     #[test]
     fn parse_schema_v1() {
         assert_eq!(SchemaVersion::parse("v1").unwrap(), SchemaVersion::V1);
+        assert_eq!(SchemaVersion::parse("v2").unwrap().as_str(), "v2");
     }
 
     #[test]
@@ -19,7 +19,9 @@
 use anyhow::{anyhow, Result};
 use serde_json::Value;
 
-use signia_core::determinism::canonical_json::canonicalize_json;
+use signia_core::determinism::canonical_json::{
+    canonicalize_json, to_canonical_bytes, to_canonical_bytes_with, CanonicalJsonOptions,
+};
 
 /// Normalize a plugin id.
 ///
@@ -110,17 +112,26 @@ pub fn canonicalize_json_value(v: &Value) -> Result<Value> {
 }
 
 /// Canonicalize and serialize a JSON value to bytes deterministically.
+///
+/// Delegates to core's `to_canonical_bytes`, the single definition of canonical
+/// bytes, and fails on values it cannot canonicalize (non-finite numbers).
+///
+/// Note that `serde_json` turns a Rust NaN/infinity into `null` when building a
+/// `Value` (`json!(f64::NAN)`, `to_value`); reject those before converting if
+/// `null` is not an acceptable stand-in.
 pub fn canonical_json_bytes(v: &Value) -> Result<Vec<u8>> {
-    let c = canonicalize_json(v)?;
-    // serde_json serialization is deterministic for canonicalized structure
-    // (sorted object keys, stable arrays).
-    let bytes = serde_json::to_vec(&c)?;
-    Ok(bytes)
+    to_canonical_bytes(v).map_err(|e| anyhow!("canonical json: {e}"))
+}
+
+/// `canonical_json_bytes` with explicit options (e.g. a float policy).
+pub fn canonical_json_bytes_with(v: &Value, opts: &CanonicalJsonOptions) -> Result<Vec<u8>> {
+    to_canonical_bytes_with(v, opts).map_err(|e| anyhow!("canonical json: {e}"))
 }
 
 /// Normalize a small user-provided JSON payload before hashing.
 ///
-/// This is useful for caching and reproducibility.
+/// This is useful for caching and reproducibility. Non-integer floats are
+/// rejected (the default `FloatPolicy::Reject`).
 pub fn normalize_payload_for_hashing(v: &Value) -> Result<Vec<u8>> {
     canonical_json_bytes(v)
 }
@@ -155,4 +166,29 @@ mod tests {
         let s = String::from_utf8(bytes).unwrap();
         assert!(s.find(r#""a":2"#).unwrap() < s.find(r#""b":1"#).unwrap());
     }
+
+    #[test]
+    fn canonical_json_bytes_match_core() {
+        let v = json!({"b": [1.0, -0.0, 2e2], "a": {"d": 1, "c": null}});
+        assert_eq!(canonical_json_bytes(&v).unwrap(), to_canonical_bytes(&v).unwrap());
+        assert_eq!(canonical_json_bytes(&v).unwrap(), br#"{"a":{"c":null,"d":1},"b":[1,0,200]}"#);
+    }
+
+    #[test]
+    fn payload_hashing_rejects_fractional_floats() {
+        assert!(normalize_payload_for_hashing(&json!({"x": 2.5})).is_err());
+        assert_eq!(normalize_payload_for_hashing(&json!({"x": 2.0})).unwrap(), br#"{"x":2}"#);
+    }
+
+    #[test]
+    fn nan_never_hashes_as_a_number() {
+        // NaN cannot be written as JSON text...
+        assert!(serde_json::from_str::<Value>(r#"{"x":NaN}"#).is_err());
+        assert!(serde_json::from_str::<Value>(r#"{"x":1e400}"#).is_err());
+        // ...and serde_json maps a Rust NaN to null, the same bytes every time.
+        for _ in 0..3 {
+            let bytes = canonical_json_bytes(&json!({"x": f64::NAN, "y": f64::INFINITY})).unwrap();
+            assert_eq!(bytes, br#"{"x":null,"y":null}"#);
+        }
+    }
 }
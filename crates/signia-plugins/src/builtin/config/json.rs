@@ -35,6 +35,7 @@ pub fn merge_configs(base: BuiltinConfig, override_cfg: BuiltinConfig) -> Builti
         repo: merge_repo(base.repo, override_cfg.repo),
         dataset: merge_dataset(base.dataset, override_cfg.dataset),
         workflow: merge_workflow(base.workflow, override_cfg.workflow),
+        openapi: merge_openapi(base.openapi, override_cfg.openapi),
         api: merge_api(base.api, override_cfg.api),
     }
 }
@@ -63,6 +64,14 @@ fn merge_workflow(base: super::WorkflowConfig, o: super::WorkflowConfig) -> supe
         max_nodes: o.max_nodes,
         max_edges: o.max_edges,
         enable_yaml: o.enable_yaml,
+        max_meta_value_bytes: o.max_meta_value_bytes,
+        forbid_isolated: o.forbid_isolated,
+    }
+}
+
+fn merge_openapi(_base: super::OpenApiConfig, o: super::OpenApiConfig) -> super::OpenApiConfig {
+    super::OpenApiConfig {
+        max_ref_depth: o.max_ref_depth,
     }
 }
 
@@ -15,6 +15,9 @@
 
 #![cfg(feature = "builtin")]
 
+pub mod json;
+pub mod schema_detect;
+
 use serde::{Deserialize, Serialize};
 
 /// Built-in configuration root.
@@ -29,6 +32,8 @@ pub struct BuiltinConfig {
     #[serde(default)]
     pub workflow: WorkflowConfig,
     #[serde(default)]
+    pub openapi: OpenApiConfig,
+    #[serde(default)]
     pub api: ApiConfig,
 }
 
@@ -38,6 +43,7 @@ impl Default for BuiltinConfig {
             repo: RepoConfig::default(),
             dataset: DatasetConfig::default(),
             workflow: WorkflowConfig::default(),
+            openapi: OpenApiConfig::default(),
             api: ApiConfig::default(),
         }
     }
@@ -151,6 +157,14 @@ pub struct WorkflowConfig {
     /// Whether YAML parsing is enabled in hosts that support YAML.
     #[serde(default)]
     pub enable_yaml: bool,
+
+    /// Maximum bytes of a single node `meta` value once stringified.
+    #[serde(default = "WorkflowConfig::default_max_meta_value_bytes")]
+    pub max_meta_value_bytes: usize,
+
+    /// Fail instead of warning when a multi-node workflow has nodes without edges.
+    #[serde(default)]
+    pub forbid_isolated: bool,
 }
 
 impl Default for WorkflowConfig {
@@ -159,6 +173,8 @@ impl Default for WorkflowConfig {
             max_nodes: Self::default_max_nodes(),
             max_edges: Self::default_max_edges(),
             enable_yaml: true,
+            max_meta_value_bytes: Self::default_max_meta_value_bytes(),
+            forbid_isolated: false,
         }
     }
 }
@@ -170,6 +186,31 @@ impl WorkflowConfig {
     fn default_max_edges() -> usize {
         400_000
     }
+    fn default_max_meta_value_bytes() -> usize {
+        16 * 1024 // 16 KiB
+    }
+}
+
+/// OpenAPI plugin configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenApiConfig {
+    /// Maximum `$ref` hops followed when a component is itself a `$ref`.
+    #[serde(default = "OpenApiConfig::default_max_ref_depth")]
+    pub max_ref_depth: usize,
+}
+
+impl Default for OpenApiConfig {
+    fn default() -> Self {
+        Self {
+            max_ref_depth: Self::default_max_ref_depth(),
+        }
+    }
+}
+
+impl OpenApiConfig {
+    fn default_max_ref_depth() -> usize {
+        32
+    }
 }
 
 /// Built-in API configuration for hosts.
@@ -212,6 +253,7 @@ mod tests {
         assert!(c.repo.max_files > 0);
         assert!(c.dataset.max_files > 0);
         assert!(c.workflow.max_nodes > 0);
+        assert!(c.openapi.max_ref_depth > 0);
         assert!(c.api.enabled);
     }
 
@@ -20,7 +20,7 @@ use std::collections::BTreeMap;
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 
-use signia_core::determinism::hashing::hash_bytes_hex;
+use signia_core::determinism::hashing::{hash_bytes_hex, HashAlg, Hasher};
 use signia_core::determinism::merkle::{merkle_root_hex, MerkleLeaf};
 
 use crate::builtin::repo::tree_walk::normalize_repo_path;
@@ -79,6 +79,25 @@ pub fn ensure_file_sha256(record: &mut DatasetFileRecord) -> Result<()> {
     Ok(())
 }
 
+/// Like `ensure_file_sha256`, but hashes content streamed in `chunks` so the
+/// host never holds a whole file in memory. Sets `sha256` and `size`; any
+/// `bytes` or precomputed `sha256` on the record are replaced.
+pub fn ensure_file_sha256_chunked<'a>(
+    record: &mut DatasetFileRecord,
+    chunks: impl IntoIterator<Item = &'a [u8]>,
+) -> Result<()> {
+    let mut h = Hasher::new(HashAlg::Sha256);
+    let mut size = 0u64;
+    for chunk in chunks {
+        h.update(chunk);
+        size += chunk.len() as u64;
+    }
+    record.bytes = None;
+    record.sha256 = Some(h.finalize_hex());
+    record.size = size;
+    Ok(())
+}
+
 /// Compute per-file sha256 map keyed by normalized path.
 ///
 /// Returns: BTreeMap<path, sha256>
@@ -165,6 +184,19 @@ mod tests {
         assert_eq!(f1, f2);
     }
 
+    #[test]
+    fn chunked_sha256_matches_bytes() {
+        let data = b"hello, chunked dataset".to_vec();
+        let mut whole = DatasetFileRecord::new("a.txt", 0).with_bytes(data.clone());
+        ensure_file_sha256(&mut whole).unwrap();
+
+        let mut chunked = DatasetFileRecord::new("a.txt", 0);
+        ensure_file_sha256_chunked(&mut chunked, data.chunks(5)).unwrap();
+        assert_eq!(chunked.sha256, whole.sha256);
+        assert_eq!(chunked.size, whole.size);
+        assert!(chunked.bytes.is_none());
+    }
+
     #[test]
     fn merkle_root_stable() {
         let a = DatasetFileRecord::new("x.txt", 1).with_bytes(b"x".to_vec());
@@ -7,8 +7,13 @@
 //! - validate dataset metadata and files list
 //! - build a deterministic IR that represents dataset structure
 //! - compute a stable dataset fingerprint (hash)
+//! - attach per-column stats for CSV files with inline `content` (see `stats`)
 //! - attach metadata to PipelineContext for downstream compilation
 //!
+//! Configuration (`DatasetConfig`, see `register_with_config`):
+//! - `max_files` bounds the number of files
+//! - `enable_merkle` adds a `datasetMerkleRoot` over `(path, size)` leaves
+//!
 //! Non-responsibilities:
 //! - downloading datasets
 //! - decompressing archives
@@ -18,34 +23,55 @@
 
 #![cfg(feature = "builtin")]
 
+pub mod stats;
+
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use serde_json::Value;
 
 use signia_core::determinism::hashing::hash_bytes_hex;
-use signia_core::model::ir::{IrEdge, IrGraph, IrNode};
+use signia_core::determinism::merkle::{keyed_leaves_root_hex, MerkleTreeOptions, OddNodePolicy};
+use signia_core::model::ir::{IrEdge, IrGraph, IrId, IrNode};
 use signia_core::pipeline::context::PipelineContext;
 
+use crate::builtin::config::DatasetConfig;
 use crate::plugin::{Plugin, PluginInput, PluginOutput};
 use crate::registry::PluginRegistry;
 use crate::spec::PluginSpec;
 
-/// Register the dataset plugin.
+/// Register the dataset plugin with the default `DatasetConfig`.
 pub fn register(registry: &mut PluginRegistry) {
+    register_with_config(registry, DatasetConfig::default());
+}
+
+/// Register the dataset plugin with `config`.
+pub fn register_with_config(registry: &mut PluginRegistry, config: DatasetConfig) {
     let spec = PluginSpec::new("builtin.dataset", "Dataset Plugin", "0.1.0")
         .support("dataset")
         .limit("max_nodes", 300_000)
         .limit("max_edges", 600_000)
+        .limit("max_files", config.max_files as u64)
         .want("network", false)
         .want("filesystem", false)
         .meta("category", "data");
 
     registry
-        .register(spec, Box::new(DatasetPlugin))
+        .register(spec, Box::new(DatasetPlugin::new(config)))
         .expect("failed to register builtin.dataset");
 }
 
 /// Dataset plugin implementation.
-pub struct DatasetPlugin;
+#[derive(Debug, Clone, Default)]
+pub struct DatasetPlugin {
+    pub config: DatasetConfig,
+}
+
+impl DatasetPlugin {
+    pub fn new(config: DatasetConfig) -> Self {
+        Self { config }
+    }
+}
 
 impl Plugin for DatasetPlugin {
     fn name(&self) -> &str {
@@ -60,18 +86,18 @@ impl Plugin for DatasetPlugin {
         input_type == "dataset"
     }
 
-    fn execute(&self, input: &PluginInput) -> Result<PluginOutput> {
+    fn execute(&self, input: PluginInput<'_>) -> Result<PluginOutput> {
         let ctx = match input {
             PluginInput::Pipeline(ctx) => ctx,
             _ => anyhow::bail!("dataset plugin requires pipeline input"),
         };
 
-        execute_dataset(ctx)?;
+        execute_dataset(ctx, &self.config)?;
         Ok(PluginOutput::None)
     }
 }
 
-fn execute_dataset(ctx: &mut PipelineContext) -> Result<()> {
+fn execute_dataset(ctx: &mut PipelineContext, config: &DatasetConfig) -> Result<()> {
     let meta = ctx
         .inputs
         .get("dataset")
@@ -83,16 +109,26 @@ fn execute_dataset(ctx: &mut PipelineContext) -> Result<()> {
         .get("files")
         .and_then(|v| v.as_array())
         .ok_or_else(|| anyhow::anyhow!("dataset.files missing or invalid"))?;
+    if files.len() > config.max_files {
+        return Err(anyhow::anyhow!(
+            "dataset has {} files (max_files {})",
+            files.len(),
+            config.max_files
+        ));
+    }
 
     let mut graph = IrGraph::new();
 
     let root = IrNode::new("dataset", name);
-    let root_id = graph.add_node(root);
+    let root_id = graph.add_node(root)?;
 
     // Add version node
     let ver_node = IrNode::new("version", version);
-    let ver_id = graph.add_node(ver_node);
-    graph.add_edge(IrEdge::new(root_id, ver_id, "version"));
+    let ver_id = graph.add_node(ver_node)?;
+    graph.add_edge(IrEdge::new(root_id.as_str(), ver_id, "version"))?;
+
+    // Size nodes are shared by files of equal size.
+    let mut sizes: BTreeMap<u64, IrId> = BTreeMap::new();
 
     // Add file nodes
     for f in files {
@@ -100,13 +136,33 @@ fn execute_dataset(ctx: &mut PipelineContext) -> Result<()> {
         let size = f.get("size").and_then(|v| v.as_u64()).unwrap_or(0);
 
         let node = IrNode::new("file", path);
-        let file_id = graph.add_node(node);
-        graph.add_edge(IrEdge::new(root_id, file_id, "contains"));
+        let file_id = graph.add_node(node)?;
+        graph.add_edge(IrEdge::new(root_id.as_str(), file_id.as_str(), "contains"))?;
 
         // Attach size as a scalar node (keeps IR simple and deterministic)
-        let size_node = IrNode::new("size", size.to_string());
-        let size_id = graph.add_node(size_node);
-        graph.add_edge(IrEdge::new(file_id, size_id, "has"));
+        let size_id = match sizes.get(&size) {
+            Some(id) => id.clone(),
+            None => {
+                let id = graph.add_node(IrNode::new("size", size.to_string()))?;
+                sizes.insert(size, id.clone());
+                id
+            }
+        };
+        graph.add_edge(IrEdge::new(file_id.as_str(), size_id, "has"))?;
+
+        // Column nodes with stats, in header order. Keys are scoped to the file
+        // so files with the same header do not collide.
+        if path.to_ascii_lowercase().ends_with(".csv") {
+            if let Some(content) = f.get("content").and_then(|v| v.as_str()) {
+                for col in stats::csv_column_stats(content)? {
+                    let mut node = IrNode::new("column", col.name.as_str());
+                    node.key = IrNode::make_key("column", &format!("{path}#{}", col.name));
+                    node.attrs = col.to_attrs();
+                    let col_id = graph.add_node(node)?;
+                    graph.add_edge(IrEdge::new(file_id.as_str(), col_id, "column"))?;
+                }
+            }
+        }
     }
 
     // Compute a stable dataset fingerprint:
@@ -119,6 +175,22 @@ fn execute_dataset(ctx: &mut PipelineContext) -> Result<()> {
     }
     entries.sort_by(|a, b| a.0.cmp(&b.0));
 
+    if config.enable_merkle {
+        let sizes: Vec<String> = entries.iter().map(|(_, s)| s.to_string()).collect();
+        let leaves: Vec<(&str, &str)> = entries
+            .iter()
+            .zip(&sizes)
+            .map(|((p, _), s)| (p.as_str(), s.as_str()))
+            .collect();
+        let opts = MerkleTreeOptions {
+            hash_alg: "sha256".to_string(),
+            domain_leaf: signia_core::domain::MERKLE_LEAF.to_string(),
+            domain_node: signia_core::domain::MERKLE_NODE.to_string(),
+            odd_policy: OddNodePolicy::Duplicate,
+        };
+        ctx.set_metadata_checked("datasetMerkleRoot", keyed_leaves_root_hex(&opts, &leaves)?)?;
+    }
+
     let mut buf = Vec::new();
     for (p, s) in entries {
         buf.extend_from_slice(p.as_bytes());
@@ -128,8 +200,7 @@ fn execute_dataset(ctx: &mut PipelineContext) -> Result<()> {
     }
     let fingerprint = hash_bytes_hex(&buf)?;
 
-    ctx.metadata
-        .insert("datasetFingerprint".to_string(), Value::String(fingerprint));
+    ctx.set_metadata_checked("datasetFingerprint", fingerprint)?;
 
     ctx.ir = Some(graph);
     Ok(())
@@ -145,6 +216,7 @@ fn get_str<'a>(v: &'a Value, key: &str) -> Result<&'a str> {
 mod tests {
     use super::*;
     use serde_json::json;
+    use signia_core::model::ir::IrValue;
     use signia_core::pipeline::context::PipelineConfig;
 
     #[test]
@@ -162,10 +234,115 @@ mod tests {
             }),
         );
 
-        let plugin = DatasetPlugin;
-        plugin.execute(&PluginInput::Pipeline(&mut ctx)).unwrap();
+        let plugin = DatasetPlugin::default();
+        plugin.execute(PluginInput::Pipeline(&mut ctx)).unwrap();
 
         assert!(ctx.ir.is_some());
         assert!(ctx.metadata.get("datasetFingerprint").is_some());
     }
+
+    #[test]
+    fn csv_content_adds_column_nodes_with_stats() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert(
+            "dataset".to_string(),
+            json!({
+                "name": "prices",
+                "version": "v1",
+                "files": [
+                    { "path": "prices.csv", "size": 20, "content": "sku,price\na,2\nb,\n" }
+                ]
+            }),
+        );
+        DatasetPlugin::default().execute(PluginInput::Pipeline(&mut ctx)).unwrap();
+
+        let graph = ctx.ir.unwrap();
+        let price = graph
+            .nodes
+            .values()
+            .find(|n| n.node_type == "column" && n.name == "price")
+            .expect("price column node");
+        assert_eq!(price.attrs.get("nullCount"), Some(&IrValue::I64(1)));
+        assert_eq!(price.attrs.get("max"), Some(&IrValue::I64(2)));
+    }
+
+    #[test]
+    fn equal_sizes_and_shared_headers_do_not_collide() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert(
+            "dataset".to_string(),
+            json!({
+                "name": "prices",
+                "files": [
+                    { "path": "a.csv", "size": 7, "content": "sku,price\na,2\n" },
+                    { "path": "b.csv", "size": 7, "content": "sku,price\nb,3\n" }
+                ]
+            }),
+        );
+        DatasetPlugin::default().execute(PluginInput::Pipeline(&mut ctx)).unwrap();
+
+        let graph = ctx.ir.unwrap();
+        let count = |t: &str| graph.nodes.values().filter(|n| n.node_type == t).count();
+        assert_eq!(count("size"), 1);
+        assert_eq!(count("column"), 4);
+        graph.validate_basic().unwrap();
+    }
+
+    #[test]
+    fn empty_dataset_emits_single_root() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert(
+            "dataset".to_string(),
+            json!({ "name": "empty", "version": "v1", "files": [] }),
+        );
+        DatasetPlugin::default().execute(PluginInput::Pipeline(&mut ctx)).unwrap();
+
+        // Root plus its version node; no file nodes.
+        let graph = ctx.ir.unwrap();
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 1);
+
+        // Fingerprint of an empty file list is the hash of zero bytes.
+        assert_eq!(
+            ctx.metadata.get("datasetFingerprint"),
+            Some(&hash_bytes_hex(b"").unwrap())
+        );
+    }
+
+    fn dataset_input() -> Value {
+        json!({
+            "name": "d",
+            "version": "v1",
+            "files": [
+                { "path": "a.jsonl", "size": 1 },
+                { "path": "b.jsonl", "size": 2 },
+                { "path": "c.jsonl", "size": 3 }
+            ]
+        })
+    }
+
+    #[test]
+    fn max_files_is_enforced() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert("dataset".to_string(), dataset_input());
+        let plugin = DatasetPlugin::new(DatasetConfig { max_files: 2, ..DatasetConfig::default() });
+        let err = plugin.execute(PluginInput::Pipeline(&mut ctx)).unwrap_err();
+        assert_eq!(err.to_string(), "dataset has 3 files (max_files 2)");
+    }
+
+    #[test]
+    fn merkle_root_follows_enable_merkle() {
+        let run = |enable_merkle: bool| {
+            let mut ctx = PipelineContext::new(PipelineConfig::default());
+            ctx.inputs.insert("dataset".to_string(), dataset_input());
+            let plugin = DatasetPlugin::new(DatasetConfig { enable_merkle, ..DatasetConfig::default() });
+            plugin.execute(PluginInput::Pipeline(&mut ctx)).unwrap();
+            ctx.metadata
+        };
+
+        let with = run(true);
+        let root = with.get("datasetMerkleRoot").unwrap();
+        assert_eq!(root.len(), 64);
+        assert!(!run(false).contains_key("datasetMerkleRoot"));
+    }
 }
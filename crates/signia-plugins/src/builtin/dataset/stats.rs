@@ -0,0 +1,223 @@
+//! Deterministic per-column statistics for CSV datasets.
+//!
+//! Stats are computed from host-provided CSV content and attached to `column`
+//! IR nodes as typed attrs:
+//! - `count` (rows), `nullCount`, `distinctCount` (distinct non-null raw values)
+//! - `min` / `max` for numeric columns only
+//!
+//! Determinism rules:
+//! - a cell is null if it is empty or `null` (any case), the same rule as schema
+//!   inference; cells missing from short rows are null too
+//! - a column is numeric if every non-null cell parses as an i64 (`min`/`max` are
+//!   I64) or as a finite f64; NaN/inf make it non-numeric
+//! - float `min`/`max` are attached as strings in Rust's shortest round-trip
+//!   form (`"2.5"`, `"-0"`), since canonical JSON hashing rejects non-integer
+//!   floats
+//! - floats are compared with `f64::total_cmp`, so ordering is total
+//! - distinct values are counted on trimmed raw strings ("1" and "1.0" differ)
+
+#![cfg(feature = "builtin")]
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, Result};
+use signia_core::model::ir::IrValue;
+
+/// Min/max of a numeric column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericRange {
+    Int { min: i64, max: i64 },
+    Float { min: f64, max: f64 },
+}
+
+/// Statistics for one CSV column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    pub name: String,
+    pub count: u64,
+    pub null_count: u64,
+    pub distinct_count: u64,
+    /// `None` unless every non-null value is numeric (and there is at least one).
+    pub range: Option<NumericRange>,
+}
+
+impl ColumnStats {
+    /// Typed IR attrs for a `column` node.
+    pub fn to_attrs(&self) -> BTreeMap<String, IrValue> {
+        let mut attrs = BTreeMap::new();
+        attrs.insert("count".to_string(), IrValue::I64(self.count as i64));
+        attrs.insert("nullCount".to_string(), IrValue::I64(self.null_count as i64));
+        attrs.insert("distinctCount".to_string(), IrValue::I64(self.distinct_count as i64));
+        match self.range {
+            Some(NumericRange::Int { min, max }) => {
+                attrs.insert("min".to_string(), IrValue::I64(min));
+                attrs.insert("max".to_string(), IrValue::I64(max));
+            }
+            Some(NumericRange::Float { min, max }) => {
+                attrs.insert("min".to_string(), IrValue::String(min.to_string()));
+                attrs.insert("max".to_string(), IrValue::String(max.to_string()));
+            }
+            None => {}
+        }
+        attrs
+    }
+}
+
+/// Compute stats for every column of a CSV document, in header order.
+pub fn csv_column_stats(text: &str) -> Result<Vec<ColumnStats>> {
+    let mut lines = text.lines();
+    let header = lines.next().ok_or_else(|| anyhow!("csv content has no header"))?;
+    let names: Vec<String> = header.split(',').map(|s| s.trim().to_string()).collect();
+    if names.iter().any(|c| c.is_empty()) {
+        return Err(anyhow!("csv header invalid"));
+    }
+
+    let mut acc: Vec<ColumnAcc> = names.iter().map(|_| ColumnAcc::default()).collect();
+    let mut rows = 0u64;
+
+    for line in lines {
+        let l = line.trim();
+        if l.is_empty() {
+            continue;
+        }
+        let cells: Vec<&str> = l.split(',').collect();
+        for (i, a) in acc.iter_mut().enumerate() {
+            a.push(cells.get(i).map(|c| c.trim()).unwrap_or(""));
+        }
+        rows += 1;
+    }
+
+    Ok(names
+        .into_iter()
+        .zip(acc)
+        .map(|(name, a)| a.finish(name, rows))
+        .collect())
+}
+
+fn is_null_cell(s: &str) -> bool {
+    s.is_empty() || s.eq_ignore_ascii_case("null")
+}
+
+#[derive(Default)]
+struct ColumnAcc {
+    nulls: u64,
+    distinct: BTreeSet<String>,
+    ints: Option<(i64, i64)>,
+    floats: Option<(f64, f64)>,
+    all_int: bool,
+    all_float: bool,
+    seen: bool,
+}
+
+impl ColumnAcc {
+    fn push(&mut self, cell: &str) {
+        if is_null_cell(cell) {
+            self.nulls += 1;
+            return;
+        }
+        if !self.seen {
+            self.seen = true;
+            self.all_int = true;
+            self.all_float = true;
+        }
+        self.distinct.insert(cell.to_string());
+
+        match cell.parse::<i64>() {
+            Ok(n) if self.all_int => {
+                self.ints = Some(match self.ints {
+                    Some((lo, hi)) => (lo.min(n), hi.max(n)),
+                    None => (n, n),
+                });
+            }
+            Ok(_) => {}
+            Err(_) => self.all_int = false,
+        }
+
+        match cell.parse::<f64>() {
+            Ok(x) if x.is_finite() && self.all_float => {
+                self.floats = Some(match self.floats {
+                    Some((lo, hi)) => (
+                        if x.total_cmp(&lo) == Ordering::Less { x } else { lo },
+                        if x.total_cmp(&hi) == Ordering::Greater { x } else { hi },
+                    ),
+                    None => (x, x),
+                });
+            }
+            Ok(x) if x.is_finite() => {}
+            _ => self.all_float = false,
+        }
+    }
+
+    fn finish(self, name: String, rows: u64) -> ColumnStats {
+        let range = match (self.seen, self.all_int, self.all_float) {
+            (false, _, _) => None,
+            (true, true, _) => self.ints.map(|(min, max)| NumericRange::Int { min, max }),
+            (true, false, true) => self.floats.map(|(min, max)| NumericRange::Float { min, max }),
+            _ => None,
+        };
+        ColumnStats {
+            name,
+            count: rows,
+            null_count: self.nulls,
+            distinct_count: self.distinct.len() as u64,
+            range,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "id,price,city\n3,2.5,Oslo\n1,,Lima\n2,-0.0,Oslo\n3,10,\n";
+
+    #[test]
+    fn numeric_column_stats() {
+        let stats = csv_column_stats(CSV).unwrap();
+
+        let id = &stats[0];
+        assert_eq!(id.name, "id");
+        assert_eq!(id.count, 4);
+        assert_eq!(id.null_count, 0);
+        assert_eq!(id.distinct_count, 3);
+        assert_eq!(id.range, Some(NumericRange::Int { min: 1, max: 3 }));
+
+        let price = &stats[1];
+        assert_eq!(price.null_count, 1);
+        assert_eq!(price.distinct_count, 3);
+        match price.range {
+            Some(NumericRange::Float { min, max }) => {
+                assert_eq!(min.to_bits(), (-0.0f64).to_bits());
+                assert_eq!(max, 10.0);
+            }
+            other => panic!("unexpected range {other:?}"),
+        }
+        let attrs = price.to_attrs();
+        assert_eq!(attrs.get("min"), Some(&IrValue::String("-0".to_string())));
+        assert_eq!(attrs.get("max"), Some(&IrValue::String("10".to_string())));
+    }
+
+    #[test]
+    fn string_column_stats() {
+        let stats = csv_column_stats(CSV).unwrap();
+        let city = &stats[2];
+        assert_eq!(city.count, 4);
+        assert_eq!(city.null_count, 1);
+        assert_eq!(city.distinct_count, 2);
+        assert_eq!(city.range, None);
+
+        let attrs = city.to_attrs();
+        assert!(!attrs.contains_key("min"));
+        assert_eq!(attrs.get("nullCount"), Some(&IrValue::I64(1)));
+
+        // Same content, same stats.
+        assert_eq!(csv_column_stats(CSV).unwrap(), stats);
+    }
+
+    #[test]
+    fn nan_makes_column_non_numeric() {
+        let stats = csv_column_stats("x\n1\nNaN\n").unwrap();
+        assert_eq!(stats[0].range, None);
+    }
+}
@@ -9,11 +9,13 @@
 
 #![cfg(feature = "builtin")]
 
+pub mod config;
 pub mod dataset;
 pub mod openapi;
 pub mod repo;
 pub mod workflow;
 
+use crate::builtin::config::BuiltinConfig;
 use crate::registry::PluginRegistry;
 
 /// Register all built-in plugins into the provided registry.
@@ -21,10 +23,15 @@ use crate::registry::PluginRegistry;
 /// This function is deterministic: plugin ids are stable, and the registry uses
 /// a `BTreeMap` internally.
 pub fn register_all(registry: &mut PluginRegistry) {
+    register_all_with_config(registry, &BuiltinConfig::default());
+}
+
+/// Register all built-in plugins, each configured from its section of `config`.
+pub fn register_all_with_config(registry: &mut PluginRegistry, config: &BuiltinConfig) {
     // Register in a stable order (even though registry is ordered).
     // This keeps logs and debugging consistent.
-    repo::register(registry);
-    openapi::register(registry);
-    dataset::register(registry);
-    workflow::register(registry);
+    repo::register_with_config(registry, config.repo.clone());
+    openapi::register_with_config(registry, config.openapi.clone());
+    dataset::register_with_config(registry, config.dataset.clone());
+    workflow::register_with_config(registry, config.workflow.clone());
 }
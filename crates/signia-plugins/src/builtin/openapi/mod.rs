@@ -0,0 +1,563 @@
+//! Built-in `openapi` plugin for SIGNIA.
+//!
+//! This plugin models an OpenAPI document as its operations and components,
+//! with `$ref`s kept as edges rather than inlined.
+//!
+//! Input expectations (provided by host):
+//! - JSON object under `ctx.inputs["openapi"]` (YAML is converted by the host)
+//!
+//! Responsibilities:
+//! - validate the document shape: an OpenAPI 3.x object whose `paths`,
+//!   path items, `components` and component sections are objects
+//! - one `operation` node per path + HTTP method, in path then method order,
+//!   and one `component` node per entry under `components/<section>`
+//! - operation node ids are the `operationId`, or [`operation_id`] when absent
+//! - one `parameter` node per inline parameter of an operation (path-level
+//!   parameters included, operation-level ones overriding by `in` + `name`)
+//! - resolve local `$ref`s to `references` edges (see [`refs`]), bounded by
+//!   `OpenApiConfig::max_ref_depth` and rejecting alias cycles
+//! - record external `$ref`s as `external_ref` nodes
+//! - record the IR hash as `openapiFingerprint` in `ctx.metadata`
+//!
+//! Non-responsibilities:
+//! - fetching external `$ref` targets
+//! - validating schemas against the OpenAPI specification
+
+#![cfg(feature = "builtin")]
+
+pub mod refs;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use signia_core::model::ir::{IrEdge, IrGraph, IrId, IrNode, IrValue};
+use signia_core::pipeline::context::{DiagnosticLevel, PipelineContext, PipelineDiagnostic};
+
+use crate::builtin::config::OpenApiConfig;
+use crate::builtin::spec::openapi_spec;
+use crate::plugin::{Plugin, PluginInput, PluginOutput};
+use crate::registry::PluginRegistry;
+
+use refs::{collect_refs, escape_token, resolve_local, RefTarget};
+
+/// HTTP methods that name an operation under a path item.
+const HTTP_METHODS: [&str; 8] = ["delete", "get", "head", "options", "patch", "post", "put", "trace"];
+
+/// Register the openapi plugin with the default `OpenApiConfig`.
+pub fn register(registry: &mut PluginRegistry) {
+    register_with_config(registry, OpenApiConfig::default());
+}
+
+/// Register the openapi plugin with `config`.
+pub fn register_with_config(registry: &mut PluginRegistry, config: OpenApiConfig) {
+    registry
+        .register(openapi_spec(), Box::new(OpenApiPlugin::new(config)))
+        .expect("failed to register builtin.api.openapi");
+}
+
+/// OpenAPI plugin implementation.
+#[derive(Debug, Clone, Default)]
+pub struct OpenApiPlugin {
+    pub config: OpenApiConfig,
+}
+
+impl OpenApiPlugin {
+    pub fn new(config: OpenApiConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Plugin for OpenApiPlugin {
+    fn name(&self) -> &str {
+        "openapi"
+    }
+
+    fn version(&self) -> &str {
+        "0.1.0"
+    }
+
+    fn supports(&self, input_type: &str) -> bool {
+        input_type == "openapi"
+    }
+
+    fn execute(&self, input: PluginInput<'_>) -> Result<PluginOutput> {
+        let ctx = match input {
+            PluginInput::Pipeline(ctx) => ctx,
+            _ => anyhow::bail!("openapi plugin requires pipeline input"),
+        };
+
+        let diagnostics = execute_openapi(ctx, &self.config)?;
+        if diagnostics.is_empty() {
+            Ok(PluginOutput::None)
+        } else {
+            Ok(PluginOutput::Diagnostics(diagnostics))
+        }
+    }
+}
+
+/// Build the OpenAPI IR into `ctx` and return non-fatal diagnostics.
+fn execute_openapi(ctx: &mut PipelineContext, config: &OpenApiConfig) -> Result<Vec<PipelineDiagnostic>> {
+    let doc = ctx
+        .inputs
+        .get("openapi")
+        .ok_or_else(|| anyhow!("missing openapi input"))?;
+
+    let (graph, diagnostics) = build_openapi_ir(doc, config)?;
+    ctx.set_metadata_checked("openapiFingerprint", graph.hash_hex()?)?;
+    ctx.ir = Some(graph);
+    Ok(diagnostics)
+}
+
+/// Build the IR for an OpenAPI document.
+///
+/// Every `$ref` becomes an edge from the operation or component that holds it;
+/// refs anywhere else hang off the document root.
+pub fn build_openapi_ir(doc: &Value, config: &OpenApiConfig) -> Result<(IrGraph, Vec<PipelineDiagnostic>)> {
+    validate_document(doc)?;
+
+    let title = doc
+        .pointer("/info/title")
+        .and_then(|x| x.as_str())
+        .unwrap_or("untitled");
+
+    let mut graph = IrGraph::new();
+    let root_id = graph.add_node(IrNode::new("openapi", title))?;
+
+    // Owners, keyed by JSON pointer: operations and components.
+    let mut owners: BTreeMap<String, IrId> = BTreeMap::new();
+
+    if let Some(paths) = doc.get("paths").and_then(|x| x.as_object()) {
+        let mut keys: Vec<&String> = paths.keys().collect();
+        keys.sort();
+        for path in keys {
+            for method in HTTP_METHODS {
+                let Some(op) = paths[path.as_str()].get(method) else {
+                    continue;
+                };
+                let label = format!("{} {path}", method.to_uppercase());
+                let mut node = IrNode::new("operation", label);
+                node.id = match op.get("operationId").and_then(|x| x.as_str()) {
+                    Some(id) => id.to_string(),
+                    None => operation_id(method, path),
+                };
+                if graph.nodes.contains_key(&node.id) {
+                    return Err(anyhow!("duplicate operation id: {}", node.id));
+                }
+                let oid = node.id.clone();
+                graph.insert_node(node)?;
+                graph.add_edge(IrEdge::new(root_id.as_str(), oid.as_str(), "contains"))?;
+
+                let op_pointer = format!("/paths/{}/{method}", escape_token(path));
+                for ((location, name), param) in operation_parameters(&paths[path.as_str()], op, &op_pointer)? {
+                    let mut node = IrNode::new("parameter", format!("{location} {name}"));
+                    node.key = IrNode::make_key(
+                        "parameter",
+                        &format!("{op_pointer}/parameters/{}/{}", escape_token(&location), escape_token(&name)),
+                    );
+                    let required = param.get("required").and_then(|x| x.as_bool()).unwrap_or(location == "path");
+                    node.attrs.insert("in".to_string(), IrValue::String(location));
+                    node.attrs.insert("name".to_string(), IrValue::String(name));
+                    node.attrs.insert("required".to_string(), IrValue::Bool(required));
+                    let pid = graph.add_node(node)?;
+                    graph.add_edge(IrEdge::new(oid.as_str(), pid, "contains"))?;
+                }
+                owners.insert(op_pointer, oid);
+            }
+        }
+    }
+
+    if let Some(components) = doc.get("components").and_then(|x| x.as_object()) {
+        let mut sections: Vec<&String> = components.keys().collect();
+        sections.sort();
+        for section in sections {
+            let Some(entries) = components[section.as_str()].as_object() else {
+                continue;
+            };
+            let mut names: Vec<&String> = entries.keys().collect();
+            names.sort();
+            for name in names {
+                let pointer = format!("/components/{}/{}", escape_token(section), escape_token(name));
+                let cid = graph.add_node(IrNode::new("component", pointer.as_str()))?;
+                graph.add_edge(IrEdge::new(root_id.as_str(), cid.as_str(), "contains"))?;
+                owners.insert(pointer, cid);
+            }
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    let mut linked = BTreeSet::<(IrId, IrId)>::new();
+    let mut external: BTreeMap<String, IrId> = BTreeMap::new();
+
+    for site in collect_refs(doc) {
+        let from = owner_of(&owners, &site.at).unwrap_or_else(|| root_id.clone());
+        match RefTarget::parse(&site.target) {
+            RefTarget::Local(pointer) => {
+                let resolved = resolve_local(doc, &pointer, config.max_ref_depth)
+                    .map_err(|e| anyhow!("{e} (at #{})", site.at))?;
+                let to = owners
+                    .get(&resolved.component)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("unresolved $ref #{pointer} (at #{})", site.at))?;
+                if linked.insert((from.clone(), to.clone())) {
+                    graph.add_edge(IrEdge::new(from, to, "references"))?;
+                }
+            }
+            RefTarget::External(target) => {
+                let to = match external.get(&target) {
+                    Some(id) => id.clone(),
+                    None => {
+                        let id = graph.add_node(IrNode::new("external_ref", target.as_str()))?;
+                        external.insert(target.clone(), id.clone());
+                        diagnostics.push(PipelineDiagnostic::new(
+                            DiagnosticLevel::Info,
+                            "openapi.ref.external",
+                            format!("external $ref {target} recorded, not fetched"),
+                        ));
+                        id
+                    }
+                };
+                if linked.insert((from.clone(), to.clone())) {
+                    graph.add_edge(IrEdge::new(from, to, "references"))?;
+                }
+            }
+        }
+    }
+
+    Ok((graph, diagnostics))
+}
+
+/// Check the parts of the document the IR is built from.
+///
+/// `openapi`, when present, must be a 3.x version string. `paths` and
+/// `components` are optional, but when present they, every path item and every
+/// component section must be objects. `x-` extension keys are not checked.
+fn validate_document(doc: &Value) -> Result<()> {
+    if !doc.is_object() {
+        return Err(anyhow!("openapi input must be a JSON object"));
+    }
+    if let Some(version) = doc.get("openapi") {
+        match version.as_str() {
+            Some(v) if v.starts_with("3.") => {}
+            _ => return Err(anyhow!("unsupported openapi version: {version} (expected 3.x)")),
+        }
+    }
+    if let Some(paths) = doc.get("paths") {
+        let paths = paths.as_object().ok_or_else(|| anyhow!("openapi paths must be an object"))?;
+        for (path, item) in paths {
+            if !path.starts_with("x-") && !item.is_object() {
+                return Err(anyhow!("openapi path item {path} must be an object"));
+            }
+        }
+    }
+    if let Some(components) = doc.get("components") {
+        let components = components
+            .as_object()
+            .ok_or_else(|| anyhow!("openapi components must be an object"))?;
+        for (section, entries) in components {
+            if !section.starts_with("x-") && !entries.is_object() {
+                return Err(anyhow!("openapi components.{section} must be an object"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Inline parameters of one operation, keyed and ordered by (`in`, `name`).
+///
+/// Path-level parameters apply to every operation of the path item; an
+/// operation-level parameter with the same `in` + `name` replaces it.
+/// `$ref` parameters are skipped here: they become `references` edges.
+fn operation_parameters<'a>(
+    item: &'a Value,
+    op: &'a Value,
+    op_pointer: &str,
+) -> Result<BTreeMap<(String, String), &'a Value>> {
+    let mut out = BTreeMap::new();
+    for holder in [item, op] {
+        let Some(params) = holder.get("parameters") else {
+            continue;
+        };
+        let params = params
+            .as_array()
+            .ok_or_else(|| anyhow!("openapi parameters must be an array (at #{op_pointer})"))?;
+        for param in params {
+            if param.get("$ref").is_some() {
+                continue;
+            }
+            let location = param.get("in").and_then(|x| x.as_str());
+            let name = param.get("name").and_then(|x| x.as_str());
+            let (Some(location), Some(name)) = (location, name) else {
+                return Err(anyhow!("openapi parameter needs string `in` and `name` (at #{op_pointer})"));
+            };
+            out.insert((location.to_string(), name.to_string()), param);
+        }
+    }
+    Ok(out)
+}
+
+/// Stable id for an operation without `operationId`: `<METHOD>_<path>`.
+///
+/// The method is upper-cased. The path keeps ASCII letters, digits, `-._~/` and
+/// template braces (`/pets/{id}` -> `GET_/pets/{id}`); every other byte,
+/// including `%`, is percent-encoded as `%XX`, so distinct paths never share an id.
+pub fn operation_id(method: &str, path: &str) -> String {
+    let mut out = method.to_uppercase();
+    out.push('_');
+    for b in path.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b'{' | b'}' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{b:02X}")),
+        }
+    }
+    out
+}
+
+/// The operation or component enclosing the JSON pointer `at`.
+///
+/// Both kinds of owner are exactly three tokens deep
+/// (`/paths/<path>/<method>`, `/components/<section>/<name>`).
+fn owner_of(owners: &BTreeMap<String, IrId>, at: &str) -> Option<IrId> {
+    let prefix: Vec<&str> = at.split('/').take(4).collect();
+    owners.get(&prefix.join("/")).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn edges_of_type<'a>(graph: &'a IrGraph, edge_type: &str) -> Vec<&'a IrEdge> {
+        graph.edges.values().filter(|e| e.edge_type == edge_type).collect()
+    }
+
+    #[test]
+    fn local_ref_becomes_references_edge() {
+        let doc = json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Pets", "version": "1"},
+            "paths": {"/pets": {"get": {"responses": {"200": {"content": {"application/json": {
+                "schema": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}}
+            }}}}}}},
+            "components": {"schemas": {
+                "Pet": {"type": "object", "properties": {"name": {"type": "string"}}}
+            }}
+        });
+
+        let (graph, diagnostics) = build_openapi_ir(&doc, &OpenApiConfig::default()).unwrap();
+        assert!(diagnostics.is_empty());
+
+        let refs = edges_of_type(&graph, "references");
+        assert_eq!(refs.len(), 1);
+        let from = &graph.nodes[&refs[0].from];
+        let to = &graph.nodes[&refs[0].to];
+        assert_eq!((from.node_type.as_str(), from.name.as_str()), ("operation", "GET /pets"));
+        assert_eq!((to.node_type.as_str(), to.name.as_str()), ("component", "/components/schemas/Pet"));
+
+        // Not inlined: the schema's properties do not appear under the operation.
+        assert!(!graph.nodes.values().any(|n| n.name == "name"));
+    }
+
+    #[test]
+    fn recursive_schema_is_not_a_cycle() {
+        let doc = json!({"components": {"schemas": {
+            "Node": {"properties": {"children": {"items": {"$ref": "#/components/schemas/Node"}}}}
+        }}});
+
+        let (graph, _) = build_openapi_ir(&doc, &OpenApiConfig::default()).unwrap();
+        let refs = edges_of_type(&graph, "references");
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].from, refs[0].to);
+    }
+
+    #[test]
+    fn cyclic_alias_ref_is_detected() {
+        let doc = json!({
+            "paths": {"/a": {"get": {"responses": {"200": {"$ref": "#/components/responses/A"}}}}},
+            "components": {"responses": {
+                "A": {"$ref": "#/components/responses/B"},
+                "B": {"$ref": "#/components/responses/A"}
+            }}
+        });
+
+        let err = build_openapi_ir(&doc, &OpenApiConfig::default()).unwrap_err().to_string();
+        assert!(err.starts_with("cyclic $ref: "), "{err}");
+    }
+
+    #[test]
+    fn ref_depth_is_capped() {
+        let doc = json!({
+            "paths": {"/a": {"get": {"responses": {"200": {"$ref": "#/components/responses/A"}}}}},
+            "components": {"responses": {
+                "A": {"$ref": "#/components/responses/B"},
+                "B": {"$ref": "#/components/responses/C"},
+                "C": {"description": "ok"}
+            }}
+        });
+
+        let config = OpenApiConfig { max_ref_depth: 1 };
+        let err = build_openapi_ir(&doc, &config).unwrap_err().to_string();
+        assert!(err.contains("exceeds max depth 1"), "{err}");
+        assert!(build_openapi_ir(&doc, &OpenApiConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn external_ref_is_recorded_not_fetched() {
+        let doc = json!({"paths": {"/a": {"get": {"responses": {
+            "200": {"$ref": "common.yaml#/responses/Ok"},
+            "404": {"$ref": "common.yaml#/responses/Ok"}
+        }}}}});
+
+        let (graph, diagnostics) = build_openapi_ir(&doc, &OpenApiConfig::default()).unwrap();
+        let ext: Vec<&IrNode> = graph.nodes.values().filter(|n| n.node_type == "external_ref").collect();
+        assert_eq!(ext.len(), 1);
+        assert_eq!(ext[0].name, "common.yaml#/responses/Ok");
+        assert_eq!(edges_of_type(&graph, "references").len(), 1);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "openapi.ref.external");
+    }
+
+    #[test]
+    fn operation_ids_are_stable_and_escaped() {
+        assert_eq!(operation_id("get", "/pets"), "GET_/pets");
+        assert_eq!(operation_id("get", "/pets/{id}"), "GET_/pets/{id}");
+        assert_eq!(operation_id("delete", "/pets/{id}"), operation_id("DELETE", "/pets/{id}"));
+        assert_ne!(operation_id("get", "/pets/{id}"), operation_id("get", "/pets/{petId}"));
+        assert_eq!(operation_id("post", "/a b/%"), "POST_/a%20b/%25");
+        assert_eq!(operation_id("get", "/caf\u{e9}"), "GET_/caf%C3%A9");
+    }
+
+    #[test]
+    fn operation_node_ids_prefer_operation_id() {
+        let doc = json!({"paths": {"/pets/{id}": {
+            "get": {"operationId": "getPet"},
+            "delete": {}
+        }}});
+
+        let (graph, _) = build_openapi_ir(&doc, &OpenApiConfig::default()).unwrap();
+        assert_eq!(graph.nodes["getPet"].name, "GET /pets/{id}");
+        assert_eq!(graph.nodes["DELETE_/pets/{id}"].name, "DELETE /pets/{id}");
+    }
+
+    #[test]
+    fn duplicate_operation_id_fails() {
+        let doc = json!({"paths": {
+            "/a": {"get": {"operationId": "op"}},
+            "/b": {"get": {"operationId": "op"}}
+        }});
+
+        let err = build_openapi_ir(&doc, &OpenApiConfig::default()).unwrap_err().to_string();
+        assert_eq!(err, "duplicate operation id: op");
+    }
+
+    #[test]
+    fn plugin_sets_ir() {
+        use signia_core::pipeline::context::PipelineConfig;
+
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert("openapi".to_string(), json!({"info": {"title": "t"}, "paths": {}}));
+
+        OpenApiPlugin::default()
+            .execute(PluginInput::Pipeline(&mut ctx))
+            .unwrap();
+        assert!(ctx.ir.is_some());
+    }
+
+    fn fingerprint_of(doc: Value) -> String {
+        use signia_core::pipeline::context::PipelineConfig;
+
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert("openapi".to_string(), doc);
+        OpenApiPlugin::default()
+            .execute(PluginInput::Pipeline(&mut ctx))
+            .unwrap();
+        assert_eq!(ctx.metadata["openapiFingerprint"], ctx.ir.as_ref().unwrap().hash_hex().unwrap());
+        ctx.metadata["openapiFingerprint"].clone()
+    }
+
+    #[test]
+    fn fingerprint_is_recorded_and_tracks_content() {
+        let doc = json!({"openapi": "3.1.0", "paths": {"/b": {"get": {}}, "/a": {"post": {}, "get": {}}}});
+        let fp = fingerprint_of(doc.clone());
+        assert_eq!(fp.len(), 64);
+        assert_eq!(fingerprint_of(doc), fp);
+        assert_ne!(fingerprint_of(json!({"openapi": "3.1.0", "paths": {"/a": {"get": {}}}})), fp);
+    }
+
+    #[test]
+    fn operations_are_ordered_by_path_then_method() {
+        let doc = json!({"paths": {"/b": {"get": {}}, "/a": {"post": {}, "get": {}}}});
+        let (graph, _) = build_openapi_ir(&doc, &OpenApiConfig::default()).unwrap();
+
+        // Edge ids are allocated in insertion order: e0, e1, e2.
+        let order: Vec<&str> = (0..3)
+            .map(|i| graph.nodes[&graph.edges[&format!("e{i}")].to].name.as_str())
+            .collect();
+        assert_eq!(order, ["GET /a", "POST /a", "GET /b"]);
+    }
+
+    #[test]
+    fn parameters_become_nodes_under_their_operation() {
+        let doc = json!({"paths": {"/pets/{id}": {
+            "parameters": [
+                {"name": "id", "in": "path"},
+                {"name": "verbose", "in": "query"}
+            ],
+            "get": {"operationId": "getPet", "parameters": [
+                {"name": "verbose", "in": "query", "required": true},
+                {"$ref": "#/components/parameters/Trace"}
+            ]},
+            "delete": {"operationId": "deletePet"}
+        }}, "components": {"parameters": {"Trace": {"name": "trace", "in": "header"}}}});
+
+        let (graph, _) = build_openapi_ir(&doc, &OpenApiConfig::default()).unwrap();
+        let params_of = |op: &str| -> Vec<(String, bool)> {
+            let mut out: Vec<(String, bool)> = graph
+                .edges
+                .values()
+                .filter(|e| e.from == op && e.edge_type == "contains")
+                .map(|e| &graph.nodes[&e.to])
+                .map(|n| (n.name.clone(), n.attrs["required"] == IrValue::Bool(true)))
+                .collect();
+            out.sort();
+            out
+        };
+
+        assert_eq!(
+            params_of("getPet"),
+            [("path id".to_string(), true), ("query verbose".to_string(), true)]
+        );
+        assert_eq!(
+            params_of("deletePet"),
+            [("path id".to_string(), true), ("query verbose".to_string(), false)]
+        );
+
+        // The $ref parameter is a reference, not an inline node.
+        let refs = edges_of_type(&graph, "references");
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].from, "getPet");
+    }
+
+    #[test]
+    fn malformed_documents_are_rejected() {
+        let cases = [
+            (json!([]), "must be a JSON object"),
+            (json!({"openapi": "2.0"}), "unsupported openapi version"),
+            (json!({"paths": []}), "paths must be an object"),
+            (json!({"paths": {"/a": 1}}), "path item /a must be an object"),
+            (json!({"components": {"schemas": []}}), "components.schemas must be an object"),
+            (json!({"paths": {"/a": {"get": {"parameters": {}}}}}), "parameters must be an array"),
+            (json!({"paths": {"/a": {"get": {"parameters": [{"name": "q"}]}}}}), "needs string `in` and `name`"),
+        ];
+        for (doc, want) in cases {
+            let err = build_openapi_ir(&doc, &OpenApiConfig::default()).unwrap_err().to_string();
+            assert!(err.contains(want), "{want}: {err}");
+        }
+
+        let ok = json!({"openapi": "3.0.3", "paths": {"x-note": 1}, "components": {"x-note": true}});
+        assert!(build_openapi_ir(&ok, &OpenApiConfig::default()).is_ok());
+    }
+}
@@ -0,0 +1,203 @@
+//! `$ref` resolution for the built-in `openapi` plugin.
+//!
+//! References are modeled, not inlined: the plugin turns every `$ref` into a
+//! `references` edge from the object holding it to the component it names.
+//!
+//! Rules:
+//! - local refs must point into `#/components/...`
+//! - a component that is itself a `$ref` (an alias) is followed, at most
+//!   `max_depth` hops; alias cycles are errors
+//! - external refs (anything not starting with `#`) are recorded, never fetched
+//! - traversal visits object keys in sorted order, so results are stable
+//!
+//! Cycles through schema bodies (e.g. a `Node` whose `children` refer back to
+//! `Node`) are ordinary recursive schemas and are not errors: nothing is inlined.
+
+#![cfg(feature = "builtin")]
+
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// A `$ref` occurrence in a document.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RefSite {
+    /// JSON pointer (RFC 6901) of the object holding `$ref`.
+    pub at: String,
+    /// The `$ref` value as written.
+    pub target: String,
+}
+
+/// Where a `$ref` points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefTarget {
+    /// `#...`: a JSON pointer into the same document (without the `#`).
+    Local(String),
+    /// Anything else (`common.yaml#/Pet`, `https://...`); recorded, not fetched.
+    External(String),
+}
+
+impl RefTarget {
+    /// Classify a `$ref` value.
+    pub fn parse(r: &str) -> Self {
+        match r.strip_prefix('#') {
+            Some(pointer) => RefTarget::Local(pointer.to_string()),
+            None => RefTarget::External(r.to_string()),
+        }
+    }
+}
+
+/// A local ref followed through any aliases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved {
+    /// `/components/<section>/<name>` named by the ref itself.
+    pub component: String,
+    /// Pointers visited, starting with the ref's own pointer.
+    pub chain: Vec<String>,
+}
+
+/// Collect every `$ref` in `doc`, depth-first with object keys sorted.
+pub fn collect_refs(doc: &Value) -> Vec<RefSite> {
+    let mut out = Vec::new();
+    walk(doc, &mut String::new(), &mut out);
+    out
+}
+
+fn walk(v: &Value, at: &mut String, out: &mut Vec<RefSite>) {
+    match v {
+        Value::Object(map) => {
+            if let Some(Value::String(r)) = map.get("$ref") {
+                out.push(RefSite {
+                    at: at.clone(),
+                    target: r.clone(),
+                });
+            }
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            for k in keys {
+                let len = at.len();
+                at.push('/');
+                at.push_str(&escape_token(k));
+                walk(&map[k.as_str()], at, out);
+                at.truncate(len);
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                let len = at.len();
+                at.push('/');
+                at.push_str(&i.to_string());
+                walk(item, at, out);
+                at.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Escape a JSON pointer token (`~` -> `~0`, `/` -> `~1`).
+pub fn escape_token(s: &str) -> String {
+    s.replace('~', "~0").replace('/', "~1")
+}
+
+/// The `/components/<section>/<name>` prefix of `pointer`, if it has one.
+pub fn component_of(pointer: &str) -> Option<String> {
+    let rest = pointer.strip_prefix("/components/")?;
+    let mut tokens = rest.splitn(3, '/');
+    let section = tokens.next().filter(|s| !s.is_empty())?;
+    let name = tokens.next().filter(|s| !s.is_empty())?;
+    Some(format!("/components/{section}/{name}"))
+}
+
+/// Resolve the local `pointer` in `doc`, following aliases at most `max_depth` hops.
+///
+/// Fails if any pointer on the way is outside `#/components`, does not exist,
+/// repeats (a cycle) or the chain is longer than `max_depth`. An alias to an
+/// external ref ends the chain; the external ref is collected on its own.
+pub fn resolve_local(doc: &Value, pointer: &str, max_depth: usize) -> Result<Resolved> {
+    let component = component_of(pointer)
+        .ok_or_else(|| anyhow!("$ref #{pointer} does not point into #/components"))?;
+    let mut chain = vec![pointer.to_string()];
+    let mut seen = BTreeSet::from([pointer.to_string()]);
+
+    loop {
+        let current = chain.last().expect("chain starts non-empty");
+        let v = doc
+            .pointer(current)
+            .ok_or_else(|| anyhow!("unresolved $ref #{current}"))?;
+        let next = match v.get("$ref").and_then(Value::as_str).map(RefTarget::parse) {
+            Some(RefTarget::Local(next)) => next,
+            Some(RefTarget::External(_)) | None => return Ok(Resolved { component, chain }),
+        };
+
+        if component_of(&next).is_none() {
+            return Err(anyhow!("$ref #{next} does not point into #/components"));
+        }
+        let cyclic = !seen.insert(next.clone());
+        chain.push(next);
+        if cyclic {
+            let path: Vec<String> = chain.iter().map(|p| format!("#{p}")).collect();
+            return Err(anyhow!("cyclic $ref: {}", path.join(" -> ")));
+        }
+        if chain.len() - 1 > max_depth {
+            return Err(anyhow!("$ref chain from #{pointer} exceeds max depth {max_depth}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn collects_refs_in_sorted_order() {
+        let doc = json!({
+            "paths": {"/pets": {"get": {"responses": {"200": {"$ref": "#/components/responses/Pets"}}}}},
+            "components": {"schemas": {"B": {"$ref": "other.yaml#/B"}, "A": {"items": [{"$ref": "#/components/schemas/B"}]}}}
+        });
+        let sites = collect_refs(&doc);
+        let at: Vec<&str> = sites.iter().map(|s| s.at.as_str()).collect();
+        assert_eq!(
+            at,
+            ["/components/schemas/A/items/0", "/components/schemas/B", "/paths/~1pets/get/responses/200"]
+        );
+        assert_eq!(RefTarget::parse(&sites[1].target), RefTarget::External("other.yaml#/B".to_string()));
+    }
+
+    #[test]
+    fn resolves_local_refs_and_aliases() {
+        let doc = json!({"components": {"schemas": {
+            "Pet": {"type": "object", "properties": {"name": {"type": "string"}}},
+            "Animal": {"$ref": "#/components/schemas/Pet"}
+        }}});
+
+        let r = resolve_local(&doc, "/components/schemas/Pet/properties/name", 4).unwrap();
+        assert_eq!(r.component, "/components/schemas/Pet");
+
+        let r = resolve_local(&doc, "/components/schemas/Animal", 4).unwrap();
+        assert_eq!(r.component, "/components/schemas/Animal");
+        assert_eq!(r.chain, ["/components/schemas/Animal", "/components/schemas/Pet"]);
+
+        let err = resolve_local(&doc, "/components/schemas/Animal", 0).unwrap_err().to_string();
+        assert!(err.contains("exceeds max depth 0"), "{err}");
+        let err = resolve_local(&doc, "/components/schemas/Missing", 4).unwrap_err().to_string();
+        assert!(err.contains("unresolved $ref #/components/schemas/Missing"), "{err}");
+        let err = resolve_local(&doc, "/info", 4).unwrap_err().to_string();
+        assert!(err.contains("does not point into #/components"), "{err}");
+    }
+
+    #[test]
+    fn detects_alias_cycles() {
+        let doc = json!({"components": {"schemas": {
+            "A": {"$ref": "#/components/schemas/B"},
+            "B": {"$ref": "#/components/schemas/A"}
+        }}});
+        let err = resolve_local(&doc, "/components/schemas/A", 32).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "cyclic $ref: #/components/schemas/A -> #/components/schemas/B -> #/components/schemas/A"
+        );
+    }
+}
@@ -29,8 +29,11 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use signia_core::determinism::hashing::hash_bytes_hex;
+use signia_core::model::ir::IrGraph;
 use signia_core::provenance::SourceRef;
 
+use crate::builtin::repo::build_repo_ir;
+
 /// Maximum number of files allowed by default when fetching a repo snapshot.
 pub const DEFAULT_MAX_FILES: u64 = 10_000;
 
@@ -237,6 +240,17 @@ impl RepoSnapshot {
             "files": files
         })
     }
+
+    /// Build the repo plugin's IR directly, skipping the JSON round-trip.
+    ///
+    /// The result is identical to running the repo plugin on
+    /// `to_repo_plugin_input(owner, repo, ..)`.
+    pub fn to_ir(&self, owner: &str, repo: &str) -> Result<IrGraph> {
+        build_repo_ir(
+            &format!("{}/{}", owner, repo),
+            self.files.iter().map(|f| f.path.as_str()),
+        )
+    }
 }
 
 /// Trait implemented by the host to fetch GitHub repositories deterministically.
@@ -8,6 +8,10 @@
 //! - convert repo structure into canonical IR
 //! - emit schema / manifest artifacts via pipeline context
 //!
+//! Configuration (`RepoConfig`, see `register_with_config`):
+//! - `include`/`exclude` filter file paths (see `tree_walk::is_included`)
+//! - `max_files` bounds the files left after filtering
+//!
 //! Non-responsibilities:
 //! - cloning repositories
 //! - network access
@@ -17,32 +21,51 @@
 
 #![cfg(feature = "builtin")]
 
-use anyhow::Result;
+pub mod github_fetch;
+pub mod tree_walk;
+
+use anyhow::{anyhow, Result};
 
 use signia_core::model::ir::{IrEdge, IrGraph, IrNode};
-use signia_core::pipeline::context::PipelineContext;
+use signia_core::pipeline::context::{DiagnosticLevel, PipelineContext, PipelineDiagnostic};
 
+use crate::builtin::config::RepoConfig;
 use crate::plugin::{Plugin, PluginInput, PluginOutput};
 use crate::registry::PluginRegistry;
 use crate::spec::PluginSpec;
 
-/// Register the repo plugin.
+/// Register the repo plugin with the default `RepoConfig`.
 pub fn register(registry: &mut PluginRegistry) {
+    register_with_config(registry, RepoConfig::default());
+}
+
+/// Register the repo plugin with `config`.
+pub fn register_with_config(registry: &mut PluginRegistry, config: RepoConfig) {
     let spec = PluginSpec::new("builtin.repo", "Repository Plugin", "0.1.0")
         .support("repo")
         .limit("max_nodes", 200_000)
         .limit("max_edges", 400_000)
+        .limit("max_files", config.max_files as u64)
         .want("network", false)
         .want("filesystem", false)
         .meta("category", "source");
 
     registry
-        .register(spec, Box::new(RepoPlugin))
+        .register(spec, Box::new(RepoPlugin::new(config)))
         .expect("failed to register builtin.repo");
 }
 
 /// Repo plugin implementation.
-pub struct RepoPlugin;
+#[derive(Debug, Clone, Default)]
+pub struct RepoPlugin {
+    pub config: RepoConfig,
+}
+
+impl RepoPlugin {
+    pub fn new(config: RepoConfig) -> Self {
+        Self { config }
+    }
+}
 
 impl Plugin for RepoPlugin {
     fn name(&self) -> &str {
@@ -57,19 +80,23 @@ impl Plugin for RepoPlugin {
         input_type == "repo"
     }
 
-    fn execute(&self, input: &PluginInput) -> Result<PluginOutput> {
+    fn execute(&self, input: PluginInput<'_>) -> Result<PluginOutput> {
         let ctx = match input {
             PluginInput::Pipeline(ctx) => ctx,
             _ => anyhow::bail!("repo plugin requires pipeline input"),
         };
 
-        execute_repo(ctx)?;
-        Ok(PluginOutput::None)
+        let diagnostics = execute_repo(ctx, &self.config)?;
+        if diagnostics.is_empty() {
+            Ok(PluginOutput::None)
+        } else {
+            Ok(PluginOutput::Diagnostics(diagnostics))
+        }
     }
 }
 
 /// Core execution logic for repo plugin.
-fn execute_repo(ctx: &mut PipelineContext) -> Result<()> {
+fn execute_repo(ctx: &mut PipelineContext, config: &RepoConfig) -> Result<Vec<PipelineDiagnostic>> {
     // Expect repo metadata to be present in pipeline inputs.
     let meta = ctx
         .inputs
@@ -81,29 +108,65 @@ fn execute_repo(ctx: &mut PipelineContext) -> Result<()> {
         .and_then(|v| v.as_str())
         .ok_or_else(|| anyhow::anyhow!("repo.name missing or invalid"))?;
 
-    let mut graph = IrGraph::new();
-
-    // Root node
-    let root = IrNode::new("repo", repo_name);
-    let root_id = graph.add_node(root);
-
-    // Files
+    // Files, filtered by include/exclude
+    let mut paths = Vec::new();
+    let mut excluded = 0usize;
     if let Some(files) = meta.get("files").and_then(|v| v.as_array()) {
         for file in files {
             let path = file
                 .get("path")
                 .and_then(|v| v.as_str())
                 .ok_or_else(|| anyhow::anyhow!("file.path missing"))?;
+            if tree_walk::is_included(path, &config.include, &config.exclude) {
+                paths.push(path);
+            } else {
+                excluded += 1;
+            }
+        }
+    }
 
-            let node = IrNode::new("file", path);
-            let node_id = graph.add_node(node);
+    if paths.len() > config.max_files {
+        return Err(anyhow!(
+            "repo has {} files after filtering (max_files {})",
+            paths.len(),
+            config.max_files
+        ));
+    }
 
-            graph.add_edge(IrEdge::new(root_id, node_id, "contains"));
-        }
+    let mut diagnostics = Vec::new();
+    if excluded > 0 {
+        diagnostics.push(PipelineDiagnostic::new(
+            DiagnosticLevel::Info,
+            "repo.files.excluded",
+            format!("{excluded} files excluded by include/exclude patterns"),
+        ));
     }
 
-    ctx.ir = Some(graph);
-    Ok(())
+    ctx.ir = Some(build_repo_ir(repo_name, paths)?);
+    Ok(diagnostics)
+}
+
+/// Build the repo IR from a repo name and its file paths.
+///
+/// This is the single code path shared by the plugin (JSON input) and
+/// `RepoSnapshot::to_ir` (direct input), so both produce identical graphs.
+/// A path listed twice is an error (duplicate node key).
+pub fn build_repo_ir<'a>(name: &str, paths: impl IntoIterator<Item = &'a str>) -> Result<IrGraph> {
+    let mut graph = IrGraph::new();
+
+    // Root node
+    let root = IrNode::new("repo", name);
+    let root_id = graph.add_node(root)?;
+
+    // Files
+    for path in paths {
+        let node = IrNode::new("file", path);
+        let node_id = graph.add_node(node)?;
+
+        graph.add_edge(IrEdge::new(root_id.as_str(), node_id, "contains"))?;
+    }
+
+    Ok(graph)
 }
 
 #[cfg(test)]
@@ -126,12 +189,115 @@ mod tests {
             }),
         );
 
-        let plugin = RepoPlugin;
-        let out = plugin.execute(&PluginInput::Pipeline(&mut ctx)).unwrap();
+        let plugin = RepoPlugin::default();
+        let out = plugin.execute(PluginInput::Pipeline(&mut ctx)).unwrap();
         matches!(out, PluginOutput::None);
 
         assert!(ctx.ir.is_some());
         let graph = ctx.ir.unwrap();
         assert_eq!(graph.nodes.len(), 3);
     }
+
+    #[test]
+    fn snapshot_to_ir_matches_plugin_path() {
+        use crate::builtin::repo::github_fetch::{snapshot_from_files, GitHubFetchRequest, RepoFile};
+
+        let req = GitHubFetchRequest::new("o", "r", "deadbeef");
+        let snapshot = snapshot_from_files(
+            &req,
+            vec![RepoFile::new("src/lib.rs", 10), RepoFile::new("README.md", 5)],
+        )
+        .unwrap();
+
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs
+            .insert("repo".to_string(), snapshot.to_repo_plugin_input("o", "r", "deadbeef"));
+        RepoPlugin::default().execute(PluginInput::Pipeline(&mut ctx)).unwrap();
+
+        let via_json = ctx.ir.unwrap();
+        let direct = snapshot.to_ir("o", "r").unwrap();
+        assert_eq!(via_json, direct);
+        assert_eq!(via_json.hash_hex().unwrap(), direct.hash_hex().unwrap());
+    }
+
+    #[test]
+    fn empty_repo_emits_single_root() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs
+            .insert("repo".to_string(), json!({ "name": "empty", "files": [] }));
+        RepoPlugin::default().execute(PluginInput::Pipeline(&mut ctx)).unwrap();
+
+        let graph = ctx.ir.unwrap();
+        assert_eq!(graph.nodes.len(), 1);
+        assert!(graph.edges.is_empty());
+
+        // A missing `files` array is the same as an empty one.
+        let mut ctx2 = PipelineContext::new(PipelineConfig::default());
+        ctx2.inputs.insert("repo".to_string(), json!({ "name": "empty" }));
+        RepoPlugin::default().execute(PluginInput::Pipeline(&mut ctx2)).unwrap();
+        assert_eq!(graph, ctx2.ir.unwrap());
+    }
+
+    fn repo_input() -> serde_json::Value {
+        json!({
+            "name": "demo",
+            "files": [
+                { "path": "README.md" },
+                { "path": "src/lib.rs" },
+                { "path": "src/main.rs" },
+                { "path": "target/debug/demo" }
+            ]
+        })
+    }
+
+    #[test]
+    fn max_files_is_enforced() {
+        let run = |max_files: usize| {
+            let mut ctx = PipelineContext::new(PipelineConfig::default());
+            ctx.inputs.insert("repo".to_string(), repo_input());
+            let plugin = RepoPlugin::new(RepoConfig { max_files, ..RepoConfig::default() });
+            plugin.execute(PluginInput::Pipeline(&mut ctx))?;
+            anyhow::Ok(ctx.ir.unwrap())
+        };
+
+        // `target/**` is excluded by default, leaving 3 files.
+        assert_eq!(run(3).unwrap().nodes.len(), 4);
+        let err = run(2).unwrap_err().to_string();
+        assert_eq!(err, "repo has 3 files after filtering (max_files 2)");
+    }
+
+    #[test]
+    fn include_and_exclude_filter_files() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert("repo".to_string(), repo_input());
+        let plugin = RepoPlugin::new(RepoConfig {
+            include: vec!["src/**".to_string()],
+            exclude: vec!["*main.rs".to_string()],
+            ..RepoConfig::default()
+        });
+        let out = plugin.execute(PluginInput::Pipeline(&mut ctx)).unwrap();
+
+        let graph = ctx.ir.unwrap();
+        let mut files: Vec<&str> = graph
+            .nodes
+            .values()
+            .filter(|n| n.node_type == "file")
+            .map(|n| n.name.as_str())
+            .collect();
+        files.sort();
+        assert_eq!(files, ["src/lib.rs"]);
+        assert!(matches!(out, PluginOutput::Diagnostics(d) if d[0].code == "repo.files.excluded"));
+    }
+
+    #[test]
+    fn registered_config_takes_effect() {
+        let mut reg = PluginRegistry::default();
+        register_with_config(&mut reg, RepoConfig { max_files: 1, ..RepoConfig::default() });
+        let registered = reg.get("builtin.repo").unwrap();
+        assert_eq!(registered.spec.limits.get("max_files"), Some(&1));
+
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert("repo".to_string(), repo_input());
+        assert!(registered.plugin.execute(PluginInput::Pipeline(&mut ctx)).is_err());
+    }
 }
@@ -6,7 +6,8 @@
 //!
 //! This module provides:
 //! - stable path normalization
-//! - deterministic include/exclude filtering (glob-like)
+//! - deterministic include/exclude filtering (glob-like), or ordered
+//!   gitignore-style rules with negation (see `Rule`)
 //! - deterministic ordering
 //! - limits enforcement
 //!
@@ -54,9 +55,12 @@ impl VFile {
 pub struct WalkOptions {
     pub include: Vec<String>,
     pub exclude: Vec<String>,
+    /// Ordered rules applied after `include`/`exclude`; the last matching rule wins.
+    pub rules: Vec<Rule>,
     pub max_files: u64,
     pub max_total_bytes: u64,
     pub include_contents: bool,
+    pub on_overflow: OverflowPolicy,
 }
 
 impl Default for WalkOptions {
@@ -64,13 +68,44 @@ impl Default for WalkOptions {
         Self {
             include: Vec::new(),
             exclude: Vec::new(),
+            rules: Vec::new(),
             max_files: DEFAULT_MAX_FILES,
             max_total_bytes: DEFAULT_MAX_TOTAL_BYTES,
             include_contents: false,
+            on_overflow: OverflowPolicy::Error,
         }
     }
 }
 
+/// What to do when the selected files exceed `max_files` or `max_total_bytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Fail the walk (default).
+    #[default]
+    Error,
+    /// Keep the lexicographically-first files that fit and report the rest as skipped.
+    ///
+    /// The kept set is always a prefix of the sorted selection, so the result does
+    /// not depend on input order.
+    TruncateDeterministic,
+}
+
+/// Result of a walk, including files dropped by truncation.
+#[derive(Debug, Clone, Default)]
+pub struct WalkResult {
+    /// Kept files, sorted by normalized path.
+    pub files: Vec<RepoFile>,
+    /// Normalized paths of files dropped by `OverflowPolicy::TruncateDeterministic`, sorted.
+    pub skipped: Vec<String>,
+}
+
+impl WalkResult {
+    /// Returns true if any file was dropped.
+    pub fn is_truncated(&self) -> bool {
+        !self.skipped.is_empty()
+    }
+}
+
 /// Normalize a path deterministically:
 /// - replace backslashes with forward slashes
 /// - remove repeated slashes
@@ -100,81 +135,45 @@ pub fn normalize_repo_path(p: &str) -> Result<String> {
 /// Very small deterministic "glob-like" matcher.
 ///
 /// Supported forms:
-/// - "*" matches any sequence within a segment
-/// - "**" matches across path separators
-/// - suffix match like "*.rs"
+/// - "*" matches any sequence, including `/` (so "*.rs" is a suffix match at any depth)
+/// - "**" matches any sequence, including `/`
+/// - "**/" matches zero or more leading directories ("**/test*" matches "test.rs")
 ///
-/// This is not a full glob engine but is deterministic and sufficient for common filters.
+/// Patterns are anchored at both ends: "dist/**" matches "dist/a.js" but not
+/// "src/dist/a.js". This is not a full glob engine but is deterministic and
+/// sufficient for common filters.
 pub fn matches_pattern(path: &str, pattern: &str) -> bool {
-    if pattern == "**" {
-        return true;
-    }
+    glob_match(pattern.as_bytes(), path.as_bytes())
+}
 
-    // Quick path: exact match
-    if path == pattern {
-        return true;
+fn glob_match(p: &[u8], s: &[u8]) -> bool {
+    if let Some(rest) = p.strip_prefix(b"**/") {
+        return glob_match(rest, s)
+            || s.iter()
+                .enumerate()
+                .any(|(i, &c)| c == b'/' && glob_match(rest, &s[i + 1..]));
     }
-
-    // Split on "**"
-    if pattern.contains("**") {
-        let parts: Vec<&str> = pattern.split("**").collect();
-        let mut idx = 0usize;
-        for part in parts {
-            if part.is_empty() {
-                continue;
-            }
-            if let Some(pos) = path[idx..].find(part) {
-                idx += pos + part.len();
-            } else {
-                return false;
-            }
+    match p.first() {
+        None => s.is_empty(),
+        Some(b'*') => {
+            let rest = p.strip_prefix(b"**").unwrap_or(&p[1..]);
+            (0..=s.len()).any(|i| glob_match(rest, &s[i..]))
         }
-        return true;
+        Some(&c) => s.first() == Some(&c) && glob_match(&p[1..], &s[1..]),
     }
+}
 
-    // Segment-level "*"
-    if pattern.contains('*') {
-        // Convert to a simple regex-like matching without allocating regex engine.
-        // Only supports "*" wildcard.
-        let mut pi = 0usize;
-        let pbytes = pattern.as_bytes();
-        let sbytes = path.as_bytes();
-
-        let mut si = 0usize;
-        let mut star: Option<usize> = None;
-        let mut match_i = 0usize;
-
-        while si < sbytes.len() {
-            if pi < pbytes.len() && (pbytes[pi] == sbytes[si]) {
-                pi += 1;
-                si += 1;
-                continue;
-            }
-
-            if pi < pbytes.len() && pbytes[pi] == b'*' {
-                star = Some(pi);
-                pi += 1;
-                match_i = si;
-                continue;
-            }
-
-            if let Some(st) = star {
-                pi = st + 1;
-                match_i += 1;
-                si = match_i;
-                continue;
-            }
-
-            return false;
-        }
-
-        while pi < pbytes.len() && pbytes[pi] == b'*' {
-            pi += 1;
-        }
-        return pi == pbytes.len();
+/// Anchored form of an include/exclude list pattern.
+///
+/// The lists keep their historical meaning: a pattern containing `**` matches at
+/// any directory depth, so "node_modules/**" also drops "web/node_modules/x.js".
+/// Other patterns ("README.md", "*.rs") are matched as written.
+pub fn list_pattern_glob(pattern: &str) -> String {
+    if pattern.contains("**") && !pattern.starts_with("**") {
+        format!("**/{pattern}")
+    } else {
+        pattern.to_string()
     }
-
-    false
 }
 
 /// Determine whether a path is included given include/exclude lists.
@@ -182,37 +181,107 @@ pub fn matches_pattern(path: &str, pattern: &str) -> bool {
 /// - If include is empty: include all
 /// - If include is non-empty: include if any include pattern matches
 /// - Exclude always removes if any exclude pattern matches
+///
+/// Patterns are read with `list_pattern_glob`. Exclude wins when both match, and
+/// an excluded path cannot be re-included. Use `Rule`s with `is_included_by_rules`
+/// for gitignore-style negation.
 pub fn is_included(path: &str, include: &[String], exclude: &[String]) -> bool {
+    let matches = |p: &String| matches_pattern(path, &list_pattern_glob(p));
     let inc_ok = if include.is_empty() {
         true
     } else {
-        include.iter().any(|p| matches_pattern(path, p))
+        include.iter().any(matches)
     };
 
     if !inc_ok {
         return false;
     }
 
-    let exc = exclude.iter().any(|p| matches_pattern(path, p));
+    let exc = exclude.iter().any(matches);
     !exc
 }
 
+/// One ordered filter rule, gitignore-style.
+///
+/// A matching rule excludes the path, or re-includes it when `negate` is set
+/// (`!pattern`). Rules are evaluated in order and the last match wins; a path
+/// no rule matches is included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub pattern: String,
+    pub negate: bool,
+}
+
+impl Rule {
+    /// Exclude paths matching `pattern`.
+    pub fn exclude(pattern: impl Into<String>) -> Self {
+        Self { pattern: pattern.into(), negate: false }
+    }
+
+    /// Re-include paths matching `pattern`.
+    pub fn include(pattern: impl Into<String>) -> Self {
+        Self { pattern: pattern.into(), negate: true }
+    }
+
+    /// Parse gitignore-style syntax: `!pattern` re-includes, anything else excludes.
+    pub fn parse(line: &str) -> Self {
+        match line.strip_prefix('!') {
+            Some(pattern) => Self::include(pattern),
+            None => Self::exclude(line),
+        }
+    }
+}
+
+/// The `is_included` lists as ordered rules with the same result for every path:
+/// with a non-empty `include`, exclude everything, re-include the `include`
+/// patterns, then apply `exclude` (so exclude wins over include).
+pub fn rules_from_lists(include: &[String], exclude: &[String]) -> Vec<Rule> {
+    let mut rules = Vec::new();
+    if !include.is_empty() {
+        rules.push(Rule::exclude("**"));
+        rules.extend(include.iter().map(|p| Rule::include(list_pattern_glob(p))));
+    }
+    rules.extend(exclude.iter().map(|p| Rule::exclude(list_pattern_glob(p))));
+    rules
+}
+
+/// Determine whether a path is included by ordered rules (last match wins).
+pub fn is_included_by_rules(path: &str, rules: &[Rule]) -> bool {
+    rules
+        .iter()
+        .rev()
+        .find(|r| matches_pattern(path, &r.pattern))
+        .map(|r| r.negate)
+        .unwrap_or(true)
+}
+
 /// Walk a set of virtual files deterministically, applying filters and limits.
 ///
-/// Output is a `Vec<RepoFile>` sorted by normalized path.
+/// Output is a `Vec<RepoFile>` sorted by normalized path. Files skipped under
+/// `OverflowPolicy::TruncateDeterministic` are dropped silently; use
+/// `walk_virtual_files_report` to see them.
 pub fn walk_virtual_files(files: &[VFile], opts: &WalkOptions) -> Result<Vec<RepoFile>> {
+    Ok(walk_virtual_files_report(files, opts)?.files)
+}
+
+/// Walk a set of virtual files deterministically and report skipped paths.
+pub fn walk_virtual_files_report(files: &[VFile], opts: &WalkOptions) -> Result<WalkResult> {
     let mut selected: Vec<(String, &VFile)> = Vec::new();
+    let mut rules = rules_from_lists(&opts.include, &opts.exclude);
+    rules.extend(opts.rules.iter().cloned());
 
     for f in files {
         let norm = normalize_repo_path(&f.path)?;
-        if is_included(&norm, &opts.include, &opts.exclude) {
+        if is_included_by_rules(&norm, &rules) {
             selected.push((norm, f));
         }
     }
 
     selected.sort_by(|a, b| a.0.cmp(&b.0));
 
-    if selected.len() as u64 > opts.max_files {
+    let truncate = opts.on_overflow == OverflowPolicy::TruncateDeterministic;
+
+    if !truncate && selected.len() as u64 > opts.max_files {
         return Err(anyhow!(
             "file count exceeds limit: files={}, max_files={}",
             selected.len(),
@@ -221,7 +290,8 @@ pub fn walk_virtual_files(files: &[VFile], opts: &WalkOptions) -> Result<Vec<Rep
     }
 
     let mut total = 0u64;
-    let mut out = Vec::with_capacity(selected.len());
+    let mut out = WalkResult::default();
+    let mut overflowed = false;
 
     for (path, f) in selected {
         let size = if let Some(b) = &f.bytes {
@@ -230,14 +300,29 @@ pub fn walk_virtual_files(files: &[VFile], opts: &WalkOptions) -> Result<Vec<Rep
             f.size
         };
 
-        total = total.saturating_add(size);
-        if total > opts.max_total_bytes {
+        if overflowed {
+            out.skipped.push(path);
+            continue;
+        }
+
+        let next_total = total.saturating_add(size);
+        let over_files = out.files.len() as u64 >= opts.max_files;
+        let over_bytes = next_total > opts.max_total_bytes;
+
+        if over_bytes && !truncate {
             return Err(anyhow!(
                 "total bytes exceeds limit: total_bytes={}, max_total_bytes={}",
-                total,
+                next_total,
                 opts.max_total_bytes
             ));
         }
+        if over_files || over_bytes {
+            // Stop at the first file that does not fit so the kept set is a sorted prefix.
+            overflowed = true;
+            out.skipped.push(path);
+            continue;
+        }
+        total = next_total;
 
         let rf = RepoFile {
             path,
@@ -246,7 +331,7 @@ pub fn walk_virtual_files(files: &[VFile], opts: &WalkOptions) -> Result<Vec<Rep
             mode: f.mode.clone(),
             bytes: if opts.include_contents { f.bytes.clone() } else { None },
         };
-        out.push(rf);
+        out.files.push(rf);
     }
 
     Ok(out)
@@ -272,6 +357,88 @@ mod tests {
         assert!(!is_included("README.md", &inc, &exc));
     }
 
+    #[test]
+    fn list_patterns_match_nested_directories() {
+        let exc = crate::builtin::config::RepoConfig::default().exclude;
+        let rules = rules_from_lists(&[], &exc);
+        for (path, expected) in [
+            ("node_modules/a.js", false),
+            ("web/node_modules/a.js", false),
+            ("crates/x/target/debug/x", false),
+            ("a/.git/HEAD", false),
+            ("src/build.rs", true),
+            ("src/lib.rs", true),
+        ] {
+            assert_eq!(is_included(path, &[], &exc), expected, "{path}");
+            assert_eq!(is_included_by_rules(path, &rules), expected, "{path}");
+        }
+
+        // Patterns without `**` stay anchored.
+        let exc = vec!["README.md".to_string()];
+        assert!(!is_included("README.md", &[], &exc));
+        assert!(is_included("docs/README.md", &[], &exc));
+    }
+
+    #[test]
+    fn patterns_are_anchored() {
+        assert!(matches_pattern("dist/a.js", "dist/**"));
+        assert!(!matches_pattern("src/dist/a.js", "dist/**"));
+        assert!(matches_pattern("src/dist/a.js", "**/dist/**"));
+        assert!(matches_pattern("test.rs", "**/test*"));
+        assert!(matches_pattern("a/b/lib.rs", "*.rs"));
+        assert!(!matches_pattern("lib.rsx", "*.rs"));
+        assert!(matches_pattern("anything/at/all", "**"));
+    }
+
+    #[test]
+    fn overlapping_include_exclude_exclude_wins() {
+        let inc = vec!["src/**".to_string()];
+        let exc = vec!["src/gen/**".to_string()];
+        let rules = rules_from_lists(&inc, &exc);
+
+        for (path, expected) in [("src/lib.rs", true), ("src/gen/api.rs", false), ("README.md", false)] {
+            assert_eq!(is_included(path, &inc, &exc), expected, "{path}");
+            assert_eq!(is_included_by_rules(path, &rules), expected, "{path}");
+        }
+        assert!(is_included_by_rules("anything", &rules_from_lists(&[], &[])));
+    }
+
+    #[test]
+    fn negation_reincludes_last_match_wins() {
+        let rules: Vec<Rule> = ["src/gen/**", "!src/gen/keep.rs", "*.tmp"]
+            .into_iter()
+            .map(Rule::parse)
+            .collect();
+        assert_eq!(rules[1], Rule::include("src/gen/keep.rs"));
+
+        assert!(is_included_by_rules("src/lib.rs", &rules));
+        assert!(!is_included_by_rules("src/gen/api.rs", &rules));
+        assert!(is_included_by_rules("src/gen/keep.rs", &rules));
+        assert!(!is_included_by_rules("src/gen/keep.tmp", &rules));
+
+        // Order matters: a later exclude overrides an earlier re-include.
+        let reversed = vec![Rule::include("src/gen/keep.rs"), Rule::exclude("src/gen/**")];
+        assert!(!is_included_by_rules("src/gen/keep.rs", &reversed));
+    }
+
+    #[test]
+    fn walk_applies_rules_after_lists() {
+        let files = vec![
+            VFile::new("src/lib.rs", 1),
+            VFile::new("src/gen/api.rs", 1),
+            VFile::new("src/gen/keep.rs", 1),
+        ];
+        let opts = WalkOptions {
+            exclude: vec!["src/gen/**".to_string()],
+            rules: vec![Rule::parse("!src/gen/keep.rs")],
+            ..WalkOptions::default()
+        };
+
+        let out = walk_virtual_files(&files, &opts).unwrap();
+        let paths: Vec<String> = out.into_iter().map(|f| f.path).collect();
+        assert_eq!(paths, vec!["src/gen/keep.rs", "src/lib.rs"]);
+    }
+
     #[test]
     fn walk_is_deterministic_sorted() {
         let files = vec![
@@ -284,4 +451,54 @@ mod tests {
         let paths: Vec<String> = out.into_iter().map(|f| f.path).collect();
         assert_eq!(paths, vec!["a.txt", "b.txt", "c.txt"]);
     }
+
+    #[test]
+    fn truncation_is_deterministic_and_reports_skipped() {
+        let opts = WalkOptions {
+            max_files: 10,
+            max_total_bytes: 5,
+            on_overflow: OverflowPolicy::TruncateDeterministic,
+            ..WalkOptions::default()
+        };
+
+        let a = vec![
+            VFile::new("d.txt", 1),
+            VFile::new("a.txt", 2),
+            VFile::new("c.txt", 2),
+            VFile::new("b.txt", 2),
+        ];
+        let mut b = a.clone();
+        b.reverse();
+
+        let ra = walk_virtual_files_report(&a, &opts).unwrap();
+        let rb = walk_virtual_files_report(&b, &opts).unwrap();
+
+        let kept: Vec<&str> = ra.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(kept, vec!["a.txt", "b.txt"]);
+        // d.txt would fit by size, but the kept set must stay a sorted prefix.
+        assert_eq!(ra.skipped, vec!["c.txt", "d.txt"]);
+        assert!(ra.is_truncated());
+
+        let kept_b: Vec<&str> = rb.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(kept, kept_b);
+        assert_eq!(ra.skipped, rb.skipped);
+
+        let by_count = WalkOptions {
+            max_files: 1,
+            on_overflow: OverflowPolicy::TruncateDeterministic,
+            ..WalkOptions::default()
+        };
+        let rc = walk_virtual_files_report(&a, &by_count).unwrap();
+        assert_eq!(rc.files.len(), 1);
+        assert_eq!(rc.skipped, vec!["b.txt", "c.txt", "d.txt"]);
+    }
+
+    #[test]
+    fn overflow_errors_by_default() {
+        let opts = WalkOptions {
+            max_total_bytes: 1,
+            ..WalkOptions::default()
+        };
+        assert!(walk_virtual_files(&[VFile::new("a", 2)], &opts).is_err());
+    }
 }
@@ -20,20 +20,21 @@ use crate::spec::PluginSpec;
 /// Built-in plugin ids shipped with this crate.
 ///
 /// Keep this list stable and append-only when possible.
-pub const BUILTIN_PLUGIN_IDS: [&str; 2] = ["builtin.repo", "builtin.dataset"];
+pub const BUILTIN_PLUGIN_IDS: [&str; 3] = ["builtin.repo", "builtin.dataset", "builtin.api.openapi"];
 
 /// Return deterministic specs for all built-in plugins.
 ///
 /// This is intentionally a pure function so it can be used in `--json` outputs
 /// and documentation generators.
 pub fn builtin_specs() -> Vec<PluginSpec> {
-    vec![repo_spec(), dataset_spec()]
+    vec![repo_spec(), dataset_spec(), openapi_spec()]
 }
 
 /// Register all built-in plugins into the provided registry.
 pub fn register_all(registry: &mut PluginRegistry) {
     crate::builtin::repo::register(registry);
     crate::builtin::dataset::register(registry);
+    crate::builtin::openapi::register(registry);
 }
 
 /// Spec for `builtin.repo`.
@@ -58,6 +59,15 @@ pub fn dataset_spec() -> PluginSpec {
         .meta("category", "data")
 }
 
+/// Spec for `builtin.api.openapi`.
+pub fn openapi_spec() -> PluginSpec {
+    PluginSpec::new("builtin.api.openapi", "OpenAPI Plugin", "0.1.0")
+        .support("openapi")
+        .want("network", false)
+        .want("filesystem", false)
+        .meta("category", "api")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,4 +87,14 @@ mod tests {
         let ids: Vec<&str> = specs.iter().map(|s| s.id.as_str()).collect();
         assert_eq!(ids, BUILTIN_PLUGIN_IDS);
     }
+
+    #[test]
+    fn register_all_registers_every_catalog_spec() {
+        let mut reg = PluginRegistry::default();
+        register_all(&mut reg);
+        assert_eq!(reg.list_ids().len(), BUILTIN_PLUGIN_IDS.len());
+        for id in BUILTIN_PLUGIN_IDS {
+            assert!(reg.get(id).is_some(), "{id} not registered");
+        }
+    }
 }
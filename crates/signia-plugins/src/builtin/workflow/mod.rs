@@ -17,10 +17,15 @@
 //!     ]
 //!   }
 //!
+//! Optional fields (`version`, `label`): a missing (or null) field is omitted from the IR
+//! and recorded as absent in the fingerprint; an empty string is a value like any
+//! other. `"label": ""` and no label are distinct inputs and never hash the same.
+//!
 //! Responsibilities:
 //! - validate and normalize workflow graph
 //! - enforce determinism (stable ordering, stable ids)
 //! - build `IrGraph` and attach stable fingerprint
+//! - report non-fatal findings (e.g. isolated nodes) as diagnostics
 //!
 //! Non-responsibilities:
 //! - executing the workflow
@@ -29,36 +34,55 @@
 
 #![cfg(feature = "builtin")]
 
+pub mod validate;
+#[cfg(feature = "yaml")]
+pub mod yaml;
+
 use std::collections::{BTreeMap, BTreeSet};
 
 use anyhow::{anyhow, Result};
 use serde_json::Value;
 
 use signia_core::determinism::hashing::hash_bytes_hex;
-use signia_core::model::ir::{IrEdge, IrGraph, IrNode};
-use signia_core::pipeline::context::PipelineContext;
+use signia_core::model::ir::{IrEdge, IrGraph, IrId, IrKey, IrNode};
+use signia_core::pipeline::context::{DiagnosticLevel, PipelineContext, PipelineDiagnostic};
 
+use crate::builtin::config::WorkflowConfig;
 use crate::plugin::{Plugin, PluginInput, PluginOutput};
 use crate::registry::PluginRegistry;
 use crate::spec::PluginSpec;
 
-/// Register the workflow plugin.
+/// Register the workflow plugin with the default `WorkflowConfig`.
 pub fn register(registry: &mut PluginRegistry) {
+    register_with_config(registry, WorkflowConfig::default());
+}
+
+/// Register the workflow plugin with `config`.
+pub fn register_with_config(registry: &mut PluginRegistry, config: WorkflowConfig) {
     let spec = PluginSpec::new("builtin.workflow", "Workflow Plugin", "0.1.0")
         .support("workflow")
-        .limit("max_nodes", 200_000)
-        .limit("max_edges", 400_000)
+        .limit("max_nodes", config.max_nodes as u64)
+        .limit("max_edges", config.max_edges as u64)
         .want("network", false)
         .want("filesystem", false)
         .meta("category", "orchestration");
 
     registry
-        .register(spec, Box::new(WorkflowPlugin))
+        .register(spec, Box::new(WorkflowPlugin::new(config)))
         .expect("failed to register builtin.workflow");
 }
 
 /// Workflow plugin implementation.
-pub struct WorkflowPlugin;
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowPlugin {
+    pub config: WorkflowConfig,
+}
+
+impl WorkflowPlugin {
+    pub fn new(config: WorkflowConfig) -> Self {
+        Self { config }
+    }
+}
 
 impl Plugin for WorkflowPlugin {
     fn name(&self) -> &str {
@@ -73,25 +97,30 @@ impl Plugin for WorkflowPlugin {
         input_type == "workflow"
     }
 
-    fn execute(&self, input: &PluginInput) -> Result<PluginOutput> {
+    fn execute(&self, input: PluginInput<'_>) -> Result<PluginOutput> {
         let ctx = match input {
             PluginInput::Pipeline(ctx) => ctx,
             _ => anyhow::bail!("workflow plugin requires pipeline input"),
         };
 
-        execute_workflow(ctx)?;
-        Ok(PluginOutput::None)
+        let diagnostics = execute_workflow(ctx, &self.config)?;
+        if diagnostics.is_empty() {
+            Ok(PluginOutput::None)
+        } else {
+            Ok(PluginOutput::Diagnostics(diagnostics))
+        }
     }
 }
 
-fn execute_workflow(ctx: &mut PipelineContext) -> Result<()> {
+/// Build the workflow IR into `ctx` and return non-fatal diagnostics.
+fn execute_workflow(ctx: &mut PipelineContext, config: &WorkflowConfig) -> Result<Vec<PipelineDiagnostic>> {
     let v = ctx
         .inputs
         .get("workflow")
         .ok_or_else(|| anyhow!("missing workflow input"))?;
 
     let name = get_str(v, "name")?;
-    let version = v.get("version").and_then(|x| x.as_str()).unwrap_or("unknown");
+    let version = get_opt_str(v, "version")?;
 
     let nodes = v
         .get("nodes")
@@ -128,6 +157,20 @@ fn execute_workflow(ctx: &mut PipelineContext) -> Result<()> {
         if !matches!(kind, "data" | "control" | "event") {
             return Err(anyhow!("invalid edge kind: {kind}"));
         }
+        get_opt_str(e, "label")?;
+    }
+
+    let mut diagnostics = Vec::new();
+    let isolated = validate::find_isolated_nodes(nodes, edges);
+    if config.forbid_isolated && !isolated.is_empty() {
+        return Err(anyhow!("workflow has isolated nodes: {}", isolated.join(", ")));
+    }
+    for id in isolated {
+        diagnostics.push(PipelineDiagnostic::new(
+            DiagnosticLevel::Warning,
+            "workflow.node.isolated",
+            format!("workflow node {id} has no edges"),
+        ));
     }
 
     // Deterministic ordering:
@@ -142,87 +185,95 @@ fn execute_workflow(ctx: &mut PipelineContext) -> Result<()> {
             get_str(a, "from").unwrap(),
             get_str(a, "to").unwrap(),
             get_str(a, "kind").unwrap(),
-            a.get("label").and_then(|x| x.as_str()).unwrap_or(""),
+            get_opt_str(a, "label").unwrap(),
         );
         let bk = (
             get_str(b, "from").unwrap(),
             get_str(b, "to").unwrap(),
             get_str(b, "kind").unwrap(),
-            b.get("label").and_then(|x| x.as_str()).unwrap_or(""),
+            get_opt_str(b, "label").unwrap(),
         );
         ak.cmp(&bk)
     });
 
     // Build IR
     let mut graph = IrGraph::new();
-    let root_id = graph.add_node(IrNode::new("workflow", name));
-    let ver_id = graph.add_node(IrNode::new("version", version));
-    graph.add_edge(IrEdge::new(root_id, ver_id, "version"));
+    let root_id = graph.add_node(IrNode::new("workflow", name))?;
+    if let Some(version) = version {
+        let ver_id = graph.add_node(IrNode::new("version", version))?;
+        graph.add_edge(IrEdge::new(root_id.as_str(), ver_id, "version"))?;
+    }
 
-    let mut id_to_ir: BTreeMap<String, u64> = BTreeMap::new();
+    // Scalar nodes (type, meta, kind) are shared by key across the graph.
+    let mut scalars: BTreeMap<IrKey, IrId> = BTreeMap::new();
+    let mut id_to_ir: BTreeMap<String, IrId> = BTreeMap::new();
 
-    for n in nodes_sorted {
+    for &n in &nodes_sorted {
         let id = get_str(n, "id")?;
         let t = get_str(n, "type")?;
         let label = format!("{id}:{t}");
-        let nid = graph.add_node(IrNode::new("node", label));
+        let nid = graph.add_node(IrNode::new("node", label))?;
 
         // Attach node type as a scalar node
-        let tid = graph.add_node(IrNode::new("type", t));
-        graph.add_edge(IrEdge::new(nid, tid, "has"));
+        let tid = scalar_node(&mut graph, &mut scalars, IrNode::new("type", t))?;
+        graph.add_edge(IrEdge::new(nid.as_str(), tid, "has"))?;
 
         // Attach stable metadata keys (if provided)
         if let Some(meta) = n.get("meta").and_then(|x| x.as_object()) {
             let mut keys: Vec<&String> = meta.keys().collect();
             keys.sort();
             for k in keys {
-                let val = meta.get(k).unwrap();
-                let vs = if val.is_string() {
-                    val.as_str().unwrap().to_string()
-                } else {
-                    // stable JSON string for non-string values
-                    serde_json::to_string(val)?
-                };
-                let mid = graph.add_node(IrNode::new("meta", format!("{k}={vs}")));
-                graph.add_edge(IrEdge::new(nid, mid, "meta"));
+                let vs = meta_value_string(meta.get(k).unwrap())?;
+                if vs.len() > config.max_meta_value_bytes {
+                    return Err(anyhow!(
+                        "workflow node {id} meta value {k} is {} bytes (max {})",
+                        vs.len(),
+                        config.max_meta_value_bytes
+                    ));
+                }
+                let mid = scalar_node(&mut graph, &mut scalars, IrNode::new("meta", format!("{k}={vs}")))?;
+                graph.add_edge(IrEdge::new(nid.as_str(), mid, "meta"))?;
             }
         }
 
-        graph.add_edge(IrEdge::new(root_id, nid, "contains"));
+        graph.add_edge(IrEdge::new(root_id.as_str(), nid.as_str(), "contains"))?;
         id_to_ir.insert(id.to_string(), nid);
     }
 
-    for e in edges_sorted {
+    for &e in &edges_sorted {
         let from = get_str(e, "from")?;
         let to = get_str(e, "to")?;
         let kind = get_str(e, "kind")?;
-        let label = e.get("label").and_then(|x| x.as_str()).unwrap_or("");
+        let label = get_opt_str(e, "label")?;
 
-        let from_id = *id_to_ir.get(from).unwrap();
-        let to_id = *id_to_ir.get(to).unwrap();
+        let from_id = id_to_ir[from].as_str();
+        let to_id = id_to_ir[to].as_str();
 
         // Represent as an edge node for richer modeling
-        let en = graph.add_node(IrNode::new("edge", format!("{from}->{to}:{kind}:{label}")));
-        graph.add_edge(IrEdge::new(root_id, en, "contains"));
-        graph.add_edge(IrEdge::new(en, from_id, "from"));
-        graph.add_edge(IrEdge::new(en, to_id, "to"));
+        let edge_name = match label {
+            Some(label) => format!("{from}->{to}:{kind}:{label}"),
+            None => format!("{from}->{to}:{kind}"),
+        };
+        let en = graph.add_node(IrNode::new("edge", edge_name))?;
+        graph.add_edge(IrEdge::new(root_id.as_str(), en.as_str(), "contains"))?;
+        graph.add_edge(IrEdge::new(en.as_str(), from_id, "from"))?;
+        graph.add_edge(IrEdge::new(en.as_str(), to_id, "to"))?;
 
-        let k_id = graph.add_node(IrNode::new("kind", kind));
-        graph.add_edge(IrEdge::new(en, k_id, "has"));
+        let k_id = scalar_node(&mut graph, &mut scalars, IrNode::new("kind", kind))?;
+        graph.add_edge(IrEdge::new(en.as_str(), k_id, "has"))?;
     }
 
     // Fingerprint: stable text concatenation (nodes + edges)
     let fingerprint = workflow_fingerprint(name, version, &nodes_sorted, &edges_sorted)?;
-    ctx.metadata
-        .insert("workflowFingerprint".to_string(), Value::String(fingerprint));
+    ctx.set_metadata_checked("workflowFingerprint", fingerprint)?;
 
     ctx.ir = Some(graph);
-    Ok(())
+    Ok(diagnostics)
 }
 
 fn workflow_fingerprint(
     name: &str,
-    version: &str,
+    version: Option<&str>,
     nodes_sorted: &[&Value],
     edges_sorted: &[&Value],
 ) -> Result<String> {
@@ -230,7 +281,7 @@ fn workflow_fingerprint(
     buf.extend_from_slice(b"workflow\n");
     buf.extend_from_slice(name.as_bytes());
     buf.extend_from_slice(b"\n");
-    buf.extend_from_slice(version.as_bytes());
+    push_opt(&mut buf, version);
     buf.extend_from_slice(b"\n");
 
     buf.extend_from_slice(b"nodes\n");
@@ -247,12 +298,7 @@ fn workflow_fingerprint(
             let mut keys: Vec<&String> = meta.keys().collect();
             keys.sort();
             for k in keys {
-                let val = meta.get(k).unwrap();
-                let vs = if val.is_string() {
-                    val.as_str().unwrap().to_string()
-                } else {
-                    serde_json::to_string(val)?
-                };
+                let vs = meta_value_string(meta.get(k).unwrap())?;
                 buf.extend_from_slice(b"meta\t");
                 buf.extend_from_slice(k.as_bytes());
                 buf.extend_from_slice(b"=");
@@ -267,7 +313,7 @@ fn workflow_fingerprint(
         let from = get_str(e, "from")?;
         let to = get_str(e, "to")?;
         let kind = get_str(e, "kind")?;
-        let label = e.get("label").and_then(|x| x.as_str()).unwrap_or("");
+        let label = get_opt_str(e, "label")?;
 
         buf.extend_from_slice(from.as_bytes());
         buf.extend_from_slice(b"\t");
@@ -275,11 +321,41 @@ fn workflow_fingerprint(
         buf.extend_from_slice(b"\t");
         buf.extend_from_slice(kind.as_bytes());
         buf.extend_from_slice(b"\t");
-        buf.extend_from_slice(label.as_bytes());
+        push_opt(&mut buf, label);
         buf.extend_from_slice(b"\n");
     }
 
-    hash_bytes_hex(&buf)
+    Ok(hash_bytes_hex(&buf)?)
+}
+
+/// Id of the node with `node`'s key, adding it on first use.
+fn scalar_node(graph: &mut IrGraph, scalars: &mut BTreeMap<IrKey, IrId>, node: IrNode) -> Result<IrId> {
+    if let Some(id) = scalars.get(&node.key) {
+        return Ok(id.clone());
+    }
+    let key = node.key.clone();
+    let id = graph.add_node(node)?;
+    scalars.insert(key, id.clone());
+    Ok(id)
+}
+
+/// Optional fingerprint field: `-` when missing, `=<value>` when present (even if empty).
+fn push_opt(buf: &mut Vec<u8>, value: Option<&str>) {
+    match value {
+        Some(v) => {
+            buf.push(b'=');
+            buf.extend_from_slice(v.as_bytes());
+        }
+        None => buf.push(b'-'),
+    }
+}
+
+/// Strings as-is; other values as stable JSON text.
+fn meta_value_string(val: &Value) -> Result<String> {
+    match val {
+        Value::String(s) => Ok(s.clone()),
+        other => Ok(serde_json::to_string(other)?),
+    }
 }
 
 fn get_str<'a>(v: &'a Value, key: &str) -> Result<&'a str> {
@@ -288,12 +364,23 @@ fn get_str<'a>(v: &'a Value, key: &str) -> Result<&'a str> {
         .ok_or_else(|| anyhow!("missing or invalid string field: {key}"))
 }
 
+/// `None` if `key` is absent (or null); an error if present but not a string.
+fn get_opt_str<'a>(v: &'a Value, key: &str) -> Result<Option<&'a str>> {
+    match v.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(_) => Err(anyhow!("invalid string field: {key}")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
     use signia_core::pipeline::context::PipelineConfig;
 
+    use crate::plugin::execute_pipeline;
+
     #[test]
     fn workflow_plugin_executes_and_fingerprints() {
         let mut ctx = PipelineContext::new(PipelineConfig::default());
@@ -312,13 +399,84 @@ mod tests {
             }),
         );
 
-        let plugin = WorkflowPlugin;
-        plugin.execute(&PluginInput::Pipeline(&mut ctx)).unwrap();
+        let plugin = WorkflowPlugin::default();
+        plugin.execute(PluginInput::Pipeline(&mut ctx)).unwrap();
 
         assert!(ctx.ir.is_some());
         assert!(ctx.metadata.get("workflowFingerprint").is_some());
     }
 
+    fn fingerprint_of(workflow: Value) -> String {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert("workflow".to_string(), workflow);
+        WorkflowPlugin::default()
+            .execute(PluginInput::Pipeline(&mut ctx))
+            .unwrap();
+        ctx.metadata["workflowFingerprint"].clone()
+    }
+
+    #[test]
+    fn empty_string_and_missing_field_fingerprint_differently() {
+        let nodes = json!([{"id":"a","type":"x"},{"id":"b","type":"y"}]);
+        let labeled = |edge: Value| json!({"name": "demo", "nodes": nodes, "edges": [edge]});
+
+        let empty_label = fingerprint_of(labeled(json!({"from":"a","to":"b","kind":"data","label":""})));
+        let no_label = fingerprint_of(labeled(json!({"from":"a","to":"b","kind":"data"})));
+        assert_ne!(empty_label, no_label);
+
+        let empty_version = fingerprint_of(json!({"name": "demo", "version": "", "nodes": [], "edges": []}));
+        let no_version = fingerprint_of(json!({"name": "demo", "nodes": [], "edges": []}));
+        assert_ne!(empty_version, no_version);
+    }
+
+    #[test]
+    fn non_string_label_is_rejected() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert(
+            "workflow".to_string(),
+            json!({
+                "name": "demo",
+                "nodes": [{"id":"a","type":"x"},{"id":"b","type":"y"}],
+                "edges": [{"from":"a","to":"b","kind":"data","label":7}]
+            }),
+        );
+
+        let err = WorkflowPlugin::default()
+            .execute(PluginInput::Pipeline(&mut ctx))
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid string field: label"), "{err}");
+    }
+
+    #[test]
+    fn shared_type_meta_and_kind_nodes_are_reused() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert(
+            "workflow".to_string(),
+            json!({
+                "name": "demo",
+                "nodes": [
+                    {"id":"a","type":"http","meta":{"retries":3}},
+                    {"id":"b","type":"http","meta":{"retries":3}},
+                    {"id":"c","type":"llm"}
+                ],
+                "edges": [
+                    {"from":"a","to":"b","kind":"data"},
+                    {"from":"b","to":"c","kind":"data"}
+                ]
+            }),
+        );
+        WorkflowPlugin::default().execute(PluginInput::Pipeline(&mut ctx)).unwrap();
+
+        let graph = ctx.ir.unwrap();
+        let count = |t: &str| graph.nodes.values().filter(|n| n.node_type == t).count();
+        assert_eq!(count("type"), 2);
+        assert_eq!(count("meta"), 1);
+        assert_eq!(count("kind"), 1);
+        let has = graph.edges.values().filter(|e| e.edge_type == "has").count();
+        assert_eq!(has, 5, "3 node types + 2 edge kinds");
+        graph.validate_basic().unwrap();
+    }
+
     #[test]
     fn duplicate_node_id_fails() {
         let mut ctx = PipelineContext::new(PipelineConfig::default());
@@ -334,8 +492,97 @@ mod tests {
             }),
         );
 
-        let plugin = WorkflowPlugin;
-        let r = plugin.execute(&PluginInput::Pipeline(&mut ctx));
+        let plugin = WorkflowPlugin::default();
+        let r = plugin.execute(PluginInput::Pipeline(&mut ctx));
         assert!(r.is_err());
     }
+
+    #[test]
+    fn oversized_meta_value_is_rejected() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert(
+            "workflow".to_string(),
+            json!({
+                "name": "demo",
+                "nodes": [
+                    {"id":"a","type":"x","meta":{"small":"ok","blob":{"data":"z".repeat(64)}}}
+                ],
+                "edges": []
+            }),
+        );
+
+        let plugin = WorkflowPlugin::new(WorkflowConfig {
+            max_meta_value_bytes: 32,
+            ..WorkflowConfig::default()
+        });
+        let err = plugin
+            .execute(PluginInput::Pipeline(&mut ctx))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("node a"), "{err}");
+        assert!(err.contains("blob"), "{err}");
+        assert!(ctx.ir.is_none());
+
+        // The default limit accepts it.
+        WorkflowPlugin::default()
+            .execute(PluginInput::Pipeline(&mut ctx))
+            .unwrap();
+    }
+
+    #[test]
+    fn isolated_node_warns_but_compiles() {
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert(
+            "workflow".to_string(),
+            json!({
+                "name": "demo",
+                "nodes": [
+                    {"id":"a","type":"x"},
+                    {"id":"b","type":"y"},
+                    {"id":"lonely","type":"z"}
+                ],
+                "edges": [
+                    {"from":"a","to":"b","kind":"data"}
+                ]
+            }),
+        );
+
+        let out = execute_pipeline(&WorkflowPlugin::default(), &mut ctx).unwrap();
+        assert!(matches!(out, PluginOutput::None));
+        assert!(ctx.ir.is_some());
+
+        let warnings: Vec<_> = ctx
+            .diagnostics
+            .iter()
+            .filter(|d| matches!(d.level, DiagnosticLevel::Warning))
+            .collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, "workflow.node.isolated");
+        assert!(warnings[0].message.contains("lonely"));
+
+        let strict = WorkflowPlugin::new(WorkflowConfig {
+            forbid_isolated: true,
+            ..WorkflowConfig::default()
+        });
+        let mut ctx = PipelineContext::new(PipelineConfig::default());
+        ctx.inputs.insert(
+            "workflow".to_string(),
+            json!({
+                "name": "demo",
+                "nodes": [{"id":"a","type":"x"}, {"id":"b","type":"y"}],
+                "edges": []
+            }),
+        );
+        let err = execute_pipeline(&strict, &mut ctx).unwrap_err().to_string();
+        assert!(err.contains("isolated nodes: a, b"), "{err}");
+        assert!(ctx.ir.is_none());
+
+        // A single-node workflow is valid even when isolation is forbidden.
+        ctx.inputs.insert(
+            "workflow".to_string(),
+            json!({"name": "demo", "nodes": [{"id":"a","type":"x"}], "edges": []}),
+        );
+        execute_pipeline(&strict, &mut ctx).unwrap();
+        assert!(ctx.diagnostics.is_empty());
+    }
 }
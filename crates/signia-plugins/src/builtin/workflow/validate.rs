@@ -55,7 +55,8 @@ pub fn validate_workflow(v: &Value) -> Result<()> {
     let edges = obj
         .get("edges")
         .and_then(|x| x.as_array())
-        .unwrap_or(&Vec::new());
+        .map(|x| x.as_slice())
+        .unwrap_or(&[]);
 
     validate_nodes(nodes)?;
     validate_edges(nodes, edges)?;
@@ -167,6 +168,34 @@ fn validate_edges(nodes: &[Value], edges: &[Value]) -> Result<()> {
     Ok(())
 }
 
+/// Ids of nodes that no edge starts or ends at, sorted.
+///
+/// A single-node workflow has no isolated nodes: it cannot have edges.
+/// Nodes and edges without string ids are ignored (see `validate_workflow`).
+pub fn find_isolated_nodes(nodes: &[Value], edges: &[Value]) -> Vec<String> {
+    if nodes.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut connected = BTreeSet::<&str>::new();
+    for e in edges {
+        for end in ["from", "to"] {
+            if let Some(id) = e.get(end).and_then(|x| x.as_str()) {
+                connected.insert(id);
+            }
+        }
+    }
+
+    let ids: BTreeSet<&str> = nodes
+        .iter()
+        .filter_map(|n| n.get("id").and_then(|x| x.as_str()))
+        .collect();
+    ids.into_iter()
+        .filter(|id| !connected.contains(id))
+        .map(str::to_string)
+        .collect()
+}
+
 /// Build a deterministic summary useful for debugging and CI logs.
 pub fn workflow_summary(v: &Value) -> Result<BTreeMap<String, usize>> {
     let obj = v
@@ -181,7 +210,8 @@ pub fn workflow_summary(v: &Value) -> Result<BTreeMap<String, usize>> {
     let edges = obj
         .get("edges")
         .and_then(|x| x.as_array())
-        .unwrap_or(&Vec::new());
+        .map(|x| x.as_slice())
+        .unwrap_or(&[]);
 
     let mut out = BTreeMap::new();
     out.insert("nodes".to_string(), nodes.len());
@@ -235,4 +265,22 @@ mod tests {
         });
         assert!(validate_workflow(&v).is_err());
     }
+
+    #[test]
+    fn isolated_nodes_are_found() {
+        let nodes = vec![
+            json!({ "id": "c", "type": "x" }),
+            json!({ "id": "a", "type": "x" }),
+            json!({ "id": "b", "type": "y" }),
+            json!({ "id": "d", "type": "z" }),
+        ];
+        let edges = vec![json!({ "from": "a", "to": "b", "kind": "data" })];
+        assert_eq!(find_isolated_nodes(&nodes, &edges), vec!["c", "d"]);
+
+        let self_loop = vec![json!({ "from": "c", "to": "c", "kind": "control" })];
+        assert_eq!(find_isolated_nodes(&nodes[..2], &self_loop), vec!["a"]);
+
+        // A lone node is a valid workflow, not an isolated one.
+        assert!(find_isolated_nodes(&nodes[..1], &[]).is_empty());
+    }
 }
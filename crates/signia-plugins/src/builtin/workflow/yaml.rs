@@ -24,7 +24,9 @@
 use anyhow::{anyhow, Result};
 use serde_json::Value;
 
-use signia_core::determinism::canonical_json::canonicalize_json;
+use signia_core::determinism::canonical_json::{
+    canonicalize_json, canonicalize_with, CanonicalJsonOptions, FloatPolicy,
+};
 
 /// Parse workflow YAML to the canonical JSON shape used by SIGNIA.
 ///
@@ -60,7 +62,16 @@ pub fn parse_workflow_yaml(yaml_text: &str) -> Result<Value> {
 }
 
 /// Convert YAML value to JSON deterministically.
+///
+/// Non-integer floats stay JSON numbers (`FloatPolicy::AllowNative`); hashing
+/// the result still rejects them.
 pub fn yaml_to_json(v: &serde_yaml::Value) -> Result<Value> {
+    yaml_to_json_with(v, &CanonicalJsonOptions { float_policy: FloatPolicy::AllowNative })
+}
+
+/// `yaml_to_json` with explicit options: non-integer floats are handled per
+/// `opts.float_policy`.
+pub fn yaml_to_json_with(v: &serde_yaml::Value, opts: &CanonicalJsonOptions) -> Result<Value> {
     match v {
         serde_yaml::Value::Null => Ok(Value::Null),
         serde_yaml::Value::Bool(b) => Ok(Value::Bool(*b)),
@@ -71,9 +82,8 @@ pub fn yaml_to_json(v: &serde_yaml::Value) -> Result<Value> {
             } else if let Some(u) = n.as_u64() {
                 Ok(Value::Number(serde_json::Number::from(u)))
             } else if let Some(f) = n.as_f64() {
-                serde_json::Number::from_f64(f)
-                    .map(Value::Number)
-                    .ok_or_else(|| anyhow!("invalid float in yaml"))
+                let n = serde_json::Number::from_f64(f).ok_or_else(|| anyhow!("invalid float in yaml"))?;
+                canonicalize_with(&Value::Number(n), opts).map_err(|e| anyhow!("yaml: {e}"))
             } else {
                 Err(anyhow!("unknown numeric type in yaml"))
             }
@@ -82,7 +92,7 @@ pub fn yaml_to_json(v: &serde_yaml::Value) -> Result<Value> {
         serde_yaml::Value::Sequence(seq) => {
             let mut out = Vec::with_capacity(seq.len());
             for item in seq {
-                out.push(yaml_to_json(item)?);
+                out.push(yaml_to_json_with(item, opts)?);
             }
             Ok(Value::Array(out))
         }
@@ -102,7 +112,7 @@ pub fn yaml_to_json(v: &serde_yaml::Value) -> Result<Value> {
                         ks.trim().to_string()
                     }
                 };
-                pairs.push((key, yaml_to_json(v2)?));
+                pairs.push((key, yaml_to_json_with(v2, opts)?));
             }
 
             // Deterministic ordering by key
@@ -201,4 +211,17 @@ edges: []
         let s2 = serde_json::to_string(&parse_workflow_yaml(y).unwrap()).unwrap();
         assert_eq!(s1, s2);
     }
+
+    #[test]
+    fn yaml_floats_follow_float_policy() {
+        let y: serde_yaml::Value = serde_yaml::from_str("a: 0.1\nb: 1.0\nc: 1.0e20\n").unwrap();
+        let with = |float_policy| yaml_to_json_with(&y, &CanonicalJsonOptions { float_policy });
+
+        assert_eq!(yaml_to_json(&y).unwrap(), serde_json::json!({"a": 0.1, "b": 1, "c": 1e20}));
+        assert!(with(FloatPolicy::Reject).is_err());
+        assert_eq!(
+            with(FloatPolicy::RoundTripString).unwrap(),
+            serde_json::json!({"a": "0.1", "b": 1, "c": "1e+20"})
+        );
+    }
 }
@@ -150,11 +150,7 @@ mod tests {
         )
         .with_setting("x", "y");
 
-        ctx.emit_diag(PipelineDiagnostic {
-            level: DiagnosticLevel::Info,
-            code: "note".to_string(),
-            message: "hello".to_string(),
-        });
+        ctx.emit_diag(PipelineDiagnostic::new(DiagnosticLevel::Info, "note", "hello"));
 
         assert_eq!(ctx.settings.get("x").unwrap(), "y");
         assert_eq!(ctx.take_diags().len(), 1);
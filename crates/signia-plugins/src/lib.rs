@@ -5,6 +5,7 @@
 //! - plugin registry and resolution
 //! - built-in plugins (feature: `builtin`)
 //! - optional WASM sandbox runner (feature: `wasm`)
+//! - optional YAML-to-JSON input conversion (feature: `yaml`)
 //!
 //! Design principles:
 //! - deterministic execution: same input -> same output
@@ -26,22 +27,28 @@ pub mod builtin;
 pub mod sandbox;
 
 pub use plugin::{
-    HostCapabilities, Plugin, PluginError, PluginInput, PluginOutput, PluginResult,
-    PluginVersion,
+    plugin_fingerprint, HostCapabilities, Plugin, PluginError, PluginInput, PluginInputKind,
+    PluginOutput, PluginResult, PluginVersion,
 };
 pub use registry::{PluginRegistry, PluginResolver, ResolvedPlugin};
 
 /// Crate version.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Cargo features this build was compiled with.
+pub mod features {
+    /// Built-in plugins are available.
+    pub const BUILTIN: bool = cfg!(feature = "builtin");
+    /// The WASM sandbox runner is available.
+    pub const WASM: bool = cfg!(feature = "wasm");
+}
+
 /// Convenience: create a default registry with built-in plugins registered.
 ///
 /// This is typically used by CLI and API layers.
 #[cfg(feature = "builtin")]
 pub fn default_registry() -> PluginRegistry {
-    let mut reg = PluginRegistry::new();
-    builtin::register_all(&mut reg);
-    reg
+    PluginRegistry::with_builtins()
 }
 
 #[cfg(test)]
@@ -0,0 +1,409 @@
+//! Plugin trait and execution interface for SIGNIA.
+//!
+//! A plugin is a deterministic transformation over a structured input.
+//! The host (CLI/API) decides which input shape to hand to a plugin:
+//! - `Pipeline`: in-process plugins that read/write the shared pipeline context
+//! - `Json`: structured payloads (e.g. pre-parsed configs)
+//! - `Bytes`: raw payloads (e.g. sandboxed/WASM plugins)
+//!
+//! Plugins declare which shapes they accept via `Plugin::accepts`. Hosts should
+//! check this before calling `execute` so that mismatches fail early with a clear
+//! error instead of inside plugin code.
+
+use serde_json::Value;
+
+use signia_core::determinism::hashing::{hash_bytes, HashAlg};
+use signia_core::pipeline::context::{PipelineContext, PipelineDiagnostic};
+
+/// Result type returned by plugin execution.
+pub type PluginResult<T> = anyhow::Result<T>;
+
+/// Structured plugin errors.
+///
+/// These convert into `anyhow::Error` and can be downcast by hosts.
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    /// The plugin does not accept the provided input shape.
+    #[error("plugin {plugin} does not accept {kind} input")]
+    UnsupportedInput {
+        /// Plugin name.
+        plugin: String,
+        /// Input shape that was rejected.
+        kind: PluginInputKind,
+    },
+
+    /// The input shape was accepted but its content is invalid.
+    #[error("invalid plugin input: {0}")]
+    InvalidInput(String),
+}
+
+/// Host capabilities exposed to a plugin.
+///
+/// All capabilities default to denied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HostCapabilities {
+    /// Network access.
+    pub network: bool,
+    /// Filesystem access.
+    pub filesystem: bool,
+    /// Clock access.
+    pub clock: bool,
+    /// Child process spawn.
+    pub spawn: bool,
+}
+
+/// A requested plugin version (exact match).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginVersion(pub String);
+
+/// Input handed to a plugin.
+#[derive(Debug)]
+pub enum PluginInput<'a> {
+    /// No input.
+    None,
+
+    /// In-process pipeline context.
+    Pipeline(&'a mut PipelineContext),
+
+    /// Structured JSON payload.
+    Json(Value),
+
+    /// Raw bytes payload (used by sandboxed plugins).
+    Bytes(Vec<u8>),
+}
+
+impl<'a> PluginInput<'a> {
+    /// Return the shape of this input.
+    pub fn kind(&self) -> PluginInputKind {
+        match self {
+            PluginInput::None => PluginInputKind::None,
+            PluginInput::Pipeline(_) => PluginInputKind::Pipeline,
+            PluginInput::Json(_) => PluginInputKind::Json,
+            PluginInput::Bytes(_) => PluginInputKind::Bytes,
+        }
+    }
+}
+
+/// Shape of a `PluginInput`, without its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PluginInputKind {
+    /// `PluginInput::None`.
+    None,
+    /// `PluginInput::Pipeline`.
+    Pipeline,
+    /// `PluginInput::Json`.
+    Json,
+    /// `PluginInput::Bytes`.
+    Bytes,
+}
+
+impl PluginInputKind {
+    /// Stable string form.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluginInputKind::None => "none",
+            PluginInputKind::Pipeline => "pipeline",
+            PluginInputKind::Json => "json",
+            PluginInputKind::Bytes => "bytes",
+        }
+    }
+}
+
+impl std::fmt::Display for PluginInputKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Output produced by a plugin.
+#[derive(Debug, Clone)]
+pub enum PluginOutput {
+    /// No direct output (results were written into the pipeline context).
+    None,
+
+    /// Structured JSON output.
+    Json(Value),
+
+    /// Raw bytes output.
+    Bytes(Vec<u8>),
+
+    /// Non-fatal findings (e.g. warnings). Hosts append these to the pipeline
+    /// diagnostics; see `execute_pipeline`.
+    Diagnostics(Vec<PipelineDiagnostic>),
+}
+
+/// A SIGNIA plugin.
+///
+/// Implementations must be deterministic: the same input yields the same output.
+pub trait Plugin: Send + Sync {
+    /// Plugin name.
+    fn name(&self) -> &str;
+
+    /// Plugin version.
+    fn version(&self) -> &str;
+
+    /// Returns true if the plugin handles the given input type (e.g. "repo").
+    fn supports(&self, input_type: &str) -> bool;
+
+    /// Returns true if the plugin accepts the given input shape.
+    ///
+    /// Defaults to `Pipeline` only, which is what in-process plugins use.
+    fn accepts(&self, kind: PluginInputKind) -> bool {
+        kind == PluginInputKind::Pipeline
+    }
+
+    /// Execute the plugin.
+    ///
+    /// The input is taken by value, so a `Pipeline` input hands the plugin a
+    /// plain `&mut PipelineContext` it can write (`ir`, `metadata`, ...) for the
+    /// duration of the call.
+    fn execute(&self, input: PluginInput<'_>) -> PluginResult<PluginOutput>;
+
+    /// Stable fingerprint for cache keys; recorded in the manifest's `PluginRefV1`.
+    ///
+    /// Defaults to `plugin_fingerprint(name, version, &[])`. Plugins whose output
+    /// also depends on configuration should override this and pass that
+    /// configuration (in a canonical form) as `extra`.
+    fn fingerprint(&self) -> String {
+        plugin_fingerprint(self.name(), self.version(), &[])
+    }
+}
+
+/// Lowercase hex sha256 over a domain tag, `name`, `version` and `extra`.
+///
+/// Every part is length-prefixed, so different splits of the same characters
+/// never share a fingerprint.
+pub fn plugin_fingerprint(name: &str, version: &str, extra: &[&str]) -> String {
+    let mut buf = b"signia:plugin-fingerprint:v1".to_vec();
+    for part in [name, version].iter().chain(extra) {
+        buf.extend_from_slice(&(part.len() as u64).to_le_bytes());
+        buf.extend_from_slice(part.as_bytes());
+    }
+    hash_bytes(HashAlg::Sha256, &buf)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Fail with `PluginError::UnsupportedInput` if `plugin` does not accept `input`.
+pub fn ensure_accepts(plugin: &dyn Plugin, input: &PluginInput) -> PluginResult<()> {
+    let kind = input.kind();
+    if !plugin.accepts(kind) {
+        return Err(PluginError::UnsupportedInput {
+            plugin: plugin.name().to_string(),
+            kind,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Execute `plugin` over a pipeline context.
+///
+/// `PluginOutput::Diagnostics` are appended to `ctx.diagnostics`, capped at
+/// `ctx.max_diagnostics`, and `None` is returned in their place; other outputs
+/// are returned unchanged.
+pub fn execute_pipeline(plugin: &dyn Plugin, ctx: &mut PipelineContext) -> PluginResult<PluginOutput> {
+    let out = plugin.execute(PluginInput::Pipeline(&mut *ctx))?;
+    match out {
+        PluginOutput::Diagnostics(diagnostics) => {
+            ctx.diagnostics.extend(diagnostics);
+            ctx.truncate_diagnostics();
+            Ok(PluginOutput::None)
+        }
+        other => Ok(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use signia_core::pipeline::context::DiagnosticLevel;
+
+    struct JsonOnlyPlugin;
+
+    impl Plugin for JsonOnlyPlugin {
+        fn name(&self) -> &str {
+            "json-only"
+        }
+        fn version(&self) -> &str {
+            "0.1.0"
+        }
+        fn supports(&self, input_type: &str) -> bool {
+            input_type == "config"
+        }
+        fn accepts(&self, kind: PluginInputKind) -> bool {
+            kind == PluginInputKind::Json
+        }
+        fn execute(&self, input: PluginInput<'_>) -> PluginResult<PluginOutput> {
+            ensure_accepts(self, &input)?;
+            match input {
+                PluginInput::Json(v) => Ok(PluginOutput::Json(json!({ "keys": v.as_object().map(|o| o.len()) }))),
+                _ => Err(PluginError::InvalidInput("expected JSON".to_string()).into()),
+            }
+        }
+    }
+
+    #[test]
+    fn json_plugin_accepts_json() {
+        let out = JsonOnlyPlugin.execute(PluginInput::Json(json!({"a": 1, "b": 2}))).unwrap();
+        match out {
+            PluginOutput::Json(v) => assert_eq!(v["keys"], 2),
+            other => panic!("unexpected output: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn json_plugin_rejects_pipeline() {
+        let mut ctx = PipelineContext::default();
+        let err = JsonOnlyPlugin.execute(PluginInput::Pipeline(&mut ctx)).unwrap_err();
+        match err.downcast_ref::<PluginError>() {
+            Some(PluginError::UnsupportedInput { kind, .. }) => assert_eq!(*kind, PluginInputKind::Pipeline),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn pipeline_plugin_mutates_context_ir() {
+        use signia_core::model::ir::{IrGraph, IrNode};
+
+        struct IrWriter;
+        impl Plugin for IrWriter {
+            fn name(&self) -> &str {
+                "ir-writer"
+            }
+            fn version(&self) -> &str {
+                "0.1.0"
+            }
+            fn supports(&self, _input_type: &str) -> bool {
+                true
+            }
+            fn execute(&self, input: PluginInput<'_>) -> PluginResult<PluginOutput> {
+                ensure_accepts(self, &input)?;
+                let PluginInput::Pipeline(ctx) = input else {
+                    return Err(PluginError::InvalidInput("expected pipeline".to_string()).into());
+                };
+                let mut graph = IrGraph::new();
+                graph.add_node(IrNode::new("file", "a.rs"))?;
+                ctx.ir = Some(graph);
+                ctx.set_metadata("writer", self.name());
+                Ok(PluginOutput::None)
+            }
+        }
+
+        let mut ctx = PipelineContext::default();
+        IrWriter.execute(PluginInput::Pipeline(&mut ctx)).unwrap();
+        assert_eq!(ctx.ir.as_ref().map(|g| g.nodes.len()), Some(1));
+        assert_eq!(ctx.metadata.get("writer").map(String::as_str), Some("ir-writer"));
+
+        // Same through the host helper; the context stays usable afterwards.
+        let mut ctx = PipelineContext::default();
+        execute_pipeline(&IrWriter, &mut ctx).unwrap();
+        assert!(ctx.ir.is_some());
+        assert!(IrWriter.execute(PluginInput::None).is_err());
+    }
+
+    #[test]
+    fn default_accepts_is_pipeline_only() {
+        struct P;
+        impl Plugin for P {
+            fn name(&self) -> &str {
+                "p"
+            }
+            fn version(&self) -> &str {
+                "0.1.0"
+            }
+            fn supports(&self, _input_type: &str) -> bool {
+                true
+            }
+            fn execute(&self, _input: PluginInput<'_>) -> PluginResult<PluginOutput> {
+                Ok(PluginOutput::None)
+            }
+        }
+        assert!(P.accepts(PluginInputKind::Pipeline));
+        assert!(!P.accepts(PluginInputKind::Bytes));
+    }
+
+    /// Same name/version as `JsonOnlyPlugin`, plus a behavior-affecting setting.
+    struct ConfiguredPlugin {
+        max_depth: u32,
+    }
+
+    impl Plugin for ConfiguredPlugin {
+        fn name(&self) -> &str {
+            "json-only"
+        }
+        fn version(&self) -> &str {
+            "0.1.0"
+        }
+        fn supports(&self, _input_type: &str) -> bool {
+            true
+        }
+        fn execute(&self, _input: PluginInput<'_>) -> PluginResult<PluginOutput> {
+            Ok(PluginOutput::None)
+        }
+        fn fingerprint(&self) -> String {
+            plugin_fingerprint(self.name(), self.version(), &[&format!("max_depth={}", self.max_depth)])
+        }
+    }
+
+    #[test]
+    fn fingerprint_defaults_to_name_and_version() {
+        struct Twin;
+        impl Plugin for Twin {
+            fn name(&self) -> &str {
+                "json-only"
+            }
+            fn version(&self) -> &str {
+                "0.1.0"
+            }
+            fn supports(&self, _input_type: &str) -> bool {
+                false
+            }
+            fn execute(&self, _input: PluginInput<'_>) -> PluginResult<PluginOutput> {
+                Ok(PluginOutput::None)
+            }
+        }
+
+        let fp = JsonOnlyPlugin.fingerprint();
+        assert_eq!(fp.len(), 64);
+        assert_eq!(fp, Twin.fingerprint());
+        assert_eq!(fp, plugin_fingerprint("json-only", "0.1.0", &[]));
+        assert_ne!(fp, plugin_fingerprint("json-only", "0.1.1", &[]));
+        assert_ne!(plugin_fingerprint("ab", "c", &[]), plugin_fingerprint("a", "bc", &[]));
+
+        let a = ConfiguredPlugin { max_depth: 4 }.fingerprint();
+        assert_ne!(a, fp);
+        assert_eq!(a, ConfiguredPlugin { max_depth: 4 }.fingerprint());
+        assert_ne!(a, ConfiguredPlugin { max_depth: 8 }.fingerprint());
+    }
+
+    #[test]
+    fn pipeline_diagnostics_are_capped() {
+        struct Noisy;
+        impl Plugin for Noisy {
+            fn name(&self) -> &str {
+                "noisy"
+            }
+            fn version(&self) -> &str {
+                "0.1.0"
+            }
+            fn supports(&self, _input_type: &str) -> bool {
+                true
+            }
+            fn execute(&self, _input: PluginInput<'_>) -> PluginResult<PluginOutput> {
+                let warnings = (0..10)
+                    .map(|i| PipelineDiagnostic::new(DiagnosticLevel::Warning, "noisy.warn", format!("warn {i}")))
+                    .collect();
+                Ok(PluginOutput::Diagnostics(warnings))
+            }
+        }
+
+        let mut ctx = PipelineContext { max_diagnostics: 4, ..PipelineContext::default() };
+        execute_pipeline(&Noisy, &mut ctx).unwrap();
+        assert_eq!(ctx.diagnostics.len(), 5);
+        assert_eq!(ctx.diagnostics[3].message, "warn 3");
+        assert_eq!(ctx.diagnostics[4].data["omitted"], "6");
+    }
+}
@@ -33,6 +33,16 @@ impl PluginRegistry {
         }
     }
 
+    /// Create a registry with every built-in plugin registered (default configs).
+    ///
+    /// Unlike `new()`/`default()`, which are always empty.
+    #[cfg(feature = "builtin")]
+    pub fn with_builtins() -> Self {
+        let mut reg = Self::new();
+        crate::builtin::register_all(&mut reg);
+        reg
+    }
+
     /// Number of registered plugins.
     pub fn len(&self) -> usize {
         self.plugins.len()
@@ -69,12 +79,24 @@ impl PluginRegistry {
         self.plugins.keys().cloned().collect()
     }
 
+    /// List plugin specs (cloned) in deterministic id order.
+    pub fn list(&self) -> Vec<PluginSpec> {
+        self.plugins.values().map(|p| p.spec.clone()).collect()
+    }
+
     /// Iterate over registered plugins in deterministic id order.
     pub fn iter(&self) -> impl Iterator<Item = (&String, &RegisteredPlugin)> {
         self.plugins.iter()
     }
 }
 
+impl Default for PluginRegistry {
+    /// An empty registry, same as `new()`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Plugin resolution request.
 #[derive(Debug, Clone)]
 pub struct PluginResolver {
@@ -93,12 +115,12 @@ impl PluginResolver {
     /// - require exact id match
     /// - if version constraint provided, require plugin.version() to match
     /// - evaluate host capability compatibility via PluginSpec wants
-    pub fn resolve(
+    pub fn resolve<'r>(
         &self,
-        registry: &PluginRegistry,
+        registry: &'r PluginRegistry,
         id: &str,
         version: Option<PluginVersion>,
-    ) -> anyhow::Result<ResolvedPlugin<'_>> {
+    ) -> anyhow::Result<ResolvedPlugin<'r>> {
         let reg = registry
             .get(id)
             .ok_or_else(|| anyhow::anyhow!("plugin not found: {id}"))?;
@@ -129,6 +151,41 @@ impl PluginResolver {
             evaluation: ev,
         })
     }
+
+    /// Resolve the plugin that handles `input_type`.
+    ///
+    /// Exact `supports` entries win; declared prefixes are only consulted when
+    /// no spec supports the type exactly. More than one candidate at the
+    /// deciding step is an ambiguity error.
+    pub fn resolve_for_type<'r>(
+        &self,
+        registry: &'r PluginRegistry,
+        input_type: &str,
+    ) -> anyhow::Result<ResolvedPlugin<'r>> {
+        let exact: Vec<&String> = registry
+            .iter()
+            .filter(|(_, r)| r.spec.supports_type(input_type))
+            .map(|(id, _)| id)
+            .collect();
+        let candidates = if exact.is_empty() {
+            registry
+                .iter()
+                .filter(|(_, r)| r.spec.supports_type_prefix(input_type))
+                .map(|(id, _)| id)
+                .collect()
+        } else {
+            exact
+        };
+
+        match candidates.as_slice() {
+            [] => anyhow::bail!("no plugin supports input type: {input_type}"),
+            [id] => self.resolve(registry, id, None),
+            many => anyhow::bail!(
+                "ambiguous plugins for input type {input_type}: {}",
+                many.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
 }
 
 /// A resolved plugin reference.
@@ -165,11 +222,40 @@ mod tests {
         fn supports(&self, input_type: &str) -> bool {
             input_type == "x"
         }
-        fn execute(&self, _input: &PluginInput) -> crate::plugin::PluginResult<PluginOutput> {
+        fn execute(&self, _input: PluginInput<'_>) -> crate::plugin::PluginResult<PluginOutput> {
             Ok(PluginOutput::None)
         }
     }
 
+    #[test]
+    fn default_is_empty_with_builtins_is_not() {
+        assert!(PluginRegistry::default().is_empty());
+        assert_eq!(PluginRegistry::default().list_ids(), PluginRegistry::new().list_ids());
+
+        #[cfg(feature = "builtin")]
+        {
+            let reg = PluginRegistry::with_builtins();
+            for id in ["builtin.api.openapi", "builtin.dataset", "builtin.repo", "builtin.workflow"] {
+                assert!(reg.get(id).is_some(), "{id} not registered");
+            }
+        }
+    }
+
+    #[test]
+    fn list_returns_specs_sorted_by_id() {
+        let mut reg = PluginRegistry::new();
+        for id in ["c.plugin", "a.plugin", "b.plugin"] {
+            reg.register(PluginSpec::new(id, "Test", "0.1.0").support("x"), Box::new(TestPlugin))
+                .unwrap();
+        }
+
+        let specs = reg.list();
+        let ids: Vec<&str> = specs.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["a.plugin", "b.plugin", "c.plugin"]);
+        assert_eq!(ids, reg.list_ids());
+        assert_eq!(specs[0], reg.get("a.plugin").unwrap().spec);
+    }
+
     #[test]
     fn registry_register_and_resolve() {
         let mut reg = PluginRegistry::new();
@@ -189,4 +275,27 @@ mod tests {
         let resolved = resolver.resolve(&reg, "builtin.test", None).unwrap();
         assert_eq!(resolved.version(), "0.1.0");
     }
+
+    #[test]
+    fn resolve_for_type_prefers_exact_over_prefix() {
+        let mut reg = PluginRegistry::new();
+        reg.register(PluginSpec::new("api.any", "Any", "0.1.0").support_prefix("openapi"), Box::new(TestPlugin))
+            .unwrap();
+        reg.register(PluginSpec::new("api.v3", "V3", "0.1.0").support("openapi.v3"), Box::new(TestPlugin))
+            .unwrap();
+        let resolver = PluginResolver::new(HostCapabilities::default());
+
+        assert_eq!(resolver.resolve_for_type(&reg, "openapi.v3").unwrap().id.as_str(), "api.v3");
+        assert_eq!(resolver.resolve_for_type(&reg, "openapi.v2").unwrap().id.as_str(), "api.any");
+        assert_eq!(resolver.resolve_for_type(&reg, "openapi").unwrap().id.as_str(), "api.any");
+        assert!(resolver.resolve_for_type(&reg, "openapix").is_err());
+
+        reg.register(PluginSpec::new("api.other", "Other", "0.1.0").support_prefix("openapi"), Box::new(TestPlugin))
+            .unwrap();
+        let err = resolver.resolve_for_type(&reg, "openapi.v2").err().unwrap().to_string();
+        assert!(err.contains("ambiguous"), "{err}");
+        assert!(err.contains("api.any, api.other"), "{err}");
+        // An exact match still decides.
+        assert_eq!(resolver.resolve_for_type(&reg, "openapi.v3").unwrap().id.as_str(), "api.v3");
+    }
 }
@@ -13,9 +13,20 @@
 
 #![cfg(feature = "wasm")]
 
-use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use crate::plugin::{HostCapabilities, PluginInput, PluginOutput, PluginResult};
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+use signia_core::determinism::hashing::{hash_bytes, HashAlg};
+
+use crate::plugin::{
+    plugin_fingerprint, HostCapabilities, Plugin, PluginError, PluginInput, PluginInputKind,
+    PluginOutput, PluginResult,
+};
+use crate::registry::PluginRegistry;
+use crate::spec::PluginSpec;
 
 use wasmtime::{Engine, Instance, Linker, Module, Store};
 use wasmtime_wasi::{WasiCtx, WasiCtxBuilder};
@@ -81,9 +92,23 @@ impl WasmSandbox {
     /// (func (export "execute"))
     /// ```
     ///
+    /// Only `PluginInput::Bytes` is accepted: the sandbox never receives a
+    /// reference to host memory such as the pipeline context.
+    ///
     /// Communication is done via host functions and shared memory
     /// (out of scope for this minimal implementation).
-    pub fn execute(&self, _input: &PluginInput) -> PluginResult<PluginOutput> {
+    pub fn execute(&self, input: PluginInput<'_>) -> PluginResult<PluginOutput> {
+        let _bytes = match input {
+            PluginInput::Bytes(b) => b,
+            other => {
+                return Err(PluginError::UnsupportedInput {
+                    plugin: "wasm-sandbox".to_string(),
+                    kind: other.kind(),
+                }
+                .into())
+            }
+        };
+
         let mut store = Store::new(&self.engine, ());
         store.add_fuel(self.config.fuel).map_err(|e| anyhow!(e))?;
 
@@ -106,3 +131,228 @@ impl WasmSandbox {
         Ok(PluginOutput::None)
     }
 }
+
+/// File name of the spec colocated with each external WASM plugin.
+pub const PLUGIN_SPEC_FILE: &str = "plugin.json";
+
+/// On-disk form of `plugin.json`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginSpecFile {
+    id: String,
+    name: String,
+    version: String,
+    #[serde(default)]
+    supports: Vec<String>,
+    #[serde(default)]
+    wants: std::collections::BTreeMap<String, bool>,
+}
+
+/// An external plugin backed by a `WasmSandbox`.
+pub struct WasmPlugin {
+    name: String,
+    version: String,
+    supports: Vec<String>,
+    module_sha256: String,
+    sandbox: WasmSandbox,
+}
+
+impl WasmPlugin {
+    /// Wrap `bytes` in a sandbox configured with `config`.
+    pub fn new(spec: &PluginSpec, bytes: &[u8], config: SandboxConfig) -> Result<Self> {
+        let module_sha256 = hash_bytes(HashAlg::Sha256, bytes)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        Ok(Self {
+            name: spec.name.clone(),
+            version: spec.version.clone(),
+            supports: spec.supports.clone(),
+            module_sha256,
+            sandbox: WasmSandbox::from_bytes(bytes, config)?,
+        })
+    }
+}
+
+impl Plugin for WasmPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    fn supports(&self, input_type: &str) -> bool {
+        self.supports.iter().any(|t| t == input_type)
+    }
+
+    fn accepts(&self, kind: PluginInputKind) -> bool {
+        kind == PluginInputKind::Bytes
+    }
+
+    fn execute(&self, input: PluginInput<'_>) -> PluginResult<PluginOutput> {
+        self.sandbox.execute(input)
+    }
+
+    /// Covers the module bytes, so rebuilding a plugin without bumping its
+    /// version still changes the fingerprint.
+    fn fingerprint(&self) -> String {
+        plugin_fingerprint(&self.name, &self.version, &[&self.module_sha256])
+    }
+}
+
+/// Load every external WASM plugin below `dir` into `registry`.
+///
+/// Each plugin lives in its own subdirectory holding exactly one `*.wasm`
+/// module and a `plugin.json` spec that supplies the registry id. Every module
+/// runs under `config`; spec `wants` are recorded but grant nothing.
+///
+/// A `*.wasm` file directly in `dir` has no spec and is an error rather than
+/// being skipped. Subdirectories are visited in name order. Returns the
+/// registered ids.
+pub fn load_plugins_dir(
+    registry: &mut PluginRegistry,
+    dir: &Path,
+    config: &SandboxConfig,
+) -> Result<Vec<String>> {
+    let mut plugin_dirs = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("read plugins dir {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            plugin_dirs.push(path);
+        } else if is_module(&path) {
+            return Err(anyhow!(
+                "{}: loose *.wasm module; put each plugin in its own subdirectory with a {PLUGIN_SPEC_FILE}",
+                path.display()
+            ));
+        }
+    }
+    plugin_dirs.sort();
+
+    let mut ids = Vec::new();
+    for plugin_dir in plugin_dirs {
+        let Some(wasm) = find_module(&plugin_dir)? else {
+            continue;
+        };
+        let spec = read_spec(&plugin_dir.join(PLUGIN_SPEC_FILE))?;
+        let bytes = fs::read(&wasm).with_context(|| format!("read {}", wasm.display()))?;
+        let plugin = WasmPlugin::new(&spec, &bytes, config.clone())
+            .with_context(|| format!("load {}", wasm.display()))?;
+
+        let id = spec.id.as_str().to_string();
+        registry
+            .register(spec, Box::new(plugin))
+            .with_context(|| format!("register {}", plugin_dir.display()))?;
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+/// The single `*.wasm` file in `dir`, if any.
+fn find_module(dir: &Path) -> Result<Option<PathBuf>> {
+    let mut modules = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("read {}", dir.display()))? {
+        let path = entry?.path();
+        if is_module(&path) {
+            modules.push(path);
+        }
+    }
+    match modules.len() {
+        0 => Ok(None),
+        1 => Ok(modules.pop()),
+        n => Err(anyhow!("{}: expected one *.wasm module, found {n}", dir.display())),
+    }
+}
+
+fn is_module(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|e| e == "wasm")
+}
+
+fn read_spec(path: &Path) -> Result<PluginSpec> {
+    let raw = fs::read(path).with_context(|| format!("read {}", path.display()))?;
+    let file: PluginSpecFile =
+        serde_json::from_slice(&raw).with_context(|| format!("parse {}", path.display()))?;
+
+    let mut spec = PluginSpec::new(file.id, file.name, file.version).meta("kind", "wasm");
+    for t in file.supports {
+        spec = spec.support(t);
+    }
+    for (k, v) in file.wants {
+        spec = spec.want(k, v);
+    }
+    spec.validate().with_context(|| format!("invalid {}", path.display()))?;
+    Ok(spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(module (func (export "execute")))`
+    const NOOP_MODULE: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+        0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type: () -> ()
+        0x03, 0x02, 0x01, 0x00, // func 0: type 0
+        0x07, 0x0b, 0x01, 0x07, b'e', b'x', b'e', b'c', b'u', b't', b'e', 0x00, 0x00, // export
+        0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b, // code: empty body
+    ];
+
+    fn write_plugin(root: &Path, dir: &str, spec: &str) {
+        let d = root.join(dir);
+        fs::create_dir_all(&d).unwrap();
+        fs::write(d.join("echo.wasm"), NOOP_MODULE).unwrap();
+        fs::write(d.join(PLUGIN_SPEC_FILE), spec).unwrap();
+    }
+
+    #[test]
+    fn loads_plugins_under_spec_ids() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_plugin(tmp.path(), "b", r#"{"id":"ext.b","name":"B","version":"0.1.0","supports":["b"]}"#);
+        write_plugin(tmp.path(), "a", r#"{"id":"ext.a","name":"A","version":"0.2.0"}"#);
+        fs::create_dir_all(tmp.path().join("empty")).unwrap();
+
+        let mut reg = PluginRegistry::new();
+        let ids = load_plugins_dir(&mut reg, tmp.path(), &SandboxConfig::default()).unwrap();
+        assert_eq!(ids, vec!["ext.a", "ext.b"]);
+
+        let b = reg.get("ext.b").unwrap();
+        assert_eq!(b.spec.meta.get("kind").map(String::as_str), Some("wasm"));
+        assert!(b.plugin.supports("b"));
+        assert!(b.plugin.accepts(PluginInputKind::Bytes));
+        assert!(!b.plugin.accepts(PluginInputKind::Pipeline));
+        assert_ne!(b.plugin.fingerprint(), plugin_fingerprint("B", "0.1.0", &[]));
+    }
+
+    #[test]
+    fn rejects_missing_or_unknown_spec_fields() {
+        let tmp = tempfile::tempdir().unwrap();
+        let d = tmp.path().join("x");
+        fs::create_dir_all(&d).unwrap();
+        fs::write(d.join("x.wasm"), NOOP_MODULE).unwrap();
+
+        let mut reg = PluginRegistry::new();
+        assert!(load_plugins_dir(&mut reg, tmp.path(), &SandboxConfig::default()).is_err());
+
+        fs::write(d.join(PLUGIN_SPEC_FILE), r#"{"id":"x","name":"X","version":"1","exec":"sh"}"#).unwrap();
+        assert!(load_plugins_dir(&mut reg, tmp.path(), &SandboxConfig::default()).is_err());
+        assert!(reg.is_empty());
+    }
+
+    #[test]
+    fn rejects_loose_modules() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_plugin(tmp.path(), "a", r#"{"id":"ext.a","name":"A","version":"0.2.0"}"#);
+        fs::write(tmp.path().join("stray.wasm"), NOOP_MODULE).unwrap();
+
+        let mut reg = PluginRegistry::new();
+        let err = load_plugins_dir(&mut reg, tmp.path(), &SandboxConfig::default()).unwrap_err();
+        assert!(err.to_string().contains("stray.wasm: loose *.wasm module"), "{err}");
+        assert!(reg.is_empty());
+    }
+
+    #[test]
+    fn default_config_grants_no_capabilities() {
+        assert_eq!(SandboxConfig::default().host_caps, HostCapabilities::default());
+    }
+}
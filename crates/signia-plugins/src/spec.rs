@@ -26,7 +26,7 @@ use crate::plugin::HostCapabilities;
 /// - ASCII
 /// - segments separated by dots
 /// - example: "builtin.repo"
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PluginId(pub String);
 
 impl PluginId {
@@ -43,7 +43,7 @@ impl PluginId {
 ///
 /// This is the static declaration of what a plugin can do.
 /// It is not a permission grant: the host decides what is allowed.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct PluginSpec {
     /// Stable plugin id.
     pub id: PluginId,
@@ -57,6 +57,9 @@ pub struct PluginSpec {
     /// Input types supported by this plugin (e.g., "repo", "openapi", "dataset").
     pub supports: Vec<String>,
 
+    /// Input type prefixes supported by this plugin (opt-in, see `supports_type_prefix`).
+    pub supports_prefixes: Vec<String>,
+
     /// Optional supported versions per input type (e.g., "repo:v1").
     pub supports_versions: BTreeMap<String, Vec<String>>,
 
@@ -82,6 +85,7 @@ impl PluginSpec {
             name: name.into(),
             version: version.into(),
             supports: Vec::new(),
+            supports_prefixes: Vec::new(),
             supports_versions: BTreeMap::new(),
             limits: BTreeMap::new(),
             wants: BTreeMap::new(),
@@ -94,6 +98,12 @@ impl PluginSpec {
         self
     }
 
+    /// Declare support for `prefix` and every type below it (`prefix.*`, `prefix:*`).
+    pub fn support_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.supports_prefixes.push(prefix.into());
+        self
+    }
+
     pub fn support_version(mut self, input_type: impl Into<String>, version: impl Into<String>) -> Self {
         let k = input_type.into();
         self.supports_versions.entry(k).or_default().push(version.into());
@@ -120,6 +130,19 @@ impl PluginSpec {
         self.supports.iter().any(|t| t == input_type)
     }
 
+    /// Returns true if a declared prefix covers `input_type`.
+    ///
+    /// A prefix matches itself and types that continue it after a `.` or `:`
+    /// separator: `openapi` matches `openapi`, `openapi.v3` and `openapi:3.1`,
+    /// but not `openapix`.
+    pub fn supports_type_prefix(&self, input_type: &str) -> bool {
+        self.supports_prefixes.iter().any(|p| {
+            input_type
+                .strip_prefix(p.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.') || rest.starts_with(':'))
+        })
+    }
+
     /// Validate spec for basic quality constraints.
     pub fn validate(&self) -> Result<()> {
         if self.id.as_str().trim().is_empty() {
@@ -149,6 +172,11 @@ pub struct SpecEvaluation {
 
     /// Capabilities required by plugin and missing from host.
     pub missing: Vec<String>,
+
+    /// Capabilities required by plugin and granted by host, sorted.
+    ///
+    /// Hosts record these into the manifest (`PluginRefV1.granted_caps`).
+    pub granted: Vec<String>,
 }
 
 impl SpecEvaluation {
@@ -157,6 +185,7 @@ impl SpecEvaluation {
             allowed: true,
             reason: None,
             missing: Vec::new(),
+            granted: Vec::new(),
         }
     }
 
@@ -165,6 +194,7 @@ impl SpecEvaluation {
             allowed: false,
             reason: Some(reason.into()),
             missing,
+            granted: Vec::new(),
         }
     }
 }
@@ -174,6 +204,7 @@ impl SpecEvaluation {
 /// This is a conservative check based on `wants`.
 pub fn evaluate_spec(spec: &PluginSpec, host: &HostCapabilities) -> SpecEvaluation {
     let mut missing = Vec::new();
+    let mut granted = Vec::new();
 
     for (k, wants) in &spec.wants {
         if !*wants {
@@ -186,16 +217,20 @@ pub fn evaluate_spec(spec: &PluginSpec, host: &HostCapabilities) -> SpecEvaluati
             "spawn" => host.spawn,
             _ => false,
         };
-        if !ok {
+        if ok {
+            granted.push(k.clone());
+        } else {
             missing.push(k.clone());
         }
     }
 
-    if missing.is_empty() {
+    let mut ev = if missing.is_empty() {
         SpecEvaluation::allowed()
     } else {
         SpecEvaluation::denied("host capabilities do not satisfy plugin wants", missing)
-    }
+    };
+    ev.granted = granted;
+    ev
 }
 
 #[cfg(test)]
@@ -208,6 +243,23 @@ mod tests {
         s.validate().unwrap();
     }
 
+    #[test]
+    fn prefix_support_is_opt_in() {
+        let exact = PluginSpec::new("x", "X", "0.1.0").support("openapi");
+        assert!(exact.supports_type("openapi"));
+        assert!(!exact.supports_type("openapi.v3"));
+        assert!(!exact.supports_type_prefix("openapi"));
+
+        let prefixed = PluginSpec::new("y", "Y", "0.1.0").support_prefix("openapi");
+        assert!(!prefixed.supports_type("openapi.v3"));
+        for t in ["openapi", "openapi.v3", "openapi:3.1"] {
+            assert!(prefixed.supports_type_prefix(t), "{t}");
+        }
+        for t in ["openapix", "open", "api.openapi"] {
+            assert!(!prefixed.supports_type_prefix(t), "{t}");
+        }
+    }
+
     #[test]
     fn evaluate_spec_denies_missing() {
         let s = PluginSpec::new("x", "X", "0.1.0").want("network", true);
@@ -220,5 +272,22 @@ mod tests {
         let ev = evaluate_spec(&s, &host);
         assert!(!ev.allowed);
         assert_eq!(ev.missing, vec!["network".to_string()]);
+        assert!(ev.granted.is_empty());
+    }
+
+    #[test]
+    fn evaluate_spec_records_granted() {
+        let s = PluginSpec::new("x", "X", "0.1.0")
+            .want("network", true)
+            .want("clock", true)
+            .want("filesystem", false);
+        let host = HostCapabilities {
+            network: true,
+            clock: true,
+            ..HostCapabilities::default()
+        };
+        let ev = evaluate_spec(&s, &host);
+        assert!(ev.allowed);
+        assert_eq!(ev.granted, vec!["clock".to_string(), "network".to_string()]);
     }
 }
@@ -67,12 +67,11 @@ fn plugin_execute_contract_allows_no_side_effects() {
     let mut registry = PluginRegistry::default();
 
     let ctx = PipelineContext::new(PipelineConfig::default());
-    let input = PluginInput::None;
 
     for spec in registry.list() {
         let plugin = registry.get(&spec.id).unwrap();
         let mut ctx_clone = ctx.clone();
-        let _ = plugin.execute(&input);
+        let _ = plugin.execute(PluginInput::None);
         assert_eq!(ctx_clone.inputs, ctx.inputs);
     }
 }
@@ -88,11 +87,8 @@ fn repeated_execution_produces_identical_results() {
         let mut ctx1 = ctx_base.clone();
         let mut ctx2 = ctx_base.clone();
 
-        let input = PluginInput::Pipeline(&mut ctx1);
-        plugin.execute(&input).ok();
-
-        let input = PluginInput::Pipeline(&mut ctx2);
-        plugin.execute(&input).ok();
+        plugin.execute(PluginInput::Pipeline(&mut ctx1)).ok();
+        plugin.execute(PluginInput::Pipeline(&mut ctx2)).ok();
 
         assert_eq!(
             serde_json::to_value(&ctx1).unwrap(),
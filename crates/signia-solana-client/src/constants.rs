@@ -25,5 +25,93 @@ pub fn default_program_id() -> Pubkey {
     DEFAULT_PROGRAM_ID.parse().unwrap_or_else(|_| Pubkey::default())
 }
 
+/// Returns true if `program_id` is the placeholder `DEFAULT_PROGRAM_ID`.
+///
+/// The all-zero pubkey (what `default_program_id` falls back to) also counts.
+pub fn is_placeholder_program_id(program_id: &Pubkey) -> bool {
+    *program_id == default_program_id() || *program_id == Pubkey::default()
+}
+
+/// Why a string is not an acceptable pubkey.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PubkeyError {
+    /// The string contains characters outside the base58 alphabet.
+    #[error("pubkey {0:?} is not base58")]
+    NotBase58(String),
+
+    /// The string decodes to something other than 32 bytes.
+    #[error("pubkey {input:?} decodes to {len} bytes (expected 32)")]
+    WrongLength { input: String, len: usize },
+
+    /// The key is off the ed25519 curve (e.g. a PDA) but a signer is required.
+    #[error("pubkey {0} is off-curve where an on-curve (wallet) key is required")]
+    OffCurve(String),
+}
+
+/// Parse a base58 pubkey, distinguishing bad encoding from bad length.
+pub fn parse_pubkey(s: &str) -> Result<Pubkey, PubkeyError> {
+    let bytes = bs58::decode(s)
+        .into_vec()
+        .map_err(|_| PubkeyError::NotBase58(s.to_string()))?;
+    let arr: [u8; 32] = bytes.try_into().map_err(|b: Vec<u8>| PubkeyError::WrongLength {
+        input: s.to_string(),
+        len: b.len(),
+    })?;
+    Ok(Pubkey::new_from_array(arr))
+}
+
+/// Like `parse_pubkey`, but also require an on-curve key (one that can sign).
+pub fn parse_on_curve_pubkey(s: &str) -> Result<Pubkey, PubkeyError> {
+    let pk = parse_pubkey(s)?;
+    if !pk.is_on_curve() {
+        return Err(PubkeyError::OffCurve(pk.to_string()));
+    }
+    Ok(pk)
+}
+
+/// Size in bytes of a v1 schema record account, including the 8-byte
+/// discriminator (see `docs/onchain/accounts.md`).
+pub const RECORD_ACCOUNT_LEN: usize = 224;
+
 /// Version string embedded into client metadata and instruction tags.
 pub const CLIENT_VERSION: &str = "v1";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn parse_pubkey_accepts_valid_keys() {
+        let wallet = Keypair::new().pubkey();
+        assert_eq!(parse_pubkey(&wallet.to_string()), Ok(wallet));
+        assert_eq!(parse_on_curve_pubkey(&wallet.to_string()), Ok(wallet));
+        assert_eq!(parse_pubkey(DEFAULT_PROGRAM_ID), Ok(default_program_id()));
+    }
+
+    #[test]
+    fn parse_pubkey_rejects_non_base58() {
+        // '0', 'O', 'I' and 'l' are not in the base58 alphabet.
+        let err = parse_pubkey("0OIl").unwrap_err();
+        assert!(matches!(err, PubkeyError::NotBase58(_)), "{err}");
+        assert!(err.to_string().contains("not base58"));
+    }
+
+    #[test]
+    fn parse_pubkey_rejects_wrong_length() {
+        let err = parse_pubkey("abc").unwrap_err();
+        assert!(matches!(err, PubkeyError::WrongLength { len: 3, .. }), "{err}");
+
+        let long = bs58::encode([7u8; 33]).into_string();
+        let err = parse_pubkey(&long).unwrap_err();
+        assert!(matches!(err, PubkeyError::WrongLength { len: 33, .. }), "{err}");
+    }
+
+    #[test]
+    fn parse_on_curve_pubkey_rejects_pdas() {
+        let (pda, _) = Pubkey::find_program_address(&[SEED_REGISTRY], &default_program_id());
+        assert_eq!(parse_pubkey(&pda.to_string()), Ok(pda));
+        let err = parse_on_curve_pubkey(&pda.to_string()).unwrap_err();
+        assert_eq!(err, PubkeyError::OffCurve(pda.to_string()));
+    }
+}
@@ -3,10 +3,18 @@
 //! These helpers implement deterministic address derivation and are designed to
 //! match the on-chain program's seeds and layout.
 
-use solana_program::pubkey::Pubkey;
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use solana_program::pubkey::{Pubkey, MAX_SEED_LEN};
 
 use crate::constants::{SEED_AUTH, SEED_NAMESPACE, SEED_RECORD, SEED_REGISTRY};
 
+/// Domain prefix for `hash_long_namespace`.
+const LONG_NAMESPACE_DOMAIN: &[u8] = b"signia:namespace:v1:";
+
+/// Domain prefix for `object_id_seed` of ids that are not 32-byte digests.
+const OBJECT_ID_DOMAIN: &[u8] = b"signia:object-id:v1:";
+
 #[derive(Debug, Clone)]
 pub struct RegistryPdas {
     pub registry: (Pubkey, u8),
@@ -18,6 +26,19 @@ pub struct NamespacePdas {
     pub auth: (Pubkey, u8),
 }
 
+/// How the object id becomes record PDA seeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordSeedLayout {
+    /// One 32-byte seed: `object_id_seed(object_id)`.
+    Hashed,
+    /// Two 32-byte seeds: the halves of the 64-char lowercase hex id.
+    Split,
+}
+
+/// The layout `derive_record` uses. The on-chain program must use the same one
+/// (see `docs/onchain/pda-layout.md`).
+pub const RECORD_SEED_LAYOUT: RecordSeedLayout = RecordSeedLayout::Hashed;
+
 #[derive(Debug, Clone)]
 pub struct RecordPdas {
     pub record: (Pubkey, u8),
@@ -32,36 +53,117 @@ pub fn derive_registry(program_id: &Pubkey) -> (Pubkey, u8) {
 ///
 /// Namespace names are normalized in a deterministic way by the client.
 /// The canonical form is lowercase ASCII with '-' separators.
-pub fn derive_namespace(program_id: &Pubkey, namespace: &str) -> (Pubkey, u8) {
-    let ns = normalize_namespace(namespace);
-    Pubkey::find_program_address(&[SEED_NAMESPACE, ns.as_bytes()], program_id)
+/// Fails if the name is not a valid seed (see `validate_namespace`).
+pub fn derive_namespace(program_id: &Pubkey, namespace: &str) -> Result<(Pubkey, u8)> {
+    let ns = validate_namespace(namespace)?;
+    Ok(Pubkey::find_program_address(&[SEED_NAMESPACE, ns.as_bytes()], program_id))
 }
 
 /// Derive the namespace authority PDA.
-pub fn derive_namespace_auth(program_id: &Pubkey, namespace: &str) -> (Pubkey, u8) {
-    let ns = normalize_namespace(namespace);
-    Pubkey::find_program_address(&[SEED_AUTH, ns.as_bytes()], program_id)
+pub fn derive_namespace_auth(program_id: &Pubkey, namespace: &str) -> Result<(Pubkey, u8)> {
+    let ns = validate_namespace(namespace)?;
+    Ok(Pubkey::find_program_address(&[SEED_AUTH, ns.as_bytes()], program_id))
 }
 
 /// Derive a record PDA by namespace + object id.
 ///
 /// Object id should be a stable content-addressed id (e.g. sha256 hex).
-pub fn derive_record(program_id: &Pubkey, namespace: &str, object_id: &str) -> (Pubkey, u8) {
-    let ns = normalize_namespace(namespace);
+/// Seeds follow `RECORD_SEED_LAYOUT`; the id itself goes in instruction data.
+pub fn derive_record(program_id: &Pubkey, namespace: &str, object_id: &str) -> Result<(Pubkey, u8)> {
+    match RECORD_SEED_LAYOUT {
+        RecordSeedLayout::Hashed => derive_record_hashed(program_id, namespace, object_id),
+        RecordSeedLayout::Split => derive_record_split(program_id, namespace, object_id),
+    }
+}
+
+/// Record PDA with seeds `[SEED_RECORD, namespace, object_id_seed(object_id)]`.
+pub fn derive_record_hashed(program_id: &Pubkey, namespace: &str, object_id: &str) -> Result<(Pubkey, u8)> {
+    let ns = validate_namespace(namespace)?;
+    let oid = object_id_seed(object_id);
+    Ok(Pubkey::find_program_address(&[SEED_RECORD, ns.as_bytes(), &oid], program_id))
+}
+
+/// Record PDA with seeds `[SEED_RECORD, namespace, hex[..32], hex[32..]]`.
+///
+/// Only 64-char hex ids (after normalization) can be split; anything else is
+/// an error rather than a silently different seed.
+pub fn derive_record_split(program_id: &Pubkey, namespace: &str, object_id: &str) -> Result<(Pubkey, u8)> {
+    let ns = validate_namespace(namespace)?;
+    let oid = normalize_object_id(object_id);
+    if oid.len() != 64 || !oid.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(anyhow!("split record seeds need a 64-char hex object id, got {object_id:?}"));
+    }
+    let (hi, lo) = oid.as_bytes().split_at(MAX_SEED_LEN);
+    Ok(Pubkey::find_program_address(&[SEED_RECORD, ns.as_bytes(), hi, lo], program_id))
+}
+
+/// 32-byte PDA seed for an object id.
+///
+/// A 32-byte digest (64-char hex in any case, or case-sensitive base58, either
+/// optionally prefixed with a store algorithm such as `sha256:`) seeds with its raw bytes,
+/// as the on-chain program does for schema hashes. Any other id is hashed:
+/// `sha256("signia:object-id:v1:" || normalized)`. Either way the seed fits the
+/// 32-byte limit, where the 64-byte hex text would not.
+pub fn object_id_seed(object_id: &str) -> [u8; 32] {
     let oid = normalize_object_id(object_id);
-    Pubkey::find_program_address(&[SEED_RECORD, ns.as_bytes(), oid.as_bytes()], program_id)
+    let mut seed = [0u8; 32];
+    if oid.len() == 64 && hex::decode_to_slice(&oid, &mut seed).is_ok() {
+        return seed;
+    }
+    let mut h = Sha256::new();
+    h.update(OBJECT_ID_DOMAIN);
+    h.update(oid.as_bytes());
+    h.finalize().into()
 }
 
 /// Collect PDAs used by most flows.
-pub fn pdas_for_namespace(program_id: &Pubkey, namespace: &str) -> NamespacePdas {
-    NamespacePdas {
-        namespace: derive_namespace(program_id, namespace),
-        auth: derive_namespace_auth(program_id, namespace),
+pub fn pdas_for_namespace(program_id: &Pubkey, namespace: &str) -> Result<NamespacePdas> {
+    Ok(NamespacePdas {
+        namespace: derive_namespace(program_id, namespace)?,
+        auth: derive_namespace_auth(program_id, namespace)?,
+    })
+}
+
+pub fn pdas_for_record(program_id: &Pubkey, namespace: &str, object_id: &str) -> Result<RecordPdas> {
+    Ok(RecordPdas { record: derive_record(program_id, namespace, object_id)? })
+}
+
+/// Normalize `namespace` and check it can be used as a PDA seed.
+///
+/// Accepted input characters are ASCII letters, digits and the separators
+/// `-`, `_`, `.` and space. The normalized name must be non-empty and at most
+/// `MAX_SEED_LEN` (32) bytes, matching the on-chain program's limit.
+pub fn validate_namespace(namespace: &str) -> Result<String> {
+    if let Some(c) = namespace
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ' ')))
+    {
+        return Err(anyhow!("namespace {namespace:?} contains unsupported character {c:?}"));
+    }
+    let ns = normalize_namespace(namespace);
+    if ns.is_empty() {
+        return Err(anyhow!("namespace {namespace:?} is empty after normalization"));
     }
+    if ns.len() > MAX_SEED_LEN {
+        return Err(anyhow!(
+            "namespace {ns:?} is {} bytes (max {MAX_SEED_LEN} for a PDA seed); shorten it, or derive with \
+             Pubkey::find_program_address using hash_long_namespace({namespace:?}) as the seed",
+            ns.len()
+        ));
+    }
+    Ok(ns)
 }
 
-pub fn pdas_for_record(program_id: &Pubkey, namespace: &str, object_id: &str) -> RecordPdas {
-    RecordPdas { record: derive_record(program_id, namespace, object_id) }
+/// Deterministic 32-byte seed for a namespace that is too long to use directly.
+///
+/// Computed as `sha256("signia:namespace:v1:" || normalized)`, so names that
+/// normalize the same hash the same. This seed is not interchangeable with the
+/// plain name: programs and clients must agree to use the hashed form.
+pub fn hash_long_namespace(namespace: &str) -> [u8; 32] {
+    let mut h = Sha256::new();
+    h.update(LONG_NAMESPACE_DOMAIN);
+    h.update(normalize_namespace(namespace).as_bytes());
+    h.finalize().into()
 }
 
 fn normalize_namespace(input: &str) -> String {
@@ -79,9 +181,17 @@ fn normalize_namespace(input: &str) -> String {
     out.trim_matches('-').to_string()
 }
 
+/// Store algorithm prefixes (`<alg>:<digest>`) stripped before decoding.
+const OBJECT_ID_ALG_PREFIXES: [&str; 2] = ["sha256:", "blake3:"];
+
 fn normalize_object_id(input: &str) -> String {
-    // Accept sha256 hex or base58; normalize to lowercase hex if possible.
+    // Accept sha256 hex or base58, optionally `<alg>:`-prefixed as the store
+    // writes them; normalize to lowercase hex if possible.
     let s = input.trim();
+    let s = OBJECT_ID_ALG_PREFIXES
+        .iter()
+        .find_map(|p| s.strip_prefix(p))
+        .unwrap_or(s);
     if s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()) {
         return s.to_ascii_lowercase();
     }
@@ -104,9 +214,109 @@ mod tests {
         assert_eq!(normalize_namespace("  my-space  "), "my-space");
     }
 
+    #[test]
+    fn namespace_seed_length_boundary() {
+        let at_limit = "a".repeat(MAX_SEED_LEN);
+        assert_eq!(validate_namespace(&at_limit).unwrap(), at_limit);
+        // Separators collapse before the length check.
+        assert_eq!(validate_namespace(&format!("  {at_limit}..")).unwrap(), at_limit);
+
+        let over = "a".repeat(MAX_SEED_LEN + 1);
+        let err = validate_namespace(&over).unwrap_err().to_string();
+        assert!(err.contains("33 bytes"), "{err}");
+        assert!(err.contains("Pubkey::find_program_address using hash_long_namespace("), "{err}");
+
+        let program_id = Pubkey::new_unique();
+        assert!(derive_namespace(&program_id, &at_limit).is_ok());
+        assert!(derive_namespace(&program_id, &over).is_err());
+        assert!(pdas_for_namespace(&program_id, &over).is_err());
+    }
+
+    #[test]
+    fn namespace_charset_and_empty_are_rejected() {
+        assert!(validate_namespace("team/space").unwrap_err().to_string().contains("'/'"));
+        assert!(validate_namespace("caf\u{e9}").is_err());
+        assert!(validate_namespace(" -_. ").unwrap_err().to_string().contains("empty"));
+    }
+
+    #[test]
+    fn long_namespace_hash_is_deterministic() {
+        let long = "x".repeat(100);
+        assert_eq!(hash_long_namespace(&long), hash_long_namespace(&long));
+        assert_eq!(hash_long_namespace("My Space"), hash_long_namespace("my-space"));
+        assert_ne!(hash_long_namespace(&long), hash_long_namespace(&"x".repeat(101)));
+
+        // The hash is itself a valid seed.
+        let seed = hash_long_namespace(&long);
+        let _ = Pubkey::find_program_address(&[SEED_NAMESPACE, &seed], &Pubkey::new_unique());
+    }
+
+    #[test]
+    fn record_seed_fits_for_hex_object_ids() {
+        let id = "ab".repeat(32);
+        let seed = object_id_seed(&id);
+        assert_eq!(seed, [0xab; 32]);
+        assert_eq!(object_id_seed(&id.to_ascii_uppercase()), seed);
+        assert_eq!(object_id_seed(&bs58::encode([0xab; 32]).into_string()), seed);
+
+        // 64-char hex used to be a 64-byte seed, which cannot derive a PDA.
+        let program_id = Pubkey::new_unique();
+        let (pda, _) = derive_record(&program_id, "demo", &id).unwrap();
+        let (expected, _) = Pubkey::find_program_address(&[SEED_RECORD, b"demo", &[0xab; 32]], &program_id);
+        assert_eq!(pda, expected);
+    }
+
+    #[test]
+    fn record_seed_hashes_other_object_ids() {
+        let long = "object-".repeat(20);
+        let seed = object_id_seed(&long);
+        assert_eq!(seed, object_id_seed(&long));
+        assert_ne!(seed, object_id_seed("object-"));
+        // "0" is neither hex-64 nor base58, so it is hashed as text.
+        assert!(derive_record(&Pubkey::new_unique(), "demo", &long).is_ok());
+        assert!(derive_record(&Pubkey::new_unique(), "demo", "0").is_ok());
+    }
+
+    #[test]
+    fn split_record_derivation_is_stable_and_distinct() {
+        let program_id = Pubkey::new_unique();
+        let id = "0123456789abcdef".repeat(4);
+
+        let split = derive_record_split(&program_id, "demo", &id).unwrap();
+        assert_eq!(split, derive_record_split(&program_id, "demo", &id.to_ascii_uppercase()).unwrap());
+        let expected = Pubkey::find_program_address(
+            &[SEED_RECORD, b"demo", &id.as_bytes()[..32], &id.as_bytes()[32..]],
+            &program_id,
+        );
+        assert_eq!(split, expected);
+
+        assert_ne!(split, derive_record_hashed(&program_id, "demo", &id).unwrap());
+        assert!(derive_record_split(&program_id, "demo", "not-a-digest").is_err());
+
+        let selected = match RECORD_SEED_LAYOUT {
+            RecordSeedLayout::Hashed => derive_record_hashed(&program_id, "demo", &id).unwrap(),
+            RecordSeedLayout::Split => split,
+        };
+        assert_eq!(derive_record(&program_id, "demo", &id).unwrap(), selected);
+    }
+
     #[test]
     fn object_id_normalization_hex() {
         let h = "A".repeat(64);
         assert_eq!(normalize_object_id(&h), "a".repeat(64));
     }
+
+    #[test]
+    fn object_id_alg_prefix_is_stripped() {
+        let hex = "ab".repeat(32);
+        assert_eq!(normalize_object_id(&format!("sha256:{hex}")), hex);
+        assert_eq!(object_id_seed(&format!("sha256:{hex}")), [0xab; 32]);
+        assert_eq!(object_id_seed(&format!("blake3:{}", hex.to_ascii_uppercase())), [0xab; 32]);
+
+        let b58 = bs58::encode([0xab; 32]).into_string();
+        assert_eq!(object_id_seed(&format!("sha256:{b58}")), [0xab; 32]);
+
+        // Unknown prefixes are not stripped; the id is hashed as text.
+        assert_ne!(object_id_seed(&format!("md5:{hex}")), [0xab; 32]);
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! This client can:
 //! - derive PDAs
-//! - build instructions (create namespace, publish record)
+//! - build instructions (create namespace, publish/update/close record)
 //! - optionally submit transactions via RPC
 //!
 //! The actual on-chain program is expected to be implemented in `signia-program`.
@@ -17,7 +17,7 @@ use solana_sdk::signature::{Keypair, Signer};
 use solana_sdk::transaction::Transaction;
 
 use crate::pda;
-use crate::constants::CLIENT_VERSION;
+use crate::constants::{is_placeholder_program_id, parse_on_curve_pubkey, CLIENT_VERSION};
 
 #[derive(Debug, Clone)]
 pub struct RegistryClient {
@@ -37,6 +37,24 @@ pub struct PublishRecordArgs {
     pub kind: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateRecordArgs {
+    pub namespace: String,
+    pub object_id: String,
+    /// New off-chain pointer; replaces the stored one (`None` clears it).
+    #[serde(default)]
+    pub uri: Option<String>,
+    /// New type hint; replaces the stored one (`None` clears it).
+    #[serde(default)]
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloseRecordArgs {
+    pub namespace: String,
+    pub object_id: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateNamespaceArgs {
     pub namespace: String,
@@ -49,23 +67,38 @@ impl RegistryClient {
         Self { program_id, rpc: None }
     }
 
+    /// Like `new`, but also returns `true` if `program_id` is the placeholder
+    /// `DEFAULT_PROGRAM_ID`, so callers can warn before targeting a real cluster.
+    pub fn new_checked(program_id: Pubkey) -> (Self, bool) {
+        (Self::new(program_id), is_placeholder_program_id(&program_id))
+    }
+
     pub fn with_rpc(program_id: Pubkey, rpc_url: &str) -> Self {
         Self { program_id, rpc: Some(RpcClient::new(rpc_url.to_string())) }
     }
 
-    pub fn derive_namespace(&self, namespace: &str) -> (Pubkey, u8) {
+    /// Lamports needed to make an account of `account_data_len` bytes rent-exempt.
+    ///
+    /// Requires the client to be constructed with RPC.
+    pub fn estimate_rent(&self, account_data_len: usize) -> Result<u64> {
+        let rpc = self.rpc.as_ref().ok_or_else(|| anyhow!("rpc client not configured"))?;
+        rpc.get_minimum_balance_for_rent_exemption(account_data_len)
+            .map_err(|e| anyhow!("rent estimate for {account_data_len} bytes: {e}"))
+    }
+
+    pub fn derive_namespace(&self, namespace: &str) -> Result<(Pubkey, u8)> {
         pda::derive_namespace(&self.program_id, namespace)
     }
 
-    pub fn derive_record(&self, namespace: &str, object_id: &str) -> (Pubkey, u8) {
+    pub fn derive_record(&self, namespace: &str, object_id: &str) -> Result<(Pubkey, u8)> {
         pda::derive_record(&self.program_id, namespace, object_id)
     }
 
     /// Build instruction to create a namespace account.
     pub fn ix_create_namespace(&self, payer: Pubkey, args: CreateNamespaceArgs) -> Result<Instruction> {
-        let authority: Pubkey = args.authority.parse().map_err(|_| anyhow!("invalid authority pubkey"))?;
-        let (ns_pda, ns_bump) = self.derive_namespace(&args.namespace);
-        let (auth_pda, auth_bump) = pda::derive_namespace_auth(&self.program_id, &args.namespace);
+        let authority = parse_on_curve_pubkey(&args.authority).map_err(|e| anyhow!("invalid authority: {e}"))?;
+        let (ns_pda, ns_bump) = self.derive_namespace(&args.namespace)?;
+        let (auth_pda, auth_bump) = pda::derive_namespace_auth(&self.program_id, &args.namespace)?;
 
         let data = RegistryIx::CreateNamespace {
             version: CLIENT_VERSION.to_string(),
@@ -90,9 +123,9 @@ impl RegistryClient {
 
     /// Build instruction to publish a record within a namespace.
     pub fn ix_publish_record(&self, payer: Pubkey, authority: Pubkey, args: PublishRecordArgs) -> Result<Instruction> {
-        let (ns_pda, _ns_bump) = self.derive_namespace(&args.namespace);
-        let (auth_pda, auth_bump) = pda::derive_namespace_auth(&self.program_id, &args.namespace);
-        let (record_pda, record_bump) = self.derive_record(&args.namespace, &args.object_id);
+        let (ns_pda, _ns_bump) = self.derive_namespace(&args.namespace)?;
+        let (auth_pda, auth_bump) = pda::derive_namespace_auth(&self.program_id, &args.namespace)?;
+        let (record_pda, record_bump) = self.derive_record(&args.namespace, &args.object_id)?;
 
         let data = RegistryIx::PublishRecord {
             version: CLIENT_VERSION.to_string(),
@@ -119,6 +152,72 @@ impl RegistryClient {
         })
     }
 
+    /// Build instruction to change the `uri`/`kind` of an existing record.
+    ///
+    /// `authority` must be the namespace authority and signs the transaction.
+    pub fn ix_update_record(&self, authority: Pubkey, args: UpdateRecordArgs) -> Result<Instruction> {
+        let (ns_pda, _ns_bump) = self.derive_namespace(&args.namespace)?;
+        let (auth_pda, auth_bump) = pda::derive_namespace_auth(&self.program_id, &args.namespace)?;
+        let (record_pda, record_bump) = self.derive_record(&args.namespace, &args.object_id)?;
+
+        let data = RegistryIx::UpdateRecord {
+            version: CLIENT_VERSION.to_string(),
+            namespace: args.namespace,
+            object_id: args.object_id,
+            uri: args.uri,
+            kind: args.kind,
+            auth_bump,
+            record_bump,
+        }
+        .to_vec()?;
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new_readonly(ns_pda, false),
+                AccountMeta::new_readonly(auth_pda, false),
+                AccountMeta::new(record_pda, false),
+            ],
+            data,
+        })
+    }
+
+    /// Build instruction to close a record and send its rent lamports to `destination`.
+    ///
+    /// `authority` must be the namespace authority and signs the transaction.
+    pub fn ix_close_record(
+        &self,
+        authority: Pubkey,
+        destination: Pubkey,
+        args: CloseRecordArgs,
+    ) -> Result<Instruction> {
+        let (ns_pda, _ns_bump) = self.derive_namespace(&args.namespace)?;
+        let (auth_pda, auth_bump) = pda::derive_namespace_auth(&self.program_id, &args.namespace)?;
+        let (record_pda, record_bump) = self.derive_record(&args.namespace, &args.object_id)?;
+
+        let data = RegistryIx::CloseRecord {
+            version: CLIENT_VERSION.to_string(),
+            namespace: args.namespace,
+            object_id: args.object_id,
+            auth_bump,
+            record_bump,
+        }
+        .to_vec()?;
+
+        Ok(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new_readonly(ns_pda, false),
+                AccountMeta::new_readonly(auth_pda, false),
+                AccountMeta::new(record_pda, false),
+                AccountMeta::new(destination, false),
+            ],
+            data,
+        })
+    }
+
     /// Submit a transaction. Requires the client to be constructed with RPC.
     pub fn send_transaction(&self, payer: &Keypair, ixs: &[Instruction]) -> Result<String> {
         let rpc = self.rpc.as_ref().ok_or_else(|| anyhow!("rpc client not configured"))?;
@@ -133,7 +232,10 @@ impl RegistryClient {
 ///
 /// This encoding is designed to be stable and easy to decode on-chain.
 /// It uses a small tag byte followed by bincode-encoded payload.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Variants are append-only: new instructions go at the end with the next tag,
+/// so existing tags and payload encodings never change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 enum RegistryIx {
     CreateNamespace {
         version: String,
@@ -151,16 +253,37 @@ enum RegistryIx {
         auth_bump: u8,
         record_bump: u8,
     },
+    UpdateRecord {
+        version: String,
+        namespace: String,
+        object_id: String,
+        uri: Option<String>,
+        kind: Option<String>,
+        auth_bump: u8,
+        record_bump: u8,
+    },
+    CloseRecord {
+        version: String,
+        namespace: String,
+        object_id: String,
+        auth_bump: u8,
+        record_bump: u8,
+    },
 }
 
 impl RegistryIx {
+    /// Tag byte identifying the instruction.
+    fn tag(&self) -> u8 {
+        match self {
+            RegistryIx::CreateNamespace { .. } => 1,
+            RegistryIx::PublishRecord { .. } => 2,
+            RegistryIx::UpdateRecord { .. } => 3,
+            RegistryIx::CloseRecord { .. } => 4,
+        }
+    }
+
     fn to_vec(&self) -> Result<Vec<u8>> {
-        // Tag: 1 byte
-        let tag = match self {
-            RegistryIx::CreateNamespace { .. } => 1u8,
-            RegistryIx::PublishRecord { .. } => 2u8,
-        };
-        let mut out = vec![tag];
+        let mut out = vec![self.tag()];
         let payload = bincode::serialize(self).map_err(|e| anyhow!("serialize: {e}"))?;
         out.extend_from_slice(&payload);
         Ok(out)
@@ -171,8 +294,148 @@ impl RegistryIx {
         if data.is_empty() {
             return Err(anyhow!("empty instruction data"));
         }
-        let _tag = data[0];
         let ix: RegistryIx = bincode::deserialize(&data[1..]).map_err(|e| anyhow!("deserialize: {e}"))?;
+        if ix.tag() != data[0] {
+            return Err(anyhow!("instruction tag {} does not match payload (tag {})", data[0], ix.tag()));
+        }
         Ok(ix)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{default_program_id, RECORD_ACCOUNT_LEN};
+    use solana_client::rpc_request::RpcRequest;
+
+    #[test]
+    fn new_checked_flags_placeholder_program_id() {
+        let (client, placeholder) = RegistryClient::new_checked(default_program_id());
+        assert!(placeholder);
+        assert_eq!(client.program_id, default_program_id());
+
+        let (_, placeholder) = RegistryClient::new_checked(Pubkey::default());
+        assert!(placeholder);
+
+        let token_program: Pubkey = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".parse().unwrap();
+        let (_, placeholder) = RegistryClient::new_checked(token_program);
+        assert!(!placeholder);
+        assert!(!RegistryClient::new_checked(Pubkey::new_unique()).1);
+    }
+
+    fn sample_ns_and_id() -> (String, String) {
+        ("signia".to_string(), "ab".repeat(32))
+    }
+
+    #[test]
+    fn update_record_roundtrips() {
+        let client = RegistryClient::new(Pubkey::new_unique());
+        let authority = Pubkey::new_unique();
+        let (namespace, object_id) = sample_ns_and_id();
+        let ix = client
+            .ix_update_record(
+                authority,
+                UpdateRecordArgs {
+                    namespace: namespace.clone(),
+                    object_id: object_id.clone(),
+                    uri: Some("ipfs://bundle".to_string()),
+                    kind: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(ix.data[0], 3);
+        let (_, auth_bump) = pda::derive_namespace_auth(&client.program_id, &namespace).unwrap();
+        let (record_pda, record_bump) = client.derive_record(&namespace, &object_id).unwrap();
+        let expected = RegistryIx::UpdateRecord {
+            version: CLIENT_VERSION.to_string(),
+            namespace: namespace.clone(),
+            object_id,
+            uri: Some("ipfs://bundle".to_string()),
+            kind: None,
+            auth_bump,
+            record_bump,
+        };
+        assert_eq!(RegistryIx::from_slice(&ix.data).unwrap(), expected);
+        assert_eq!(expected.to_vec().unwrap(), ix.data);
+
+        // authority, ns, ns auth, record: the same relative order as publish.
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|m| m.pubkey).collect();
+        assert_eq!(keys[0], authority);
+        assert_eq!(keys[1], client.derive_namespace(&namespace).unwrap().0);
+        assert_eq!(keys[3], record_pda);
+        assert!(ix.accounts[0].is_signer);
+        assert!(ix.accounts[3].is_writable);
+    }
+
+    #[test]
+    fn close_record_roundtrips() {
+        let client = RegistryClient::new(Pubkey::new_unique());
+        let authority = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let (namespace, object_id) = sample_ns_and_id();
+        let ix = client
+            .ix_close_record(
+                authority,
+                destination,
+                CloseRecordArgs { namespace: namespace.clone(), object_id: object_id.clone() },
+            )
+            .unwrap();
+
+        assert_eq!(ix.data[0], 4);
+        let (_, auth_bump) = pda::derive_namespace_auth(&client.program_id, &namespace).unwrap();
+        let (record_pda, record_bump) = client.derive_record(&namespace, &object_id).unwrap();
+        let expected = RegistryIx::CloseRecord {
+            version: CLIENT_VERSION.to_string(),
+            namespace,
+            object_id,
+            auth_bump,
+            record_bump,
+        };
+        assert_eq!(RegistryIx::from_slice(&ix.data).unwrap(), expected);
+        assert_eq!(expected.to_vec().unwrap(), ix.data);
+
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|m| m.pubkey).collect();
+        assert_eq!(keys[0], authority);
+        assert_eq!(keys[3], record_pda);
+        assert_eq!(keys[4], destination);
+        assert!(ix.accounts[0].is_signer);
+        assert!(ix.accounts[3].is_writable && ix.accounts[4].is_writable);
+    }
+
+    #[test]
+    fn existing_tags_are_stable_and_checked() {
+        let publish = RegistryIx::PublishRecord {
+            version: CLIENT_VERSION.to_string(),
+            namespace: "signia".to_string(),
+            object_id: "ab".repeat(32),
+            uri: None,
+            kind: Some("schema".to_string()),
+            auth_bump: 254,
+            record_bump: 253,
+        };
+        let mut data = publish.to_vec().unwrap();
+        assert_eq!(data[0], 2);
+        assert_eq!(RegistryIx::from_slice(&data).unwrap(), publish);
+
+        // A tag that disagrees with the payload is rejected.
+        data[0] = 3;
+        assert!(RegistryIx::from_slice(&data).is_err());
+        assert!(RegistryIx::from_slice(&[]).is_err());
+    }
+
+    #[test]
+    fn estimate_rent_uses_rpc() {
+        let mut mocks = std::collections::HashMap::new();
+        mocks.insert(RpcRequest::GetMinimumBalanceForRentExemption, serde_json::json!(2_449_920));
+        let client = RegistryClient {
+            program_id: Pubkey::new_unique(),
+            rpc: Some(RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks)),
+        };
+        assert_eq!(client.estimate_rent(RECORD_ACCOUNT_LEN).unwrap(), 2_449_920);
+
+        let offline = RegistryClient::new(Pubkey::new_unique());
+        let err = offline.estimate_rent(RECORD_ACCOUNT_LEN).unwrap_err().to_string();
+        assert!(err.contains("rpc client not configured"), "{err}");
+    }
+}
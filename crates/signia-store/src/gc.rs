@@ -0,0 +1,117 @@
+//! Mark-and-sweep garbage collection for the object store.
+//!
+//! Reachability is derived from object contents: an object that parses as JSON
+//! references every string value in it that is a valid object id present in the
+//! store (e.g. `manifestObjectId`, `schemaObjectId`). Everything not reachable
+//! from the given roots is a deletion candidate.
+//!
+//! Traversal and candidate lists are sorted, so the same store and roots always
+//! produce the same report.
+
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::objects::{format_object_id, parse_object_id, HashAlg};
+use crate::Store;
+
+/// Result of a GC run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GcReport {
+    /// True if nothing was deleted.
+    pub dry_run: bool,
+    /// Number of objects reachable from the roots (including the roots).
+    pub reachable: usize,
+    /// Unreachable object ids, sorted. Deleted unless `dry_run`.
+    pub candidates: Vec<String>,
+    /// Total size in bytes of the candidates.
+    pub freed_bytes: u64,
+}
+
+impl GcReport {
+    /// Number of candidates (deleted objects when not a dry run).
+    pub fn deleted(&self) -> usize {
+        if self.dry_run {
+            0
+        } else {
+            self.candidates.len()
+        }
+    }
+}
+
+pub(crate) fn run(store: &Store, roots: &[String], dry_run: bool) -> Result<GcReport> {
+    let objects = store.objects();
+
+    // Mark
+    let mut reachable: BTreeSet<String> = BTreeSet::new();
+    let mut queue: Vec<String> = Vec::new();
+    for r in roots {
+        let (alg, digest) = parse_object_id(r)?;
+        if !objects.exists(alg.as_str(), digest)? {
+            return Err(anyhow!("gc root not found: {r}"));
+        }
+        let id = format_object_id(alg, digest);
+        if reachable.insert(id.clone()) {
+            queue.push(id);
+        }
+    }
+
+    while let Some(id) = queue.pop() {
+        let (alg, digest) = parse_object_id(&id)?;
+        let Some(bytes) = objects.get_bytes(alg.as_str(), digest)? else { continue };
+        let Ok(v) = serde_json::from_slice::<serde_json::Value>(&bytes) else { continue };
+
+        let mut refs = BTreeSet::new();
+        collect_refs(&v, &mut refs);
+        for (alg, digest) in refs {
+            let r = format_object_id(alg, &digest);
+            if !reachable.contains(&r) && objects.exists(alg.as_str(), &digest)? {
+                reachable.insert(r.clone());
+                queue.push(r);
+            }
+        }
+    }
+
+    // Sweep
+    let mut report = GcReport {
+        dry_run,
+        reachable: reachable.len(),
+        ..GcReport::default()
+    };
+    for alg in HashAlg::ALL {
+        let a = alg.as_str();
+        for digest in objects.list_ids(a)? {
+            let id = format_object_id(alg, &digest);
+            if reachable.contains(&id) {
+                continue;
+            }
+            let size = match objects.get_meta(a, &digest)? {
+                Some(m) => m.size,
+                None => objects.get_bytes(a, &digest)?.map(|b| b.len() as u64).unwrap_or(0),
+            };
+            if !dry_run {
+                objects.delete(a, &digest)?;
+            }
+            report.freed_bytes = report.freed_bytes.saturating_add(size);
+            report.candidates.push(id);
+        }
+    }
+    report.candidates.sort();
+
+    Ok(report)
+}
+
+fn collect_refs(v: &serde_json::Value, out: &mut BTreeSet<(HashAlg, String)>) {
+    match v {
+        serde_json::Value::String(s) => {
+            if let Ok((alg, digest)) = parse_object_id(s) {
+                out.insert((alg, digest.to_string()));
+            }
+        }
+        serde_json::Value::Array(a) => a.iter().for_each(|x| collect_refs(x, out)),
+        serde_json::Value::Object(o) => o.values().for_each(|x| collect_refs(x, out)),
+        _ => {}
+    }
+}
@@ -1,6 +1,7 @@
 //! Deterministic storage primitives for SIGNIA.
 
 pub mod cache;
+pub mod gc;
 pub mod kv;
 pub mod objects;
 pub mod proofs;
@@ -9,15 +10,26 @@ use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
+use crate::gc::GcReport;
 use crate::kv::{Kv, KvBackend};
-use crate::objects::{ObjectStore, ObjectStoreBackend};
+use crate::objects::{format_object_id, parse_object_id, HashAlg, ObjectMeta, ObjectStore, ObjectStoreBackend};
+
+/// Default injected creation timestamp for object metadata.
+pub const DEFAULT_CREATED_AT: &str = "1970-01-01T00:00:00Z";
 
 #[derive(Debug, Clone)]
 pub struct StoreConfig {
     pub root_dir: PathBuf,
     pub kv_backend: KvBackend,
     pub object_backend: ObjectStoreBackend,
-    pub hash_alg: String,
+    /// Digest used to derive ids for new objects. Ids are prefixed with the
+    /// algorithm (`sha256:...`), so objects written under another algorithm
+    /// stay readable.
+    pub object_hash_alg: HashAlg,
+    /// Timestamp recorded in object metadata sidecars.
+    ///
+    /// Injected rather than read from the clock so that stores are reproducible.
+    pub created_at: String,
 }
 
 impl StoreConfig {
@@ -28,9 +40,20 @@ impl StoreConfig {
             root_dir: root,
             kv_backend: KvBackend::default(),
             object_backend: ObjectStoreBackend::default(),
-            hash_alg: "sha256".to_string(),
+            object_hash_alg: HashAlg::default(),
+            created_at: DEFAULT_CREATED_AT.to_string(),
         })
     }
+
+    pub fn with_object_hash_alg(mut self, alg: HashAlg) -> Self {
+        self.object_hash_alg = alg;
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: impl Into<String>) -> Self {
+        self.created_at = created_at.into();
+        self
+    }
 }
 
 pub struct Store {
@@ -58,12 +81,38 @@ impl Store {
         &self.objects
     }
 
+    /// Store an untyped object (recorded with kind "blob").
     pub fn put_object_bytes(&self, bytes: &[u8]) -> Result<String> {
-        self.objects.put_bytes(&self.cfg.hash_alg, bytes)
+        self.put_object(bytes, "blob")
+    }
+
+    /// Store an object and its metadata sidecar.
+    ///
+    /// Returns the prefixed object id (see `StoreConfig::object_hash_alg`).
+    pub fn put_object(&self, bytes: &[u8], kind: &str) -> Result<String> {
+        let alg = self.cfg.object_hash_alg;
+        let digest = self
+            .objects
+            .put_bytes_with_meta(alg.as_str(), bytes, kind, &self.cfg.created_at)?;
+        Ok(format_object_id(alg, &digest))
+    }
+
+    /// Load the metadata sidecar of an object, if the object exists.
+    pub fn get_meta(&self, id: &str) -> Result<Option<ObjectMeta>> {
+        let (alg, digest) = parse_object_id(id)?;
+        self.objects.get_meta(alg.as_str(), digest)
+    }
+
+    /// Delete objects not reachable from `roots` (see `gc` module docs).
+    ///
+    /// With `dry_run`, reports candidates without deleting anything.
+    pub fn gc(&self, roots: &[String], dry_run: bool) -> Result<GcReport> {
+        gc::run(self, roots, dry_run)
     }
 
     pub fn get_object_bytes(&self, id: &str) -> Result<Option<Vec<u8>>> {
-        self.objects.get_bytes(&self.cfg.hash_alg, id)
+        let (alg, digest) = parse_object_id(id)?;
+        self.objects.get_bytes(alg.as_str(), digest)
     }
 }
 
@@ -86,4 +135,94 @@ mod tests {
         let got_id: String = store.kv().get_json("k").unwrap().unwrap();
         assert_eq!(got_id, id);
     }
+
+    #[test]
+    fn object_meta_roundtrip() {
+        let td = TempDir::new().unwrap();
+        let cfg = StoreConfig::local_dev(td.path())
+            .unwrap()
+            .with_created_at("2024-01-02T03:04:05Z");
+        let store = Store::open(cfg).unwrap();
+
+        let bytes = br#"{"version":"v1"}"#;
+        let id = store.put_object(bytes, "schema").unwrap();
+
+        let meta = store.get_meta(&id).unwrap().unwrap();
+        assert_eq!(meta.kind, "schema");
+        assert_eq!(meta.size, bytes.len() as u64);
+        assert_eq!(meta.created_at, "2024-01-02T03:04:05Z");
+
+        // Untyped puts are recorded as blobs.
+        let blob = store.put_object_bytes(b"abc").unwrap();
+        assert_eq!(store.get_meta(&blob).unwrap().unwrap().kind, "blob");
+
+        // Unknown objects have no metadata.
+        assert!(store.get_meta(&"0".repeat(64)).unwrap().is_none());
+    }
+
+    #[test]
+    fn object_ids_depend_on_hash_alg() {
+        let td = TempDir::new().unwrap();
+        let sha = Store::open(StoreConfig::local_dev(td.path()).unwrap()).unwrap();
+        let b3 = Store::open(
+            StoreConfig::local_dev(td.path())
+                .unwrap()
+                .with_object_hash_alg(HashAlg::Blake3),
+        )
+        .unwrap();
+
+        let sha_id = sha.put_object_bytes(b"abc").unwrap();
+        let b3_id = b3.put_object_bytes(b"abc").unwrap();
+        assert_eq!(
+            sha_id,
+            "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            b3_id,
+            "blake3:6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+
+        // Ids can be derived without a store.
+        assert_eq!(objects::object_id(HashAlg::Sha256, b"abc").unwrap(), sha_id);
+        assert_eq!(objects::object_id(HashAlg::Blake3, b"abc").unwrap(), b3_id);
+
+        // Stable across puts, and readable from a store configured for the other alg.
+        assert_eq!(b3.put_object_bytes(b"abc").unwrap(), b3_id);
+        assert_eq!(sha.get_object_bytes(&b3_id).unwrap().unwrap(), b"abc");
+        assert_eq!(b3.get_object_bytes(&sha_id).unwrap().unwrap(), b"abc");
+
+        // Unprefixed ids are read as sha256.
+        let bare = sha_id.strip_prefix("sha256:").unwrap();
+        assert_eq!(sha.get_object_bytes(bare).unwrap().unwrap(), b"abc");
+        assert!(sha.get_object_bytes(&format!("md5:{bare}")).is_err());
+    }
+
+    #[test]
+    fn gc_keeps_reachable_objects() {
+        let td = TempDir::new().unwrap();
+        let store = Store::open(StoreConfig::local_dev(td.path()).unwrap()).unwrap();
+
+        let schema = store.put_object(br#"{"entities":[]}"#, "schema").unwrap();
+        let manifest = store
+            .put_object(format!(r#"{{"schemaObjectId":"{schema}"}}"#).as_bytes(), "manifest")
+            .unwrap();
+        let orphan = store.put_object(b"orphan", "blob").unwrap();
+
+        let roots = vec![manifest.clone()];
+        let dry = store.gc(&roots, true).unwrap();
+        assert_eq!(dry.reachable, 2);
+        assert_eq!(dry.candidates, vec![orphan.clone()]);
+        assert_eq!(dry.freed_bytes, 6);
+        assert_eq!(dry.deleted(), 0);
+        assert!(store.get_object_bytes(&orphan).unwrap().is_some());
+
+        let rep = store.gc(&roots, false).unwrap();
+        assert_eq!(rep.deleted(), 1);
+        assert!(store.get_object_bytes(&orphan).unwrap().is_none());
+        assert!(store.get_meta(&orphan).unwrap().is_none());
+        assert!(store.get_object_bytes(&schema).unwrap().is_some());
+        assert!(store.get_object_bytes(&manifest).unwrap().is_some());
+
+        assert!(store.gc(&[orphan], false).is_err());
+    }
 }
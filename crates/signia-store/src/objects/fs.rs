@@ -1,13 +1,14 @@
 //! Filesystem object store backend.
+//!
+//! Each object may have a JSON metadata sidecar at `<digest>.meta.json` next to it.
 
 use std::fs;
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use sha2::{Digest, Sha256};
 
-use super::{rooted_layout, validate_object_id, ObjectStoreImpl};
+use super::{digest_hex, rooted_layout, validate_object_id, ObjectMeta, ObjectStoreImpl};
 
 pub struct FsObjectStore {
     root: PathBuf,
@@ -25,32 +26,36 @@ impl FsObjectStore {
     }
 }
 
+fn meta_path(object_path: &Path) -> PathBuf {
+    object_path.with_extension("meta.json")
+}
+
+/// Write via a temp file and rename, so readers never see a partial file.
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    {
+        let mut f = fs::File::create(&tmp)?;
+        f.write_all(bytes)?;
+        f.sync_all()?;
+    }
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
 impl ObjectStoreImpl for FsObjectStore {
     fn put_bytes(&self, alg: &str, bytes: &[u8]) -> Result<String> {
-        let id = match alg {
-            "sha256" => {
-                let mut h = Sha256::new();
-                h.update(bytes);
-                hex::encode(h.finalize())
-            }
-            _ => anyhow::bail!("unsupported hash algorithm: {alg}"),
-        };
+        let id = digest_hex(alg, bytes)?;
 
         let path = rooted_layout(&self.root, alg, &id)?;
         if path.exists() {
             return Ok(id);
         }
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        let tmp = path.with_extension("tmp");
-        {
-            let mut f = fs::File::create(&tmp)?;
-            f.write_all(bytes)?;
-            f.sync_all()?;
-        }
-        fs::rename(&tmp, &path)?;
+        write_atomic(&path, bytes)?;
         Ok(id)
     }
 
@@ -70,4 +75,68 @@ impl ObjectStoreImpl for FsObjectStore {
         validate_object_id(id)?;
         Ok(rooted_layout(&self.root, alg, id)?.exists())
     }
+
+    fn put_bytes_with_meta(&self, alg: &str, bytes: &[u8], meta: &ObjectMeta) -> Result<String> {
+        let id = digest_hex(alg, bytes)?;
+        let path = rooted_layout(&self.root, alg, &id)?;
+        let mpath = meta_path(&path);
+
+        // Sidecar first: once the object is visible, its metadata is too.
+        if !mpath.exists() {
+            write_atomic(&mpath, &serde_json::to_vec(meta)?)?;
+        }
+        if !path.exists() {
+            write_atomic(&path, bytes)?;
+        }
+        Ok(id)
+    }
+
+    fn get_meta(&self, alg: &str, id: &str) -> Result<Option<ObjectMeta>> {
+        validate_object_id(id)?;
+        let path = rooted_layout(&self.root, alg, id)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let mpath = meta_path(&path);
+        if !mpath.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(&mpath)?)?))
+    }
+
+    fn list_ids(&self, alg: &str) -> Result<Vec<String>> {
+        let dir = self.root.join(alg);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut ids = Vec::new();
+        for entry in walkdir::WalkDir::new(&dir) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            // Sidecars and temp files are not objects.
+            if let Some(name) = entry.file_name().to_str() {
+                if validate_object_id(name).is_ok() {
+                    ids.push(name.to_string());
+                }
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    fn delete(&self, alg: &str, id: &str) -> Result<()> {
+        validate_object_id(id)?;
+        let path = rooted_layout(&self.root, alg, id)?;
+        // Object first, so a crash never leaves an object without its sidecar.
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        let mpath = meta_path(&path);
+        if mpath.exists() {
+            fs::remove_file(&mpath)?;
+        }
+        Ok(())
+    }
 }
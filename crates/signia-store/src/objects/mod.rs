@@ -9,6 +9,7 @@ mod s3;
 use std::path::Path;
 
 use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
 
 pub use fs::FsObjectStore;
 pub use layout::{ObjectKey, ObjectLayout};
@@ -29,6 +30,73 @@ impl Default for ObjectStoreBackend {
     }
 }
 
+/// Digest algorithm used to derive object ids.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HashAlg {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+impl HashAlg {
+    /// All supported algorithms, in a fixed order.
+    pub const ALL: [HashAlg; 2] = [HashAlg::Sha256, HashAlg::Blake3];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlg::Sha256 => "sha256",
+            HashAlg::Blake3 => "blake3",
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "sha256" => Ok(HashAlg::Sha256),
+            "blake3" => Ok(HashAlg::Blake3),
+            _ => Err(anyhow!("unsupported hash algorithm: {s}")),
+        }
+    }
+}
+
+/// Format a store-level object id: `<alg>:<digest>` (e.g. `sha256:ab12...`).
+pub fn format_object_id(alg: HashAlg, digest: &str) -> String {
+    format!("{}:{digest}", alg.as_str())
+}
+
+/// The object id `Store::put_object` assigns to `bytes` under `alg`, computed
+/// without storing anything.
+pub fn object_id(alg: HashAlg, bytes: &[u8]) -> Result<String> {
+    Ok(format_object_id(alg, &digest_hex(alg.as_str(), bytes)?))
+}
+
+/// Split a store-level object id into its algorithm and digest.
+///
+/// Unprefixed ids are read as sha256 digests, which is what stores wrote before
+/// ids carried an algorithm prefix.
+pub fn parse_object_id(id: &str) -> Result<(HashAlg, &str)> {
+    let (alg, digest) = match id.split_once(':') {
+        Some((alg, digest)) => (HashAlg::parse(alg)?, digest),
+        None => (HashAlg::Sha256, id),
+    };
+    validate_object_id(digest)?;
+    Ok((alg, digest))
+}
+
+/// Sidecar metadata stored next to each object.
+///
+/// `created_at` is supplied by the caller (see `StoreConfig::created_at`) rather than
+/// read from the system clock, so stores built in tests are reproducible.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectMeta {
+    /// Artifact kind (e.g. "schema", "manifest", "proof", "blob").
+    pub kind: String,
+    /// Object size in bytes.
+    pub size: u64,
+    /// Injected creation timestamp.
+    pub created_at: String,
+}
+
 pub struct ObjectStore {
     inner: Box<dyn ObjectStoreImpl + Send + Sync>,
 }
@@ -55,6 +123,27 @@ impl ObjectStore {
         self.inner.get_bytes(alg, id)
     }
 
+    pub fn put_bytes_with_meta(&self, alg: &str, bytes: &[u8], kind: &str, created_at: &str) -> Result<String> {
+        let meta = ObjectMeta {
+            kind: kind.to_string(),
+            size: bytes.len() as u64,
+            created_at: created_at.to_string(),
+        };
+        self.inner.put_bytes_with_meta(alg, bytes, &meta)
+    }
+
+    pub fn get_meta(&self, alg: &str, id: &str) -> Result<Option<ObjectMeta>> {
+        self.inner.get_meta(alg, id)
+    }
+
+    pub fn list_ids(&self, alg: &str) -> Result<Vec<String>> {
+        self.inner.list_ids(alg)
+    }
+
+    pub fn delete(&self, alg: &str, id: &str) -> Result<()> {
+        self.inner.delete(alg, id)
+    }
+
     pub fn exists(&self, alg: &str, id: &str) -> Result<bool> {
         self.inner.exists(alg, id)
     }
@@ -64,6 +153,31 @@ pub trait ObjectStoreImpl {
     fn put_bytes(&self, alg: &str, bytes: &[u8]) -> Result<String>;
     fn get_bytes(&self, alg: &str, id: &str) -> Result<Option<Vec<u8>>>;
     fn exists(&self, alg: &str, id: &str) -> Result<bool>;
+    /// Store an object together with its metadata sidecar.
+    ///
+    /// The sidecar is written before the object, so an object that exists always
+    /// has metadata. If the object already exists, its sidecar is left unchanged
+    /// unless missing.
+    fn put_bytes_with_meta(&self, alg: &str, bytes: &[u8], meta: &ObjectMeta) -> Result<String>;
+    fn get_meta(&self, alg: &str, id: &str) -> Result<Option<ObjectMeta>>;
+    /// List all object ids for an algorithm, sorted.
+    fn list_ids(&self, alg: &str) -> Result<Vec<String>>;
+    /// Delete an object and its sidecar. Deleting a missing object is not an error.
+    fn delete(&self, alg: &str, id: &str) -> Result<()>;
+}
+
+fn digest_hex(alg: &str, bytes: &[u8]) -> Result<String> {
+    use sha2::{Digest, Sha256};
+
+    match alg {
+        "sha256" => {
+            let mut h = Sha256::new();
+            h.update(bytes);
+            Ok(hex::encode(h.finalize()))
+        }
+        "blake3" => Ok(blake3::hash(bytes).to_hex().to_string()),
+        _ => anyhow::bail!("unsupported hash algorithm: {alg}"),
+    }
 }
 
 pub fn validate_object_id(id: &str) -> Result<()> {
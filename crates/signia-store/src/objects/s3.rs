@@ -10,7 +10,7 @@ use aws_sdk_s3::{primitives::ByteStream, Client};
 use bytes::Bytes;
 use sha2::{Digest, Sha256};
 
-use super::{ObjectStoreImpl, validate_object_id};
+use super::{digest_hex, ObjectMeta, ObjectStoreImpl, validate_object_id};
 
 static RT: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
 
@@ -45,6 +45,61 @@ impl S3ObjectStore {
             format!("{}/{alg}/{id}", self.prefix)
         }
     }
+
+    fn meta_key(&self, alg: &str, id: &str) -> String {
+        format!("{}.meta.json", self.key(alg, id))
+    }
+
+    fn put_key(&self, key: String, bytes: &[u8]) -> Result<()> {
+        let bucket = self.bucket.clone();
+        let client = self.client.clone();
+        let body = ByteStream::from(Bytes::copy_from_slice(bytes));
+
+        rt().block_on(async move {
+            client.put_object().bucket(bucket).key(key).body(body).send().await?;
+            Ok::<(), anyhow::Error>(())
+        })
+    }
+
+    fn key_exists(&self, key: String) -> Result<bool> {
+        let bucket = self.bucket.clone();
+        let client = self.client.clone();
+
+        rt().block_on(async move {
+            let resp = client.head_object().bucket(bucket).key(key).send().await;
+            match resp {
+                Ok(_) => Ok::<bool, anyhow::Error>(true),
+                Err(e) => {
+                    let msg = format!("{e}");
+                    if msg.contains("NotFound") || msg.contains("NoSuchKey") {
+                        Ok(false)
+                    } else {
+                        Err(anyhow::anyhow!(e))
+                    }
+                }
+            }
+        })
+    }
+
+    fn get_key(&self, key: String) -> Result<Option<Vec<u8>>> {
+        let bucket = self.bucket.clone();
+        let client = self.client.clone();
+
+        rt().block_on(async move {
+            let resp = client.get_object().bucket(bucket).key(key).send().await;
+            match resp {
+                Ok(r) => Ok::<Option<Vec<u8>>, anyhow::Error>(Some(r.body.collect().await?.into_bytes().to_vec())),
+                Err(e) => {
+                    let msg = format!("{e}");
+                    if msg.contains("NotFound") || msg.contains("NoSuchKey") {
+                        Ok(None)
+                    } else {
+                        Err(anyhow::anyhow!(e))
+                    }
+                }
+            }
+        })
+    }
 }
 
 impl ObjectStoreImpl for S3ObjectStore {
@@ -96,24 +151,81 @@ impl ObjectStoreImpl for S3ObjectStore {
 
     fn exists(&self, alg: &str, id: &str) -> Result<bool> {
         validate_object_id(id)?;
-        let key = self.key(alg, id);
+        self.key_exists(self.key(alg, id))
+    }
+
+    fn put_bytes_with_meta(&self, alg: &str, bytes: &[u8], meta: &ObjectMeta) -> Result<String> {
+        let id = digest_hex(alg, bytes)?;
+
+        // Sidecar first: once the object is visible, its metadata is too.
+        // Like the FS backend, the first write wins; re-puts keep the original metadata.
+        let meta_key = self.meta_key(alg, &id);
+        if !self.key_exists(meta_key.clone())? {
+            self.put_key(meta_key, &serde_json::to_vec(meta)?)?;
+        }
+        let key = self.key(alg, &id);
+        if !self.key_exists(key.clone())? {
+            self.put_key(key, bytes)?;
+        }
+        Ok(id)
+    }
+
+    fn get_meta(&self, alg: &str, id: &str) -> Result<Option<ObjectMeta>> {
+        validate_object_id(id)?;
+        if !self.exists(alg, id)? {
+            return Ok(None);
+        }
+        match self.get_key(self.meta_key(alg, id))? {
+            Some(b) => Ok(Some(serde_json::from_slice(&b)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn list_ids(&self, alg: &str) -> Result<Vec<String>> {
+        let prefix = self.key(alg, "");
         let bucket = self.bucket.clone();
         let client = self.client.clone();
 
-        let ok = rt().block_on(async move {
-            let resp = client.head_object().bucket(bucket).key(key).send().await;
-            match resp {
-                Ok(_) => Ok::<bool, anyhow::Error>(true),
-                Err(e) => {
-                    let msg = format!("{e}");
-                    if msg.contains("NotFound") || msg.contains("NoSuchKey") {
-                        Ok(false)
-                    } else {
-                        Err(anyhow::anyhow!(e))
+        let keys = rt().block_on(async move {
+            let mut keys = Vec::new();
+            let mut token: Option<String> = None;
+            loop {
+                let resp = client
+                    .list_objects_v2()
+                    .bucket(bucket.clone())
+                    .prefix(prefix.clone())
+                    .set_continuation_token(token.take())
+                    .send()
+                    .await?;
+                for o in resp.contents() {
+                    if let Some(k) = o.key() {
+                        keys.push(k[prefix.len()..].to_string());
                     }
                 }
+                match resp.next_continuation_token() {
+                    Some(t) => token = Some(t.to_string()),
+                    None => break,
+                }
             }
+            Ok::<Vec<String>, anyhow::Error>(keys)
         })?;
-        Ok(ok)
+
+        let mut ids: Vec<String> = keys.into_iter().filter(|k| validate_object_id(k).is_ok()).collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    fn delete(&self, alg: &str, id: &str) -> Result<()> {
+        validate_object_id(id)?;
+        // Object first, so a failure never leaves an object without its sidecar.
+        for key in [self.key(alg, id), self.meta_key(alg, id)] {
+            let bucket = self.bucket.clone();
+            let client = self.client.clone();
+            rt().block_on(async move {
+                client.delete_object().bucket(bucket).key(key).send().await?;
+                Ok::<(), anyhow::Error>(())
+            })?;
+        }
+        Ok(())
     }
 }
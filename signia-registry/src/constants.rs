@@ -1,7 +1,8 @@
 pub const SEED_REGISTRY: &[u8] = b"signia:registry";
 pub const SEED_ENTRY: &[u8] = b"signia:entry";
 
-pub const MAX_NAMESPACE_LEN: usize = 64;
+/// Namespaces are used directly as PDA seeds, which are capped at 32 bytes.
+pub const MAX_NAMESPACE_LEN: usize = 32;
 pub const MAX_KIND_LEN: usize = 32;
 pub const MAX_URI_LEN: usize = 256;
 pub const MAX_VERSION_TAG_LEN: usize = 32;
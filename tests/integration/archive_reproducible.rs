@@ -0,0 +1,68 @@
+//! archive_reproducible.rs
+//!
+//! Compiles the same input twice with `--archive tar` and `--archive zip` and
+//! checks that the archives are byte-identical (hence identical sha256).
+//!
+//! `SOURCE_DATE_EPOCH` pins the manifest timestamp; all archive entry metadata
+//! (mtime, uid/gid, permissions, order) is fixed by the exporter itself.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+fn compile_archive(bin: &Path, input: &Path, format: &str, out: &Path) -> Vec<u8> {
+    let _ = fs::remove_dir_all(out);
+    fs::create_dir_all(out).unwrap();
+
+    let status = Command::new(bin)
+        .env("SOURCE_DATE_EPOCH", "0")
+        .arg("compile")
+        .arg(input)
+        .arg("--kind").arg("dataset")
+        .arg("--out").arg(out)
+        .arg("--archive").arg(format)
+        .status()
+        .expect("failed to spawn signia");
+    assert!(status.success(), "signia compile --archive {format} failed");
+
+    let p = out.join(format!("bundle.{format}"));
+    fs::read(&p).unwrap_or_else(|e| panic!("failed to read {}: {e}", p.display()))
+}
+
+#[test]
+fn archives_are_byte_identical_across_runs() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp");
+    fs::create_dir_all(&tmp).unwrap();
+    let input = tmp.join("signia_test_archive_dataset.json");
+    fs::write(&input, r#"{"name":"sample","version":"v1","files":[{"path":"sample.csv","size":42}]}"#).unwrap();
+
+    for format in ["tar", "zip"] {
+        let a = compile_archive(&bin, &input, format, &tmp.join(format!("signia_test_archive_{format}_1")));
+        let b = compile_archive(&bin, &input, format, &tmp.join(format!("signia_test_archive_{format}_2")));
+        assert!(!a.is_empty());
+        assert_eq!(a, b, "{format} archive differs between runs");
+    }
+}
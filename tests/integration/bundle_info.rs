@@ -0,0 +1,91 @@
+//! bundle_info.rs
+//!
+//! `signia bundle-info <dir>` over a compiled bundle must report the object ids
+//! `compile` printed and the digests recorded in `bundle.json`, and must refuse
+//! a bundle whose artifacts no longer match `bundle.json`.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+/// Extract a scalar field from pretty-printed JSON.
+fn field<'a>(json: &'a str, key: &str) -> &'a str {
+    let needle = format!("\"{key}\": ");
+    let start = json.find(&needle).unwrap_or_else(|| panic!("missing field {key} in {json}")) + needle.len();
+    let rest = &json[start..];
+    let end = rest.find([',', '\n']).unwrap_or(rest.len());
+    rest[..end].trim().trim_matches('"')
+}
+
+#[test]
+fn bundle_info_describes_compiled_bundle() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_bundle_info");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+
+    let input = tmp.join("repo.json");
+    fs::write(&input, r#"{"name":"demo","files":[{"path":"README.md"}]}"#).unwrap();
+    let out_dir = tmp.join("out");
+
+    let compiled = Command::new(&bin)
+        .arg("--json")
+        .arg("--store-root").arg(tmp.join("store"))
+        .arg("compile")
+        .arg(&input)
+        .arg("--out").arg(&out_dir)
+        .output()
+        .expect("failed to spawn signia");
+    assert!(compiled.status.success(), "signia compile failed: {}", String::from_utf8_lossy(&compiled.stderr));
+    let compiled = String::from_utf8(compiled.stdout).expect("stdout is not UTF-8");
+
+    let out = Command::new(&bin)
+        .arg("--json")
+        .arg("bundle-info")
+        .arg(&out_dir)
+        .output()
+        .expect("failed to spawn signia");
+    assert!(out.status.success(), "signia bundle-info failed: {}", String::from_utf8_lossy(&out.stderr));
+    let info = String::from_utf8(out.stdout).expect("stdout is not UTF-8");
+
+    assert_eq!(field(&info, "version"), "v1");
+    assert_eq!(field(&info, "schemaId"), field(&compiled, "schema_id"));
+    assert_eq!(field(&info, "manifestId"), field(&compiled, "manifest_id"));
+    assert_eq!(field(&info, "proofId"), field(&compiled, "proof_id"));
+    let recorded = fs::read_to_string(out_dir.join("bundle.json")).unwrap();
+    for name in ["schema.json", "manifest.json", "proof.json"] {
+        assert_eq!(field(&info, name), field(&recorded, name), "{name} digest");
+    }
+
+    // Edit an artifact after compiling: bundle.json is now stale.
+    fs::write(out_dir.join("manifest.json"), "{}").unwrap();
+    let out = Command::new(&bin)
+        .arg("bundle-info")
+        .arg(&out_dir)
+        .output()
+        .expect("failed to spawn signia");
+    assert!(!out.status.success(), "bundle-info accepted a stale bundle.json");
+    assert!(String::from_utf8_lossy(&out.stderr).contains("manifestId"));
+}
@@ -0,0 +1,72 @@
+//! compile_bundle_info.rs
+//!
+//! `signia compile --out <dir>` must write `bundle.json` next to the three
+//! artifacts, listing the same object ids the command reports and one digest
+//! per artifact file.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+/// Extract a scalar field from pretty-printed JSON.
+fn field<'a>(json: &'a str, key: &str) -> &'a str {
+    let needle = format!("\"{key}\": ");
+    let start = json.find(&needle).unwrap_or_else(|| panic!("missing field {key} in {json}")) + needle.len();
+    let rest = &json[start..];
+    let end = rest.find([',', '\n']).unwrap_or(rest.len());
+    rest[..end].trim().trim_matches('"')
+}
+
+#[test]
+fn compile_writes_bundle_info_with_artifact_ids() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_compile_bundle_info");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+
+    let input = tmp.join("repo.json");
+    fs::write(&input, r#"{"name":"demo","files":[{"path":"README.md"}]}"#).unwrap();
+    let out_dir = tmp.join("out");
+
+    let out = Command::new(&bin)
+        .arg("--json")
+        .arg("--store-root").arg(tmp.join("store"))
+        .arg("compile")
+        .arg(&input)
+        .arg("--out").arg(&out_dir)
+        .output()
+        .expect("failed to spawn signia");
+    assert!(out.status.success(), "signia compile failed: {}", String::from_utf8_lossy(&out.stderr));
+    let stdout = String::from_utf8(out.stdout).expect("stdout is not UTF-8");
+
+    let info = fs::read_to_string(out_dir.join("bundle.json")).expect("bundle.json was not written");
+    assert_eq!(field(&info, "version"), "v1");
+    assert_eq!(field(&info, "schemaId"), field(&stdout, "schema_id"));
+    assert_eq!(field(&info, "manifestId"), field(&stdout, "manifest_id"));
+    assert_eq!(field(&info, "proofId"), field(&stdout, "proof_id"));
+    for name in ["schema.json", "manifest.json", "proof.json"] {
+        assert_eq!(field(&info, name).len(), 64, "{name} digest in {info}");
+    }
+}
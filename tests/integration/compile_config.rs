@@ -0,0 +1,75 @@
+//! compile_config.rs
+//!
+//! `signia compile --config FILE` must configure the builtin plugins: a repo
+//! `exclude` pattern drops files from the schema, and `max_files` rejects
+//! inputs with more files.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+fn compile(bin: &Path, tmp: &Path, name: &str, config: Option<&str>) -> Output {
+    let input = tmp.join("repo.json");
+    let mut cmd = Command::new(bin);
+    cmd.arg("--store-root").arg(tmp.join("store"))
+        .arg("compile")
+        .arg(&input)
+        .arg("--out").arg(tmp.join(name));
+    if let Some(config) = config {
+        let path = tmp.join(format!("{name}.json"));
+        fs::write(&path, config).unwrap();
+        cmd.arg("--config").arg(path);
+    }
+    cmd.output().expect("failed to spawn signia")
+}
+
+#[test]
+fn config_file_changes_compile_output() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_compile_config");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+    fs::write(
+        tmp.join("repo.json"),
+        r#"{"name":"demo","files":[{"path":"README.md"},{"path":"src/lib.rs"}]}"#,
+    )
+    .unwrap();
+
+    let out = compile(&bin, &tmp, "default", None);
+    assert!(out.status.success(), "signia compile failed: {}", String::from_utf8_lossy(&out.stderr));
+    let schema = fs::read_to_string(tmp.join("default").join("schema.json")).unwrap();
+    assert!(schema.contains("src/lib.rs"), "{schema}");
+
+    let out = compile(&bin, &tmp, "excluded", Some(r#"{"repo":{"exclude":["src/**"]}}"#));
+    assert!(out.status.success(), "signia compile failed: {}", String::from_utf8_lossy(&out.stderr));
+    let schema = fs::read_to_string(tmp.join("excluded").join("schema.json")).unwrap();
+    assert!(!schema.contains("src/lib.rs"), "{schema}");
+    assert!(schema.contains("README.md"), "{schema}");
+
+    let out = compile(&bin, &tmp, "limited", Some(r#"{"repo":{"max_files":1}}"#));
+    assert!(!out.status.success(), "max_files 1 must reject a two-file repo");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("max_files 1"), "{stderr}");
+}
@@ -0,0 +1,95 @@
+//! compile_created_at.rs
+//!
+//! Two compiles with the same `--created-at` must write byte-identical bundles,
+//! and a malformed timestamp must be rejected before anything is written.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+fn compile(bin: &Path, input: &Path, out: &Path, store: &Path, created_at: &str) -> Output {
+    let _ = fs::remove_dir_all(out);
+    Command::new(bin)
+        .env_remove("SOURCE_DATE_EPOCH")
+        .arg("--store-root").arg(store)
+        .arg("compile")
+        .arg(input)
+        .arg("--out").arg(out)
+        .arg("--created-at").arg(created_at)
+        .output()
+        .expect("failed to spawn signia")
+}
+
+fn read_bundle(dir: &Path) -> Vec<Vec<u8>> {
+    ["schema.json", "manifest.json", "proof.json"]
+        .iter()
+        .map(|f| fs::read(dir.join(f)).unwrap_or_else(|e| panic!("failed to read {f}: {e}")))
+        .collect()
+}
+
+#[test]
+fn same_created_at_gives_identical_bundles() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_compile_created_at");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+
+    let input = tmp.join("repo.json");
+    fs::write(&input, r#"{"name":"demo","files":[{"path":"README.md"},{"path":"src/lib.rs"}]}"#).unwrap();
+    let store = tmp.join("store");
+
+    let mut bundles = Vec::new();
+    for run in ["a", "b"] {
+        let out_dir = tmp.join(run);
+        let out = compile(&bin, &input, &out_dir, &store, "2024-01-01T00:00:00Z");
+        assert!(out.status.success(), "signia compile failed: {}", String::from_utf8_lossy(&out.stderr));
+        bundles.push(read_bundle(&out_dir));
+    }
+    assert_eq!(bundles[0], bundles[1], "bundles differ between runs");
+
+    let schema = String::from_utf8(bundles[0][0].clone()).unwrap();
+    assert!(schema.contains("\"createdAt\": \"2024-01-01T00:00:00Z\""), "{schema}");
+}
+
+#[test]
+fn malformed_created_at_is_rejected() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_compile_created_at_bad");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+
+    let input = tmp.join("repo.json");
+    fs::write(&input, r#"{"name":"demo","files":[{"path":"README.md"}]}"#).unwrap();
+    let out_dir = tmp.join("out");
+
+    let out = compile(&bin, &input, &out_dir, &tmp.join("store"), "not-a-date");
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--created-at"));
+    assert!(!out_dir.exists(), "output directory must not be created");
+}
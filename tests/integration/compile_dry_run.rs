@@ -0,0 +1,63 @@
+//! compile_dry_run.rs
+//!
+//! `signia compile --dry-run` must report stats without touching the store or
+//! writing the output directory.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+#[test]
+fn dry_run_writes_nothing() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_compile_dry_run");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+
+    let input = tmp.join("repo.json");
+    fs::write(&input, r#"{"name":"demo","files":[{"path":"README.md"},{"path":"src/lib.rs"}]}"#).unwrap();
+    let out_dir = tmp.join("out");
+    let store = tmp.join("store");
+
+    let out = Command::new(&bin)
+        .arg("--json")
+        .arg("--store-root").arg(&store)
+        .arg("compile")
+        .arg(&input)
+        .arg("--out").arg(&out_dir)
+        .arg("--dry-run")
+        .output()
+        .expect("failed to spawn signia");
+    assert!(out.status.success(), "signia compile --dry-run failed: {}", String::from_utf8_lossy(&out.stderr));
+
+    let stdout = String::from_utf8(out.stdout).expect("stdout is not UTF-8");
+    assert!(stdout.contains("\"dry_run\": true"), "{stdout}");
+    assert!(stdout.contains("\"nodes\": 3"), "{stdout}");
+    assert!(stdout.contains("\"edges\": 2"), "{stdout}");
+    assert!(stdout.contains("\"kind\": \"repo\""), "{stdout}");
+
+    assert!(!out_dir.exists(), "dry run created the output directory");
+    assert!(!store.exists(), "dry run created the store");
+}
@@ -0,0 +1,60 @@
+//! compile_emit_ir.rs
+//!
+//! `signia compile --emit-ir <file>` must write the plugin's IR graph as JSON
+//! alongside the normal bundle.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+//! - Round-tripping the JSON into an equal `IrGraph` is tested in signia-core (`model::ir`).
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+#[test]
+fn emit_ir_writes_graph_json() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_compile_emit_ir");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+
+    let input = tmp.join("repo.json");
+    fs::write(&input, r#"{"name":"demo","files":[{"path":"README.md"},{"path":"src/lib.rs"}]}"#).unwrap();
+    let ir_path = tmp.join("ir.json");
+
+    let out = Command::new(&bin)
+        .arg("--store-root").arg(tmp.join("store"))
+        .arg("compile")
+        .arg(&input)
+        .arg("--out").arg(tmp.join("out"))
+        .arg("--emit-ir").arg(&ir_path)
+        .output()
+        .expect("failed to spawn signia");
+    assert!(out.status.success(), "signia compile --emit-ir failed: {}", String::from_utf8_lossy(&out.stderr));
+
+    let ir = fs::read_to_string(&ir_path).expect("IR file was not written");
+    assert!(ir.contains("\"irVersion\": \"ir.v1\""), "{ir}");
+    assert!(ir.contains("\"nodes\": ["), "{ir}");
+    assert!(ir.contains("\"edges\": ["), "{ir}");
+    // 3 nodes + 2 edges, each with a stable key.
+    assert_eq!(ir.matches("\"key\":").count(), 5, "{ir}");
+}
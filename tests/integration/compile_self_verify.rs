@@ -0,0 +1,59 @@
+//! compile_self_verify.rs
+//!
+//! Runs `signia compile --verify`, which verifies the emitted bundle in-process
+//! before exiting. A non-zero exit status means the self-check failed.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+//! - Corruption cases are covered by unit tests in `signia-cli` (`cmd::compile`).
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+#[test]
+fn compile_with_verify_succeeds() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_compile_self_verify");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+
+    let input = tmp.join("dataset.json");
+    fs::write(&input, r#"{"name":"sample","version":"v1","files":[{"path":"sample.csv","size":42}]}"#).unwrap();
+    let out = tmp.join("out");
+
+    let status = Command::new(&bin)
+        .arg("--store-root").arg(tmp.join("store"))
+        .arg("compile")
+        .arg(&input)
+        .arg("--kind").arg("dataset")
+        .arg("--out").arg(&out)
+        .arg("--verify")
+        .status()
+        .expect("failed to spawn signia");
+    assert!(status.success(), "signia compile --verify failed");
+
+    for name in ["schema.json", "manifest.json", "proof.json"] {
+        let artifact = fs::read_to_string(out.join(name)).unwrap_or_else(|_| panic!("missing bundle file: {name}"));
+        assert!(artifact.contains("\"version\": \"v1\""), "{name} is not a v1 artifact: {artifact}");
+    }
+}
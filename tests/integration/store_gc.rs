@@ -0,0 +1,103 @@
+//! store_gc.rs
+//!
+//! Compiles several bundles into one store, then runs `signia gc` with only the
+//! first bundle's proof as a root. The proof references its manifest, which
+//! references its schema, so exactly those three objects must survive.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+fn run_json(bin: &Path, store: &Path, args: &[&str]) -> String {
+    let out = Command::new(bin)
+        .arg("--json")
+        .arg("--store-root").arg(store)
+        .args(args)
+        .output()
+        .expect("failed to spawn signia");
+    assert!(out.status.success(), "signia {:?} failed: {}", args, String::from_utf8_lossy(&out.stderr));
+    String::from_utf8(out.stdout).expect("stdout is not UTF-8")
+}
+
+/// Extract a top-level scalar field from the CLI's pretty-printed JSON output.
+fn field<'a>(json: &'a str, key: &str) -> &'a str {
+    let needle = format!("\"{key}\": ");
+    let start = json.find(&needle).unwrap_or_else(|| panic!("missing field {key} in {json}")) + needle.len();
+    let rest = &json[start..];
+    let end = rest.find([',', '\n']).unwrap_or(rest.len());
+    rest[..end].trim().trim_matches('"')
+}
+
+/// Count the object ids listed in the `candidates` array.
+fn candidate_count(json: &str) -> usize {
+    let Some(start) = json.find("\"candidates\": [") else { return 0 };
+    let rest = &json[start..];
+    let end = rest.find(']').unwrap();
+    rest[..end].matches('"').count() / 2 - 1
+}
+
+#[test]
+fn gc_keeps_only_reachable_bundle() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_store_gc");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+    let store = tmp.join("store");
+
+    let mut compiled = Vec::new();
+    for name in ["a", "b", "c"] {
+        let input = tmp.join(format!("{name}.json"));
+        fs::write(&input, format!(r#"{{"name":"{name}","files":[{{"path":"{name}.txt"}}]}}"#)).unwrap();
+        let out_dir = tmp.join(format!("out_{name}"));
+        compiled.push(run_json(
+            &bin,
+            &store,
+            &["compile", input.to_str().unwrap(), "--kind", "repo", "--out", out_dir.to_str().unwrap()],
+        ));
+    }
+
+    let keep = &compiled[0];
+    let root = field(keep, "proof_id");
+
+    let dry = run_json(&bin, &store, &["gc", root, "--dry-run"]);
+    assert_eq!(field(&dry, "reachable"), "3");
+    assert_eq!(field(&dry, "deleted"), "0");
+    assert_eq!(candidate_count(&dry), 6);
+
+    let rep = run_json(&bin, &store, &["gc", root]);
+    assert_eq!(field(&rep, "deleted"), "6");
+    assert_ne!(field(&rep, "freed_bytes"), "0");
+
+    for f in ["schema_id", "manifest_id", "proof_id"] {
+        run_json(&bin, &store, &["fetch", field(keep, f)]);
+    }
+    let gone = field(&compiled[1], "proof_id");
+    let status = Command::new(&bin)
+        .arg("--store-root").arg(&store)
+        .args(["fetch", gone])
+        .status()
+        .unwrap();
+    assert!(!status.success(), "unreachable proof should have been deleted");
+}
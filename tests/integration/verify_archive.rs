@@ -0,0 +1,75 @@
+//! verify_archive.rs
+//!
+//! Compiles a bundle with `--archive`, then runs `signia verify --archive` on it,
+//! which extracts the artifacts in memory and runs `verify_bundle`.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+//! - Malformed archive handling is covered by unit tests in `signia-cli` (`io::input`).
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+#[test]
+fn verify_compiled_archive() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_verify_archive");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+
+    let input = tmp.join("dataset.json");
+    fs::write(&input, r#"{"name":"sample","version":"v1","files":[{"path":"sample.csv","size":42}]}"#).unwrap();
+
+    for format in ["tar", "zip"] {
+        let out = tmp.join(format!("out_{format}"));
+
+        let status = Command::new(&bin)
+            .arg("--store-root").arg(tmp.join("store"))
+            .arg("compile")
+            .arg(&input)
+            .arg("--kind").arg("dataset")
+            .arg("--out").arg(&out)
+            .arg("--archive").arg(format)
+            .status()
+            .expect("failed to spawn signia");
+        assert!(status.success(), "signia compile --archive {format} failed");
+
+        let archive = out.join(format!("bundle.{format}"));
+        let status = Command::new(&bin)
+            .arg("verify")
+            .arg("--archive").arg(&archive)
+            .status()
+            .expect("failed to spawn signia");
+        assert!(status.success(), "signia verify --archive failed for {format}");
+    }
+
+    // A file that is not an archive must fail cleanly.
+    let junk = tmp.join("junk.tar");
+    fs::write(&junk, b"not an archive").unwrap();
+    let status = Command::new(&bin)
+        .arg("verify")
+        .arg("--archive").arg(&junk)
+        .status()
+        .expect("failed to spawn signia");
+    assert!(!status.success());
+}
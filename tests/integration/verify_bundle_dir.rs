@@ -0,0 +1,67 @@
+//! verify_bundle_dir.rs
+//!
+//! Compiles a bundle with `--out`, then runs `signia verify --bundle` on the
+//! directory, which loads each artifact with `load_artifact` and runs
+//! `verify_bundle`. An edited artifact must fail verification.
+//!
+//! Notes:
+//! - The CLI path can be overridden via SIGNIA_BIN.
+//! - If the CLI binary is not found, the test is skipped.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
+
+fn signia_bin() -> Option<PathBuf> {
+    if let Ok(p) = env::var("SIGNIA_BIN") {
+        let pb = PathBuf::from(p);
+        if pb.exists() { return Some(pb); }
+    }
+    let p = repo_root().join("target").join("debug").join(if cfg!(windows) { "signia.exe" } else { "signia" });
+    if p.exists() { Some(p) } else { None }
+}
+
+fn verify_bundle(bin: &Path, dir: &Path) -> bool {
+    Command::new(bin)
+        .arg("verify")
+        .arg("--bundle").arg(dir)
+        .status()
+        .expect("failed to spawn signia")
+        .success()
+}
+
+#[test]
+fn verify_compiled_bundle_dir() {
+    let Some(bin) = signia_bin() else {
+        eprintln!("skip: signia CLI not found (set SIGNIA_BIN or build signia-cli)");
+        return;
+    };
+
+    let tmp = repo_root().join("target").join("tmp").join("signia_test_verify_bundle_dir");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+
+    let input = tmp.join("repo.json");
+    fs::write(&input, r#"{"name":"demo","files":[{"path":"README.md"},{"path":"src/lib.rs"}]}"#).unwrap();
+    let out = tmp.join("out");
+
+    let status = Command::new(&bin)
+        .arg("--store-root").arg(tmp.join("store"))
+        .arg("compile")
+        .arg(&input)
+        .arg("--out").arg(&out)
+        .status()
+        .expect("failed to spawn signia");
+    assert!(status.success(), "signia compile failed");
+    assert!(verify_bundle(&bin, &out), "signia verify --bundle failed on compile output");
+
+    let schema = out.join("schema.json");
+    let edited = fs::read_to_string(&schema).unwrap().replace("README.md", "READYOU.md");
+    fs::write(&schema, edited).unwrap();
+    assert!(!verify_bundle(&bin, &out), "edited bundle must not verify");
+}